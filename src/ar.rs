@@ -0,0 +1,276 @@
+//! In-process reader for static libraries (`ar` archives). This allows the
+//! symbols of static libraries to be read without invoking the
+//! [nm](https://sourceware.org/binutils/docs/binutils/nm.html) utility.
+//!
+//! Both the GNU/System V variant (used by GNU ar and on Linux) and the BSD
+//! variant (used on macOS and by `llvm-ar --format=bsd`) are supported
+//! including their symbol index (armap) and long member names. Thin archives
+//! are not supported as their members aren't contained in the archive.
+
+use crate::error::{Error, ErrorKind};
+use std::convert::TryInto;
+use std::fs;
+use std::path::Path;
+
+#[cfg(test)]
+#[path = "./ar_tests.rs"]
+mod ar_tests;
+
+const AR_MAGIC: &[u8; 8] = b"!<arch>\n";
+const HEADER_SIZE: usize = 60;
+const HEADER_END: &[u8; 2] = b"`\n";
+
+/// A member (usually an object file) of an archive.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ArMember {
+    /// Name of the member with long names already resolved.
+    pub name: String,
+    /// Offset of the member header within the archive. The symbol index refers
+    /// to the members by this offset.
+    pub offset: usize,
+    /// Offset of the contents within the archive.
+    data_offset: usize,
+    /// Size in bytes of the contents.
+    pub size: usize,
+}
+
+/// A parsed `ar` archive.
+#[derive(Debug)]
+pub struct ArFile {
+    data: Vec<u8>,
+    members: Vec<ArMember>,
+    /// Symbol names of the symbol index with the header offset of the member
+    /// defining them.
+    symbols: Vec<(String, usize)>,
+}
+
+impl ArFile {
+    /// Reads and parses the archive at the given path. Returns an
+    /// [`ErrorKind::Io`] error if the file couldn't be read and an
+    /// [`ErrorKind::InvalidArchive`] error if it isn't a valid archive.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let data = fs::read(path.as_ref())?;
+        ArFile::parse(data)
+    }
+
+    /// Parses the archive contained in `data`. The special members for the
+    /// symbol index and the long names are consumed and not listed as
+    /// members.
+    pub fn parse(data: Vec<u8>) -> Result<Self, Error> {
+        if !data.starts_with(AR_MAGIC) {
+            return Err(Error::new(ErrorKind::InvalidArchive));
+        }
+
+        let mut ar = ArFile {
+            data,
+            members: Vec::new(),
+            symbols: Vec::new(),
+        };
+
+        // Contents of the GNU long names member ("//").
+        let mut long_names: Option<(usize, usize)> = None;
+        // Contents and format of the symbol index.
+        let mut armap: Option<(usize, usize, ArmapFormat)> = None;
+
+        let mut offset = AR_MAGIC.len();
+        while offset + HEADER_SIZE <= ar.data.len() {
+            let header = &ar.data[offset..offset + HEADER_SIZE];
+            if &header[58..60] != HEADER_END {
+                return Err(Error::new(ErrorKind::InvalidArchive));
+            }
+            let raw_name = field(&header[0..16]);
+            let size = field(&header[48..58])
+                .parse::<usize>()
+                .map_err(|e| Error::new(ErrorKind::InvalidArchive).with(e))?;
+            let mut data_offset = offset + HEADER_SIZE;
+            let mut data_size = size;
+            if data_offset + size > ar.data.len() {
+                return Err(Error::new(ErrorKind::InvalidArchive));
+            }
+
+            let name = if let Some(len) = raw_name.strip_prefix("#1/") {
+                // BSD: The name is stored in front of the contents.
+                let len = len
+                    .parse::<usize>()
+                    .map_err(|e| Error::new(ErrorKind::InvalidArchive).with(e))?;
+                if len > size {
+                    return Err(Error::new(ErrorKind::InvalidArchive));
+                }
+                let name = &ar.data[data_offset..data_offset + len];
+                let end = name.iter().position(|&b| b == 0).unwrap_or(len);
+                data_offset += len;
+                data_size -= len;
+                String::from_utf8_lossy(&name[..end]).into_owned()
+            } else if raw_name == "/" || raw_name == "/SYM64/" {
+                let format = if raw_name == "/" {
+                    ArmapFormat::Gnu32
+                } else {
+                    ArmapFormat::Gnu64
+                };
+                armap = Some((data_offset, data_size, format));
+                offset = next_header(data_offset + data_size);
+                continue;
+            } else if raw_name == "//" {
+                long_names = Some((data_offset, data_size));
+                offset = next_header(data_offset + data_size);
+                continue;
+            } else if let Some(idx) = raw_name
+                .strip_prefix('/')
+                .and_then(|idx| idx.parse::<usize>().ok())
+            {
+                // GNU: Offset into the long names member.
+                let (start, size) =
+                    long_names.ok_or_else(|| Error::new(ErrorKind::InvalidArchive))?;
+                let names = ar
+                    .data
+                    .get(start + idx..start + size)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidArchive))?;
+                let end = names
+                    .iter()
+                    .position(|&b| b == b'\n' || b == 0)
+                    .unwrap_or(names.len());
+                let name = String::from_utf8_lossy(&names[..end]);
+                String::from(name.trim_end_matches('/'))
+            } else {
+                String::from(raw_name.strip_suffix('/').unwrap_or(raw_name))
+            };
+
+            match name.as_str() {
+                "__.SYMDEF" | "__.SYMDEF SORTED" => {
+                    armap = Some((data_offset, data_size, ArmapFormat::Bsd32));
+                }
+                "__.SYMDEF_64" | "__.SYMDEF_64 SORTED" => {
+                    armap = Some((data_offset, data_size, ArmapFormat::Bsd64));
+                }
+                _ => ar.members.push(ArMember {
+                    name,
+                    offset,
+                    data_offset,
+                    size: data_size,
+                }),
+            }
+
+            offset = next_header(data_offset + data_size);
+        }
+
+        if let Some((start, size, format)) = armap {
+            ar.symbols = parse_armap(&ar.data[start..start + size], format)?;
+        }
+
+        Ok(ar)
+    }
+
+    /// Returns all members of the archive in the order they are stored.
+    pub fn members(&self) -> &[ArMember] {
+        &self.members
+    }
+
+    /// Returns the contents of the given member.
+    pub fn member_data(&self, member: &ArMember) -> &[u8] {
+        &self.data[member.data_offset..member.data_offset + member.size]
+    }
+
+    /// Returns the symbols listed in the symbol index (armap) of the archive
+    /// together with the member defining them. The index is created by the
+    /// archiver and only contains the global symbols. An archive without an
+    /// index results in an empty Vec.
+    pub fn symbols(&self) -> Vec<(&str, &ArMember)> {
+        self.symbols
+            .iter()
+            .filter_map(|(name, offset)| {
+                self.members
+                    .iter()
+                    .find(|m| m.offset == *offset)
+                    .map(|m| (name.as_str(), m))
+            })
+            .collect()
+    }
+}
+
+/// Formats of the symbol index.
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum ArmapFormat {
+    /// GNU/System V with 32-bit big-endian offsets (`/`).
+    Gnu32,
+    /// GNU/System V with 64-bit big-endian offsets (`/SYM64/`).
+    Gnu64,
+    /// BSD with 32-bit little-endian offsets (`__.SYMDEF`).
+    Bsd32,
+    /// BSD with 64-bit little-endian offsets (`__.SYMDEF_64`).
+    Bsd64,
+}
+
+/// Parses the contents of the symbol index. Returns the symbol names with the
+/// header offset of the member defining them.
+fn parse_armap(data: &[u8], format: ArmapFormat) -> Result<Vec<(String, usize)>, Error> {
+    let word = match format {
+        ArmapFormat::Gnu32 | ArmapFormat::Bsd32 => 4,
+        ArmapFormat::Gnu64 | ArmapFormat::Bsd64 => 8,
+    };
+    let read = |offset: usize| -> Result<usize, Error> {
+        let bytes = data
+            .get(offset..offset + word)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidArchive))?;
+        Ok(match format {
+            ArmapFormat::Gnu32 => u32::from_be_bytes(bytes.try_into().unwrap()) as usize,
+            ArmapFormat::Gnu64 => u64::from_be_bytes(bytes.try_into().unwrap()) as usize,
+            ArmapFormat::Bsd32 => u32::from_le_bytes(bytes.try_into().unwrap()) as usize,
+            ArmapFormat::Bsd64 => u64::from_le_bytes(bytes.try_into().unwrap()) as usize,
+        })
+    };
+    let str_at = |strtab: &[u8], offset: usize| -> Result<String, Error> {
+        let bytes = strtab
+            .get(offset..)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidArchive))?;
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    };
+
+    let mut symbols = Vec::new();
+    match format {
+        ArmapFormat::Gnu32 | ArmapFormat::Gnu64 => {
+            // Number of symbols, offsets, and the null-terminated names.
+            let count = read(0)?;
+            let strtab = data
+                .get(word * (count + 1)..)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidArchive))?;
+            let mut name_offset = 0;
+            for i in 0..count {
+                let name = str_at(strtab, name_offset)?;
+                name_offset += name.len() + 1;
+                symbols.push((name, read(word * (i + 1))?));
+            }
+        }
+        ArmapFormat::Bsd32 | ArmapFormat::Bsd64 => {
+            // Size of the entries (name offset, member offset) in bytes, the
+            // entries, size of the string table, and the string table.
+            let size = read(0)?;
+            let strtab_start = word + size + word;
+            let strtab_size = read(word + size)?;
+            let strtab = data
+                .get(strtab_start..strtab_start + strtab_size)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidArchive))?;
+            for i in 0..size / (2 * word) {
+                let entry = word + i * 2 * word;
+                symbols.push((str_at(strtab, read(entry)?)?, read(entry + word)?));
+            }
+        }
+    }
+
+    Ok(symbols)
+}
+
+/// Returns the contents of a header field without the padding.
+fn field(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes)
+        .unwrap_or("")
+        .trim_end_matches(' ')
+}
+
+/// Returns the offset of the next header. Members are aligned to 2 bytes.
+fn next_header(end: usize) -> usize {
+    end + (end % 2)
+}
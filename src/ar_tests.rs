@@ -0,0 +1,124 @@
+mod arfile_tests {
+    use super::super::*;
+
+    #[test]
+    fn open_not_found() {
+        let err = ArFile::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn open_not_archive() {
+        let err = ArFile::open("test_data/c_app/app").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArchive);
+    }
+
+    #[test]
+    fn parse_truncated() {
+        let mut data = fs::read("test_data/c_app_c_lib_rust_lib/libs/libc_lib.a").unwrap();
+        data.truncate(200);
+        let err = ArFile::parse(data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArchive);
+    }
+
+    #[test]
+    fn gnu() {
+        let ar = ArFile::open("test_data/c_app_c_lib_rust_lib/libs/libc_lib.a").unwrap();
+        assert_eq!(ar.members().len(), 1);
+        let member = &ar.members()[0];
+        assert_eq!(member.name, "c_lib.o");
+        assert_eq!(member.size, 1048);
+        assert_eq!(
+            ar.member_data(member),
+            fs::read("test_data/c_app_c_lib_rust_lib/libs/c_lib.o").unwrap()
+        );
+
+        let syms = ar.symbols();
+        assert_eq!(syms.len(), 2);
+        assert_eq!(syms[0].0, "c_add");
+        assert_eq!(syms[0].1.name, "c_lib.o");
+        assert_eq!(syms[1].0, "c_triple_mult");
+    }
+
+    #[test]
+    fn gnu_long_names() {
+        let ar = ArFile::open("test_data/ar/libringbuf.rlib").unwrap();
+        let names = ar
+            .members()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "lib.rmeta",
+                "libringbuf.ringbuf.dd73ddf035036b90-cgu.0.rcgu.o"
+            ]
+        );
+        assert!(ar.member_data(&ar.members()[1]).starts_with(b"\x7fELF"));
+
+        let syms = ar.symbols();
+        assert_eq!(syms.len(), 2);
+        assert!(syms
+            .iter()
+            .all(|(_, m)| m.name == "libringbuf.ringbuf.dd73ddf035036b90-cgu.0.rcgu.o"));
+        assert!(syms.iter().any(|(name, _)| *name == "ringbuf_checksum"));
+    }
+
+    #[test]
+    fn bsd() {
+        let ar = ArFile::open("test_data/ar/libbsd.a").unwrap();
+        let names = ar
+            .members()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["c_lib.o", "a_very_long_object_file_name.o", "lib.rmeta"]
+        );
+        assert_eq!(ar.member_data(&ar.members()[2]), b"metadata");
+        assert_eq!(ar.members()[1].size, 1048);
+
+        let syms = ar
+            .symbols()
+            .into_iter()
+            .map(|(name, m)| (name, m.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            syms,
+            vec![
+                ("c_add", "c_lib.o"),
+                ("c_triple_mult", "c_lib.o"),
+                ("c_add", "a_very_long_object_file_name.o"),
+                ("c_triple_mult", "a_very_long_object_file_name.o"),
+            ]
+        );
+    }
+}
+
+mod armap_tests {
+    use super::super::*;
+
+    #[test]
+    fn gnu64() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&2u64.to_be_bytes());
+        data.extend_from_slice(&8u64.to_be_bytes());
+        data.extend_from_slice(&100u64.to_be_bytes());
+        data.extend_from_slice(b"foo\0bar\0");
+        assert_eq!(
+            parse_armap(&data, ArmapFormat::Gnu64).unwrap(),
+            vec![(String::from("foo"), 8), (String::from("bar"), 100)]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&2u32.to_be_bytes());
+        data.extend_from_slice(&8u32.to_be_bytes());
+        let err = parse_armap(&data, ArmapFormat::Gnu32).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArchive);
+    }
+}
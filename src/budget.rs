@@ -0,0 +1,320 @@
+//! Check the memory usage against a budget file for gating builds in CI. The
+//! budget file is written in TOML and declares limits for the total ROM/RAM
+//! usage, the usage of every language, and the size of individual symbols.
+//!
+//! ```toml
+//! [total]
+//! rom = "256 KiB"
+//! ram = "64 KiB"
+//!
+//! [lang.rust]
+//! rom = "64 KiB"
+//!
+//! [[symbol]]
+//! pattern = "^z_main_stack$"
+//! max = 4096
+//! ```
+//!
+//! Sizes are either given in bytes or as strings with a unit (e.g. `"4 KiB"`).
+//! Symbol patterns are regular expressions matched against the demangled name
+//! of every symbol.
+
+use crate::error::{Error, ErrorKind};
+use crate::report::LangReport;
+use crate::sym::{MemoryRegion, Symbol, SymbolLang};
+use bytesize::ByteSize;
+use prettytable::{format, Table};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+#[cfg(test)]
+#[path = "./budget_tests.rs"]
+mod budget_tests;
+
+/// The entity whose size is limited by the budget.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Limit {
+    /// Usage of all languages and the unattributed bytes in the memory region,
+    /// i.e. the actual size of the image.
+    Total(MemoryRegion),
+    /// Usage of a single language in the memory region.
+    Lang(SymbolLang, MemoryRegion),
+    /// Size of every symbol whose demangled name matches the pattern.
+    Symbol(String),
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Limit::Total(region) => write!(f, "Total {}", region),
+            Limit::Lang(lang, region) => write!(f, "{} {}", lang, region),
+            Limit::Symbol(pattern) => write!(f, "Symbol /{}/", pattern),
+        }
+    }
+}
+
+/// Limits read from a budget file.
+#[derive(Debug)]
+pub struct Budget {
+    /// Maximum sizes in bytes of the totals and languages.
+    limits: Vec<(Limit, u64)>,
+    /// Compiled symbol patterns with the maximum size in bytes.
+    syms: Vec<(Regex, u64)>,
+}
+
+impl Budget {
+    /// Reads and parses the budget file at the given path. Returns an
+    /// [`ErrorKind::Io`] error if the file couldn't be read. See
+    /// [`Budget::parse`] for the other errors.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let data = fs::read(path.as_ref())?;
+        let s = std::str::from_utf8(&data)
+            .map_err(|str_error| Error::new(ErrorKind::InvalidBudget).with(str_error))?;
+        Budget::parse(s)
+    }
+
+    /// Parses the content of a budget file. Returns an
+    /// [`ErrorKind::InvalidBudget`] error if the TOML is invalid or contains
+    /// unknown languages, sizes, or patterns.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let file: BudgetFile =
+            toml::from_str(s).map_err(|e| Error::new(ErrorKind::InvalidBudget).with(e))?;
+
+        let mut budget = Budget {
+            limits: Vec::new(),
+            syms: Vec::new(),
+        };
+
+        budget.add_regions(&file.total, Limit::Total)?;
+        for (name, regions) in &file.lang {
+            let lang = name
+                .parse::<SymbolLang>()
+                .map_err(|e| Error::new(ErrorKind::InvalidBudget).with(e))?;
+            budget.add_regions(regions, |region| match lang {
+                SymbolLang::Any => Limit::Total(region),
+                _ => Limit::Lang(lang, region),
+            })?;
+        }
+        for sym in &file.symbol {
+            let re = Regex::new(&sym.pattern)
+                .map_err(|e| Error::new(ErrorKind::InvalidBudget).with(e))?;
+            budget
+                .syms
+                .push((re, sym.max.bytes(ErrorKind::InvalidBudget)?));
+        }
+
+        Ok(budget)
+    }
+
+    /// Returns the limits of the totals and languages with their maximum size
+    /// in bytes followed by the limits of the symbol patterns.
+    pub fn limits(&self) -> Vec<(Limit, u64)> {
+        self.limits
+            .iter()
+            .cloned()
+            .chain(
+                self.syms
+                    .iter()
+                    .map(|(re, max)| (Limit::Symbol(String::from(re.as_str())), *max)),
+            )
+            .collect()
+    }
+
+    /// Checks the language report and the symbols against the budget.
+    /// This is intended to be called by the [`crate::Atlas::check`] method.
+    pub fn check(&self, lang_rep: &LangReport, syms: &[Symbol]) -> CheckReport {
+        let mut violations = Vec::new();
+
+        for (limit, max) in &self.limits {
+            let size = match limit {
+                Limit::Total(region) => {
+                    lang_rep.size(SymbolLang::Any, *region) + lang_rep.size_unattributed(*region)
+                }
+                Limit::Lang(lang, region) => lang_rep.size(*lang, *region),
+                Limit::Symbol(_) => unreachable!(),
+            };
+            if size.as_u64() > *max {
+                violations.push(Violation {
+                    limit: limit.clone(),
+                    symbol: None,
+                    size: size.as_u64(),
+                    max: *max,
+                });
+            }
+        }
+
+        for (re, max) in &self.syms {
+            let mut exceeded = syms
+                .iter()
+                .filter(|s| (s.size > *max) && re.is_match(&s.demangled))
+                .collect::<Vec<_>>();
+            exceeded.sort_by_key(|s| std::cmp::Reverse(s.size));
+            for s in exceeded {
+                violations.push(Violation {
+                    limit: Limit::Symbol(String::from(re.as_str())),
+                    symbol: Some(s.demangled.clone()),
+                    size: s.size,
+                    max: *max,
+                });
+            }
+        }
+
+        CheckReport { violations }
+    }
+
+    /// Adds the ROM and RAM limits of a table of the budget file.
+    fn add_regions<F>(&mut self, regions: &RegionLimits, limit: F) -> Result<(), Error>
+    where
+        F: Fn(MemoryRegion) -> Limit,
+    {
+        if let Some(rom) = &regions.rom {
+            self.limits.push((
+                limit(MemoryRegion::Rom),
+                rom.bytes(ErrorKind::InvalidBudget)?,
+            ));
+        }
+        if let Some(ram) = &regions.ram {
+            self.limits.push((
+                limit(MemoryRegion::Ram),
+                ram.bytes(ErrorKind::InvalidBudget)?,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A limit of the budget that has been exceeded.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Violation {
+    pub limit: Limit,
+    /// Demangled name of the symbol for limits of symbol patterns.
+    pub symbol: Option<String>,
+    /// Actual size in bytes.
+    pub size: u64,
+    /// Maximum size in bytes allowed by the budget.
+    pub max: u64,
+}
+
+impl Violation {
+    /// Returns how many bytes the size is over the limit.
+    pub fn over(&self) -> u64 {
+        self.size - self.max
+    }
+}
+
+/// Struct used for reporting the violations of a [`Budget`].
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    violations: Vec<Violation>,
+}
+
+impl CheckReport {
+    /// Returns all exceeded limits. Totals and languages are listed first
+    /// followed by the symbols (largest first) of every pattern.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Checks if all limits of the budget are met.
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Writes a table to the supplied `writer` with all the violations
+    /// including how far over the limit they are. Nothing is written if the
+    /// check passed.
+    pub fn print(&self, human_readable: bool, writer: &mut impl Write) -> Result<usize, Error> {
+        if self.passed() {
+            return Ok(0);
+        }
+
+        let mut table = Table::new();
+
+        let to_string = |size: u64| {
+            if human_readable {
+                ByteSize::b(size).to_string_as(true)
+            } else {
+                size.to_string()
+            }
+        };
+
+        for v in &self.violations {
+            let _ = table.add_row(row!(
+                v.limit.to_string(),
+                v.symbol.as_deref().unwrap_or(""),
+                to_string(v.size),
+                to_string(v.max),
+                to_string(v.over())
+            ));
+        }
+
+        table.set_titles(row![
+            "Limit",
+            "Symbol",
+            "Size [Bytes]",
+            "Max [Bytes]",
+            "Over [Bytes]"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+}
+
+/// Layout of the budget file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BudgetFile {
+    #[serde(default)]
+    total: RegionLimits,
+    #[serde(default)]
+    lang: BTreeMap<String, RegionLimits>,
+    #[serde(default)]
+    symbol: Vec<SymbolLimit>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegionLimits {
+    rom: Option<Size>,
+    ram: Option<Size>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SymbolLimit {
+    pattern: String,
+    max: Size,
+}
+
+/// A size in the budget file (or region file, see [`crate::regions`]) given
+/// in bytes or as a string with a unit.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Size {
+    Bytes(u64),
+    Str(String),
+}
+
+impl Size {
+    /// Returns the size in bytes. Strings with an invalid unit result in an
+    /// error of the given kind.
+    pub(crate) fn bytes(&self, kind: ErrorKind) -> Result<u64, Error> {
+        match self {
+            Size::Bytes(b) => Ok(*b),
+            Size::Str(s) => s
+                .trim()
+                .parse::<ByteSize>()
+                .map(|size| size.as_u64())
+                .map_err(|e| Error::new(kind).with(e)),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+mod budget_parse_tests {
+    use super::super::*;
+
+    #[test]
+    fn open_not_found() {
+        let err = Budget::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn open() {
+        let budget = Budget::open("test_data/budget/c_app_lma.toml").unwrap();
+        assert_eq!(budget.limits().len(), 5);
+    }
+
+    #[test]
+    fn parse() {
+        let budget = Budget::parse(
+            r#"
+            [total]
+            rom = "256 KiB"
+            ram = 65536
+
+            [lang.rust]
+            rom = "64 KiB"
+
+            [lang.Cpp]
+            ram = "1.5 KiB"
+
+            [[symbol]]
+            pattern = "^z_main_stack$"
+            max = "4 KiB"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            budget.limits(),
+            vec![
+                (Limit::Total(MemoryRegion::Rom), 256 * 1024),
+                (Limit::Total(MemoryRegion::Ram), 65536),
+                (Limit::Lang(SymbolLang::Cpp, MemoryRegion::Ram), 1536),
+                (Limit::Lang(SymbolLang::Rust, MemoryRegion::Rom), 64 * 1024),
+                (Limit::Symbol(String::from("^z_main_stack$")), 4096),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_any_lang() {
+        let budget = Budget::parse("[lang.any]\nrom = 10").unwrap();
+        assert_eq!(budget.limits(), vec![(Limit::Total(MemoryRegion::Rom), 10)]);
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!(Budget::parse("").unwrap().limits().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let invalid = [
+            "[total]\nrom = ",
+            "[total]\nflash = 10",
+            "[lang.java]\nrom = 10",
+            "[total]\nrom = \"10 apples\"",
+            "[[symbol]]\npattern = \"(\"\nmax = 10",
+            "[[symbol]]\npattern = \"foo\"",
+        ];
+        for s in invalid {
+            let err = Budget::parse(s).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidBudget, "{}", s);
+        }
+    }
+}
+
+mod budget_check_tests {
+    use super::super::*;
+    use crate::report::CombinedMem;
+
+    fn create_test_data() -> (LangReport, Vec<Symbol>) {
+        let lang_rep = LangReport::new(
+            CombinedMem::new(100, 50),
+            CombinedMem::new(20, 10),
+            CombinedMem::new(30, 0),
+            CombinedMem::default(),
+        );
+        let syms = [
+            "00001000 00000010 T small",
+            "00001010 00000028 T large_1",
+            "00001038 00000030 T large_2",
+        ]
+        .iter()
+        .map(|l| Symbol::from_rawsymbols_lang(*l, *l, SymbolLang::C).unwrap())
+        .collect();
+        (lang_rep, syms)
+    }
+
+    #[test]
+    fn passed() {
+        let (lang_rep, syms) = create_test_data();
+        let budget = Budget::parse(
+            "[total]\nrom = 150\nram = 60\n[lang.rust]\nrom = 30\n\
+             [[symbol]]\npattern = \".*\"\nmax = 48",
+        )
+        .unwrap();
+        let report = budget.check(&lang_rep, &syms);
+        assert!(report.passed());
+        assert!(report.violations().is_empty());
+
+        let mut writer = Vec::new();
+        assert_eq!(report.print(false, &mut writer).unwrap(), 0);
+        assert!(writer.is_empty());
+    }
+
+    #[test]
+    fn violations() {
+        let (lang_rep, syms) = create_test_data();
+        let budget = Budget::parse(
+            "[total]\nrom = 149\n[lang.c]\nram = 50\n[lang.cpp]\nram = 8\n\
+             [[symbol]]\npattern = \"^large\"\nmax = 32",
+        )
+        .unwrap();
+        let report = budget.check(&lang_rep, &syms);
+        assert!(!report.passed());
+        assert_eq!(
+            report.violations(),
+            &[
+                Violation {
+                    limit: Limit::Total(MemoryRegion::Rom),
+                    symbol: None,
+                    size: 150,
+                    max: 149,
+                },
+                Violation {
+                    limit: Limit::Lang(SymbolLang::Cpp, MemoryRegion::Ram),
+                    symbol: None,
+                    size: 10,
+                    max: 8,
+                },
+                Violation {
+                    limit: Limit::Symbol(String::from("^large")),
+                    symbol: Some(String::from("large_2")),
+                    size: 48,
+                    max: 32,
+                },
+                Violation {
+                    limit: Limit::Symbol(String::from("^large")),
+                    symbol: Some(String::from("large_1")),
+                    size: 40,
+                    max: 32,
+                },
+            ]
+        );
+        assert_eq!(report.violations()[0].over(), 1);
+        assert_eq!(report.violations()[2].over(), 16);
+    }
+
+    #[test]
+    fn total_unattributed() {
+        let (lang_rep, syms) = create_test_data();
+        let lang_rep = lang_rep.with_unattributed(CombinedMem::new(10, 0));
+        let budget = Budget::parse("[total]\nrom = 155\n[lang.any]\nram = 60").unwrap();
+        let report = budget.check(&lang_rep, &syms);
+        assert_eq!(
+            report.violations(),
+            &[Violation {
+                limit: Limit::Total(MemoryRegion::Rom),
+                symbol: None,
+                size: 160,
+                max: 155,
+            }]
+        );
+    }
+
+    #[test]
+    fn print() {
+        let (lang_rep, syms) = create_test_data();
+        let budget =
+            Budget::parse("[lang.c]\nrom = 90\n[[symbol]]\npattern = \"_2$\"\nmax = 0").unwrap();
+        let mut writer = Vec::new();
+        budget
+            .check(&lang_rep, &syms)
+            .print(false, &mut writer)
+            .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].starts_with(" C Rom "));
+        assert!(lines[2].trim_end().ends_with("| 10"));
+        assert!(lines[3].contains("| large_2 "));
+        assert!(lines[3].trim_end().ends_with("| 48"));
+    }
+}
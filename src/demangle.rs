@@ -0,0 +1,453 @@
+//! In-process demangling of Rust (legacy and v0) and Cpp (Itanium ABI) symbol
+//! names. Deriving the demangled name directly from the mangled one makes the
+//! analysis independent of the demangler version of the nm utility.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+#[cfg(test)]
+#[path = "./demangle_tests.rs"]
+mod demangle_tests;
+
+lazy_static! {
+    // Path elements that aren't preceded by another one (i.e. crates).
+    static ref CRATE: Regex = Regex::new(r"(?:^|[^A-Za-z0-9_:])([A-Za-z_][A-Za-z0-9_]*)::").unwrap();
+}
+
+/// A list of mangling schemes that can be recognized in a symbol name.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ManglingScheme {
+    /// The name isn't mangled (e.g., C symbols or `#[no_mangle]` Rust
+    /// functions).
+    None,
+    /// Legacy Rust mangling (`_ZN...17h<hash>E`). This is based on the Itanium
+    /// scheme but always ends with a hash of the symbol.
+    RustLegacy,
+    /// Rust v0 mangling (`_R...`).
+    RustV0,
+    /// Cpp mangling according to the Itanium ABI (`_Z...`).
+    Itanium,
+}
+
+/// Determines the [`ManglingScheme`] of the given symbol name.
+///
+/// Legacy Rust symbols are valid Itanium symbols as well. They are told apart
+/// by the hash (`17h` followed by 16 hexadecimal digits) that rustc appends as
+/// the last path element.
+pub fn scheme(mangled: &str) -> ManglingScheme {
+    if let Ok(sym) = rustc_demangle::try_demangle(mangled) {
+        if strip_prefix(mangled).starts_with("_R") {
+            return ManglingScheme::RustV0;
+        }
+        // The alternate format only differs if the symbol contains a valid
+        // hash.
+        if sym.to_string() != format!("{:#}", sym) {
+            return ManglingScheme::RustLegacy;
+        }
+    }
+
+    if cpp_demangle::Symbol::new(mangled).is_ok() {
+        return ManglingScheme::Itanium;
+    }
+
+    ManglingScheme::None
+}
+
+/// Demangles Rust (legacy and v0) and Cpp symbol names. Like the nm utility,
+/// the hash of legacy Rust symbols and the crate disambiguators of v0 symbols
+/// are omitted. Names that aren't mangled (e.g. C symbols) or can't be
+/// demangled are returned unchanged.
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::demangle;
+/// assert_eq!(
+///     demangle("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"),
+///     "core::fmt::Formatter::pad"
+/// );
+/// assert_eq!(demangle("_ZN2ot8Instance3GetEv"), "ot::Instance::Get()");
+/// assert_eq!(demangle("z_main_stack"), "z_main_stack");
+/// ```
+pub fn demangle(mangled: &str) -> String {
+    match scheme(mangled) {
+        ManglingScheme::RustLegacy | ManglingScheme::RustV0 => {
+            // The alternate format omits the hashes.
+            format!("{:#}", rustc_demangle::demangle(mangled))
+        }
+        ManglingScheme::Itanium => cpp_demangle::Symbol::new(mangled)
+            .ok()
+            .and_then(|sym| sym.demangle(&cpp_demangle::DemangleOptions::default()).ok())
+            .unwrap_or_else(|| String::from(mangled)),
+        ManglingScheme::None => String::from(mangled),
+    }
+}
+
+/// Returns a name for the symbol that stays the same across builds as long as
+/// the symbol itself doesn't change. This is used for matching the symbols of
+/// two builds of the same application.
+///
+/// The hashes of legacy Rust symbols and the crate disambiguators of v0 symbols
+/// change whenever a dependency changes. Therefore, the demangled name without
+/// them is used for Rust symbols. The numeric suffixes that compilers append to
+/// local static variables (e.g. `completed.8911`) are removed as well.
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::normalize;
+/// assert_eq!(
+///     normalize("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"),
+///     normalize("_ZN4core3fmt9Formatter3pad17h0123456789abcdefE")
+/// );
+/// assert_eq!(normalize("completed.8911"), "completed");
+/// assert_eq!(normalize("_ZN2ot8Instance3GetEv"), "_ZN2ot8Instance3GetEv");
+/// ```
+pub fn normalize(mangled: &str) -> String {
+    match scheme(mangled) {
+        ManglingScheme::RustLegacy | ManglingScheme::RustV0 => demangle(mangled),
+        ManglingScheme::Itanium => String::from(mangled),
+        ManglingScheme::None => match mangled.rsplit_once('.') {
+            Some((name, suffix))
+                if !name.is_empty()
+                    && !suffix.is_empty()
+                    && suffix.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                String::from(name)
+            }
+            _ => String::from(mangled),
+        },
+    }
+}
+
+/// Returns the name of the crate a demangled Rust symbol belongs to. This is
+/// the first element of the path. For trait implementations (`<A as B>::f`),
+/// the crate of the implementing type is used unless it is a primitive or
+/// generic type without a path (e.g. `<*const T as core::fmt::Pointer>::fmt`
+/// belongs to `core`). Returns `None` if the name doesn't contain a path (e.g.
+/// `#[no_mangle]` functions).
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::crate_name;
+/// assert_eq!(crate_name("core::fmt::Formatter::pad"), Some("core"));
+/// assert_eq!(
+///     crate_name("<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop"),
+///     Some("alloc")
+/// );
+/// assert_eq!(crate_name("rust_mult"), None);
+/// ```
+pub fn crate_name(demangled: &str) -> Option<&str> {
+    CRATE
+        .captures(demangled)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str())
+}
+
+/// Crates of the Rust standard library. Generic code of these crates is
+/// usually instantiated by other crates.
+pub const STD_CRATES: [&str; 5] = ["core", "alloc", "std", "compiler_builtins", "panic_abort"];
+
+/// Returns the name of the crate whose types a generic Rust function was
+/// instantiated with. This is the first crate outside of the standard library
+/// (see [`STD_CRATES`]) whose path appears in the generic arguments or in the
+/// self type of a trait implementation. Returns `None` if there is no such
+/// crate (e.g. for functions that aren't generic or that were instantiated
+/// with primitive types). In this case, the defining crate (see
+/// [`crate_name`]) is the instantiating one as well.
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::instantiating_crate;
+/// assert_eq!(
+///     instantiating_crate("core::ptr::drop_in_place<heapless::vec::Vec<app::Reading, 4>>"),
+///     Some("heapless")
+/// );
+/// assert_eq!(
+///     instantiating_crate("<core::slice::Iter<app::Reading> as core::iter::Iterator>::next"),
+///     Some("app")
+/// );
+/// assert_eq!(instantiating_crate("core::ptr::drop_in_place<u8>"), None);
+/// ```
+pub fn instantiating_crate(demangled: &str) -> Option<&str> {
+    // Nesting depth of the brackets in front of every byte.
+    let mut depths = Vec::with_capacity(demangled.len());
+    let mut depth = 0_usize;
+    for (i, c) in demangled.bytes().enumerate() {
+        depths.push(depth);
+        match c {
+            b'<' | b'(' | b'[' => depth += 1,
+            b'>' if (i > 0) && (demangled.as_bytes()[i - 1] == b'-') => {}
+            b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    CRATE
+        .captures_iter(demangled)
+        .filter_map(|caps| caps.get(1))
+        .filter(|m| depths[m.start()] > 0)
+        .map(|m| m.as_str())
+        .find(|name| !STD_CRATES.contains(name))
+}
+
+/// Returns the name and the disambiguator of the crate a Rust v0 symbol
+/// belongs to (see [`crate_name`]). The disambiguator is derived from the
+/// metadata of the crate (e.g. its version) and differs if multiple versions
+/// of the same crate are linked into an application. Returns `None` for other
+/// symbols. Legacy Rust symbols only contain a hash of the whole symbol which
+/// can't be used for telling the crates apart.
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::crate_version;
+/// assert_eq!(
+///     crate_version("_RNvCsg9FFvDQaDFm_7ringbuf8checksum"),
+///     Some((String::from("ringbuf"), String::from("bc2dcf194c61c5f2")))
+/// );
+/// assert_eq!(crate_version("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"), None);
+/// ```
+pub fn crate_version(mangled: &str) -> Option<(String, String)> {
+    lazy_static! {
+        // Same as `CRATE` but including the disambiguator (`ringbuf[1a2b]::`).
+        static ref VERSION: Regex =
+            Regex::new(r"(?:^|[^A-Za-z0-9_:\]])([A-Za-z_][A-Za-z0-9_]*)\[([0-9a-f]+)\]::").unwrap();
+    }
+
+    if scheme(mangled) != ManglingScheme::RustV0 {
+        return None;
+    }
+
+    // Unlike the alternate format, the default one includes the
+    // disambiguators.
+    let full = rustc_demangle::demangle(mangled).to_string();
+    VERSION
+        .captures(&full)
+        .map(|caps| (String::from(&caps[1]), String::from(&caps[2])))
+}
+
+/// Splits a demangled name into the elements of its path (e.g. namespaces,
+/// modules, types, and the function name). Separators within generic
+/// arguments, function parameters, or trait implementations (`<A as B>`) are
+/// ignored.
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::split_path;
+/// assert_eq!(
+///     split_path("ot::Mle::MleRouter::HandleAdvertisement(ot::Message const&)"),
+///     vec!["ot", "Mle", "MleRouter", "HandleAdvertisement(ot::Message const&)"]
+/// );
+/// assert_eq!(
+///     split_path("<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop"),
+///     vec!["<alloc::vec::Vec<T> as core::ops::drop::Drop>", "drop"]
+/// );
+/// ```
+pub fn split_path(demangled: &str) -> Vec<&str> {
+    let mut elements = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let bytes = demangled.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'<' | b'(' | b'[' => depth += 1,
+            // Unbalanced brackets (e.g. `operator<`) must not underflow.
+            b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+            b':' if (depth == 0) && (bytes.get(i + 1) == Some(&b':')) => {
+                elements.push(&demangled[start..i]);
+                start = i + 2;
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    elements.push(&demangled[start..]);
+
+    elements
+}
+
+/// Returns the name of the generic function (or static) that a Rust
+/// monomorphization or Cpp template instantiation was created from. The
+/// generic and template arguments are removed. For Cpp, the return type and
+/// the function parameters are removed as well because they usually depend on
+/// the template arguments. Returns `None` if the name doesn't contain generic
+/// arguments.
+///
+/// # Example
+/// ```
+/// # use atlas::demangle::generic_base;
+/// assert_eq!(
+///     generic_base("core::ptr::drop_in_place<alloc::string::String>"),
+///     Some(String::from("core::ptr::drop_in_place"))
+/// );
+/// assert_eq!(
+///     generic_base("int ot::Max<int>(int, int)"),
+///     Some(String::from("ot::Max"))
+/// );
+/// assert_eq!(generic_base("ot::Instance::Get()"), None);
+/// ```
+pub fn generic_base(demangled: &str) -> Option<String> {
+    // Special names of Cpp (e.g. `vtable for ot::Array<int>`) keep their
+    // prefix.
+    let (prefix, name) = CPP_SPECIAL_NAMES
+        .iter()
+        .find(|p| demangled.starts_with(*p))
+        .map(|p| demangled.split_at(p.len()))
+        .unwrap_or(("", demangled));
+
+    let stripped = strip_generic_args(name)?;
+    let stripped = strip_signature(&stripped);
+
+    Some(format!("{}{}", prefix, stripped))
+}
+
+/// Prefixes of the special names of the Itanium ABI (e.g. virtual tables).
+const CPP_SPECIAL_NAMES: [&str; 6] = [
+    "construction vtable for ",
+    "vtable for ",
+    "VTT for ",
+    "typeinfo name for ",
+    "typeinfo for ",
+    "guard variable for ",
+];
+
+/// Removes all generic and template arguments (`<...>` following a name) from
+/// the demangled name. Angle brackets that start a qualified path (e.g. `<A as
+/// B>::f`) are kept. Returns `None` if no arguments were removed.
+fn strip_generic_args(name: &str) -> Option<String> {
+    let bytes = name.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    // Nesting depth within the arguments being removed.
+    let mut skip = 0_usize;
+    let mut stripped = false;
+    // Length of the output right after the symbol of an operator.
+    let mut operator_end = usize::MAX;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let prev = if i > 0 { Some(bytes[i - 1]) } else { None };
+
+        if skip > 0 {
+            match c {
+                b'<' => skip += 1,
+                // `->` of function types (e.g. `Box<dyn Fn() -> u8>`).
+                b'>' if prev != Some(b'-') => skip -= 1,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        let len = operator_len(&out, &bytes[i..]);
+        if len > 0 {
+            out.extend_from_slice(&bytes[i..i + len]);
+            operator_end = out.len();
+            i += len;
+            continue;
+        }
+
+        if c == b'<' {
+            if out.len() == operator_end {
+                // Template arguments of an operator (`operator<<<int>`).
+                skip = 1;
+                stripped = true;
+            } else if out.ends_with(b"::") {
+                // Turbofish of Rust v0 symbols (`f::<u8>`).
+                out.truncate(out.len() - 2);
+                skip = 1;
+                stripped = true;
+            } else if matches!(out.last(), Some(b) if b.is_ascii_alphanumeric() || (*b == b'_') || (*b == b'}'))
+            {
+                skip = 1;
+                stripped = true;
+            } else {
+                out.push(c);
+            }
+        } else {
+            out.push(c);
+        }
+        i += 1;
+    }
+
+    if stripped {
+        // Only ASCII characters have been removed.
+        Some(String::from_utf8_lossy(&out).into_owned())
+    } else {
+        None
+    }
+}
+
+/// Removes the return type and the parameters (including qualifiers like
+/// `const`) of a demangled Cpp function. Names without them (e.g. Rust
+/// symbols or variables) are returned unchanged.
+fn strip_signature(name: &str) -> &str {
+    let bytes = name.as_bytes();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let mut end = bytes.len();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let len = operator_len(&bytes[..i], &bytes[i..]);
+        if (depth == 0) && (len > 0) {
+            i += len;
+            continue;
+        }
+
+        let c = bytes[i];
+        match c {
+            // Parameters directly follow the name of the function unlike
+            // `(anonymous namespace)`.
+            b'(' if (depth == 0)
+                && matches!(bytes[..i].last(), Some(b) if (*b != b':') && (*b != b' ')) =>
+            {
+                end = i;
+                break;
+            }
+            b'<' | b'(' | b'[' => depth += 1,
+            b'>' if (i > 0) && (bytes[i - 1] == b'-') => {}
+            b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+            // Spaces outside of brackets separate the return type.
+            b' ' if depth == 0 => start = i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    &name[start..end]
+}
+
+/// Returns the length of the symbol of a Cpp operator (e.g. `<<` or `()`) if
+/// `rest` starts with one and `before` ends with the `operator` keyword. The
+/// space of named operators (e.g. `operator new`) is treated as a symbol.
+fn operator_len(before: &[u8], rest: &[u8]) -> usize {
+    let keyword = before.ends_with(b"operator")
+        && !matches!(before.len().checked_sub(9).map(|i| before[i]), Some(b) if b.is_ascii_alphanumeric() || (b == b'_'));
+    if !keyword {
+        return 0;
+    }
+
+    // Longer operators first so that `<<=` isn't taken for `<`.
+    const OPERATORS: [&str; 40] = [
+        "<<=", ">>=", "<=>", "->*", "()", "[]", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||",
+        "++", "--", "->", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<", ">", "=", "!", "+",
+        "-", "*", "/", "%", "&", "|", "^", "~", ",", " ",
+    ];
+    OPERATORS
+        .iter()
+        .find(|op| rest.starts_with(op.as_bytes()))
+        .map(|op| op.len())
+        .unwrap_or(0)
+}
+
+/// Removes the additional leading underscore used on some platforms (e.g.
+/// `__ZN` on macOS).
+fn strip_prefix(mangled: &str) -> &str {
+    if mangled.starts_with("__Z") || mangled.starts_with("__R") {
+        &mangled[1..]
+    } else {
+        mangled
+    }
+}
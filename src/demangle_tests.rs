@@ -0,0 +1,356 @@
+mod scheme_tests {
+    use super::super::*;
+
+    #[test]
+    fn rust_legacy() {
+        assert_eq!(
+            scheme("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"),
+            ManglingScheme::RustLegacy
+        );
+        assert_eq!(
+            scheme("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E.llvm.1234567890"),
+            ManglingScheme::RustLegacy
+        );
+        assert_eq!(
+            scheme("__ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"),
+            ManglingScheme::RustLegacy
+        );
+    }
+
+    #[test]
+    fn rust_v0() {
+        assert_eq!(
+            scheme("_RNvCs15kBYyAo9fc_7mycrate7example"),
+            ManglingScheme::RustV0
+        );
+    }
+
+    #[test]
+    fn itanium() {
+        assert_eq!(scheme("_ZN2ot8Instance3GetEv"), ManglingScheme::Itanium);
+        // Valid legacy Rust syntax but without the trailing hash.
+        assert_eq!(scheme("_ZN2ot8Instance3GetE"), ManglingScheme::Itanium);
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(scheme("z_main_stack"), ManglingScheme::None);
+        assert_eq!(scheme("completed.8911"), ManglingScheme::None);
+        assert_eq!(scheme(""), ManglingScheme::None);
+    }
+}
+
+mod demangle_fn_tests {
+    use super::super::*;
+
+    #[test]
+    fn rust_legacy() {
+        assert_eq!(
+            demangle("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"),
+            "core::fmt::Formatter::pad"
+        );
+        assert_eq!(
+            demangle("_ZN54_$LT$$BP$const$u20$T$u20$as$u20$core..fmt..Pointer$GT$3fmt17hde7d70127d765717E"),
+            "<*const T as core::fmt::Pointer>::fmt"
+        );
+    }
+
+    #[test]
+    fn rust_v0() {
+        assert_eq!(
+            demangle("_RNvCs15kBYyAo9fc_7mycrate7example"),
+            "mycrate::example"
+        );
+    }
+
+    #[test]
+    fn itanium() {
+        assert_eq!(
+            demangle("_ZN2ot3Mle9MleRouter19HandleAdvertisementERKNS_7MessageERKNS_3Ip611MessageInfoEPNS_8NeighborE"),
+            "ot::Mle::MleRouter::HandleAdvertisement(ot::Message const&, ot::Ip6::MessageInfo const&, ot::Neighbor*)"
+        );
+    }
+
+    #[test]
+    fn unmangled() {
+        assert_eq!(demangle("z_main_stack"), "z_main_stack");
+        assert_eq!(demangle("_ZN"), "_ZN");
+    }
+}
+
+mod normalize_tests {
+    use super::super::*;
+
+    #[test]
+    fn rust_hash() {
+        assert_eq!(
+            normalize("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E"),
+            "core::fmt::Formatter::pad"
+        );
+        assert_eq!(
+            normalize("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E.llvm.1234567890"),
+            normalize("_ZN4core3fmt9Formatter3pad17hde7d70127d765717E")
+        );
+        assert_eq!(
+            normalize("_RNvCs15kBYyAo9fc_7mycrate7example"),
+            normalize("_RNvCs1234567890_7mycrate7example")
+        );
+    }
+
+    #[test]
+    fn local_suffix() {
+        assert_eq!(normalize("completed.8911"), "completed");
+        assert_eq!(normalize("completed.0"), "completed");
+        assert_eq!(normalize(".1234"), ".1234");
+        assert_eq!(normalize("lib.rs"), "lib.rs");
+    }
+
+    #[test]
+    fn unchanged() {
+        assert_eq!(normalize("z_main_stack"), "z_main_stack");
+        assert_eq!(normalize("_ZN2ot8Instance3GetEv"), "_ZN2ot8Instance3GetEv");
+    }
+}
+
+mod crate_name_tests {
+    use super::super::*;
+
+    #[test]
+    fn path() {
+        assert_eq!(crate_name("core::fmt::Formatter::pad"), Some("core"));
+        assert_eq!(
+            crate_name("my_crate::net::coap::{{closure}}"),
+            Some("my_crate")
+        );
+        assert_eq!(
+            crate_name("core::ptr::drop_in_place<alloc::string::String>"),
+            Some("core")
+        );
+    }
+
+    #[test]
+    fn trait_impl() {
+        assert_eq!(
+            crate_name("<heapless::vec::Vec<T, N> as core::ops::deref::Deref>::deref"),
+            Some("heapless")
+        );
+        assert_eq!(
+            crate_name("<*const T as core::fmt::Pointer>::fmt"),
+            Some("core")
+        );
+        assert_eq!(
+            crate_name("<&mut W as core::fmt::Write>::write_str"),
+            Some("core")
+        );
+        assert_eq!(crate_name("<[T]>::sort"), None);
+    }
+
+    #[test]
+    fn no_path() {
+        assert_eq!(crate_name("rust_mult"), None);
+        assert_eq!(crate_name(""), None);
+    }
+}
+
+mod instantiating_crate_tests {
+    use super::super::*;
+
+    #[test]
+    fn generic_args() {
+        assert_eq!(
+            instantiating_crate(
+                "core::ptr::drop_in_place<core::slice::sort::CopyOnDrop<app::Reading>>"
+            ),
+            Some("app")
+        );
+        assert_eq!(
+            instantiating_crate("core::slice::sort::quicksort::<app::Reading, <[app::Reading]>::sort_unstable_by<app::main::{closure#0}>::{closure#0}>"),
+            Some("app")
+        );
+    }
+
+    #[test]
+    fn self_type() {
+        assert_eq!(
+            instantiating_crate(
+                "<&[app::Reading] as core::iter::traits::collect::IntoIterator>::into_iter"
+            ),
+            Some("app")
+        );
+        assert_eq!(
+            instantiating_crate("<heapless::vec::Vec<T, N> as core::ops::drop::Drop>::drop"),
+            Some("heapless")
+        );
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(instantiating_crate("core::fmt::Formatter::pad"), None);
+        assert_eq!(
+            instantiating_crate("core::ptr::drop_in_place<alloc::string::String>"),
+            None
+        );
+        assert_eq!(
+            instantiating_crate("<u16 as core::cmp::PartialOrd>::partial_cmp"),
+            None
+        );
+        assert_eq!(instantiating_crate("app::largest"), None);
+        assert_eq!(instantiating_crate("rust_mult"), None);
+    }
+}
+
+mod crate_version_tests {
+    use super::super::*;
+
+    #[test]
+    fn v0() {
+        assert_eq!(
+            crate_version("_RNvMCse29rZBteZmL_7ringbufNtB2_7RingBuf4push"),
+            Some((String::from("ringbuf"), String::from("a3789542a9d61af9")))
+        );
+        assert_eq!(
+            crate_version("_RNvMCsg9FFvDQaDFm_7ringbufNtB2_7RingBuf4push"),
+            Some((String::from("ringbuf"), String::from("bc2dcf194c61c5f2")))
+        );
+    }
+
+    #[test]
+    fn other() {
+        assert_eq!(
+            crate_version("_ZN7ringbuf8checksum17h78e340d3d2b7b109E"),
+            None
+        );
+        assert_eq!(crate_version("_ZN2ot8Instance3GetEv"), None);
+        assert_eq!(crate_version("rust_checksum"), None);
+    }
+}
+
+mod split_path_tests {
+    use super::super::*;
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            split_path("core::fmt::Formatter::pad"),
+            vec!["core", "fmt", "Formatter", "pad"]
+        );
+        assert_eq!(split_path("z_main_stack"), vec!["z_main_stack"]);
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(
+            split_path("core::ptr::drop_in_place<alloc::string::String>"),
+            vec!["core", "ptr", "drop_in_place<alloc::string::String>"]
+        );
+        assert_eq!(
+            split_path("ot::Mle::MleRouter::HandleAdvertisement(ot::Message const&, ot::Ip6::MessageInfo const&, ot::Neighbor*)"),
+            vec![
+                "ot",
+                "Mle",
+                "MleRouter",
+                "HandleAdvertisement(ot::Message const&, ot::Ip6::MessageInfo const&, ot::Neighbor*)"
+            ]
+        );
+        assert_eq!(
+            split_path("<[T] as core::fmt::Debug>::fmt"),
+            vec!["<[T] as core::fmt::Debug>", "fmt"]
+        );
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert_eq!(
+            split_path("ot::Foo::operator<(ot::Foo const&)"),
+            vec!["ot", "Foo", "operator<(ot::Foo const&)"]
+        );
+        assert_eq!(
+            split_path("ot::Foo::operator>(ot::Foo const&)"),
+            vec!["ot", "Foo", "operator>(ot::Foo const&)"]
+        );
+    }
+}
+
+mod generic_base_tests {
+    use super::super::*;
+
+    #[test]
+    fn rust() {
+        assert_eq!(
+            generic_base("core::ptr::drop_in_place<alloc::vec::Vec<u8>>").as_deref(),
+            Some("core::ptr::drop_in_place")
+        );
+        assert_eq!(
+            generic_base("<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop").as_deref(),
+            Some("<alloc::vec::Vec as core::ops::drop::Drop>::drop")
+        );
+        assert_eq!(
+            generic_base("heapless::vec::Vec<u8, 4>::push::<u8>").as_deref(),
+            Some("heapless::vec::Vec::push")
+        );
+        assert_eq!(
+            generic_base(
+                "core::ptr::drop_in_place<alloc::boxed::Box<dyn core::ops::function::Fn() -> u8>>"
+            )
+            .as_deref(),
+            Some("core::ptr::drop_in_place")
+        );
+        assert_eq!(
+            generic_base("<[T] as core::fmt::Debug>::fmt").as_deref(),
+            None
+        );
+        assert_eq!(generic_base("core::fmt::Formatter::pad").as_deref(), None);
+    }
+
+    #[test]
+    fn cpp() {
+        assert_eq!(
+            generic_base("ot::Array<unsigned char, 16u>::PushBack(unsigned char const&)")
+                .as_deref(),
+            Some("ot::Array::PushBack")
+        );
+        assert_eq!(
+            generic_base("unsigned long ot::Max<unsigned long>(unsigned long, unsigned long)")
+                .as_deref(),
+            Some("ot::Max")
+        );
+        assert_eq!(
+            generic_base("ot::Pool<ot::Message, 8u>::Get() const").as_deref(),
+            Some("ot::Pool::Get")
+        );
+        assert_eq!(
+            generic_base("void (anonymous namespace)::Log<int>(char const*)").as_deref(),
+            Some("(anonymous namespace)::Log")
+        );
+        assert_eq!(
+            generic_base("vtable for ot::LinkedList<ot::Timer>").as_deref(),
+            Some("vtable for ot::LinkedList")
+        );
+        assert_eq!(generic_base("ot::Instance::Get()").as_deref(), None);
+        assert_eq!(generic_base("z_main_stack").as_deref(), None);
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(
+            generic_base("bool ot::Set<int>::operator<(ot::Set<int> const&) const").as_deref(),
+            Some("ot::Set::operator<")
+        );
+        assert_eq!(
+            generic_base("ot::Callback<void>::operator()(int)").as_deref(),
+            Some("ot::Callback::operator()")
+        );
+        assert_eq!(
+            generic_base("void* ot::Heap<int>::operator new(unsigned int)").as_deref(),
+            Some("ot::Heap::operator new")
+        );
+        assert_eq!(
+            generic_base("ot::Stream& ot::operator<<<int>(ot::Stream&, int)").as_deref(),
+            Some("ot::operator<<")
+        );
+        assert_eq!(
+            generic_base("ot::Foo::operator<(ot::Foo const&)").as_deref(),
+            None
+        );
+    }
+}
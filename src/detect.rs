@@ -1,9 +1,14 @@
+use crate::ar::ArFile;
+use crate::dwarf::DwarfInfo;
+use crate::elf::ElfFile;
 use crate::error::{Error, ErrorKind};
 use crate::sym::{RawSymbol, Symbol, SymbolLang};
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
 use std::fs::File;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
+use std::str::FromStr;
 
 #[cfg(test)]
 #[path = "./detect_tests.rs"]
@@ -27,6 +32,91 @@ impl Library {
             lang,
         }
     }
+
+    /// Returns the language of the library.
+    pub fn lang(&self) -> SymbolLang {
+        self.lang
+    }
+
+    /// Returns the path to the library.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks if the path of an archive (e.g. as recorded in a linker map file)
+    /// refers to this library. Relative paths are relative to the directory the
+    /// linker was invoked in which is unknown. Therefore, only their trailing
+    /// components are compared.
+    pub fn matches_archive(&self, archive: &Path) -> bool {
+        matches_archive(&self.path, archive)
+    }
+}
+
+/// Checks if the path of an archive refers to the library at `path` (see
+/// [`Library::matches_archive`]).
+fn matches_archive(path: &Path, archive: &Path) -> bool {
+    if archive.is_absolute() {
+        return path == archive;
+    }
+
+    let normal = archive
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect::<PathBuf>();
+    path.ends_with(normal)
+}
+
+/// A symbol contained in more than one of the Rust libraries. Every Rust
+/// static library bundles its own copy of the runtime (`core`, `alloc`,
+/// `compiler_builtins`). If multiple of them are linked into an application,
+/// the runtime is either duplicated or all but one copy are silently shadowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeDuplicate {
+    pub mangled: String,
+    pub demangled: String,
+    /// Paths to the Rust libraries containing a copy of the symbol together
+    /// with the size of the copy in bytes.
+    pub libs: Vec<(PathBuf, u64)>,
+    /// Paths to the libraries whose copy has been linked into the ELF file.
+    /// The archive recorded in the map file is used if available (see
+    /// [`Symbol::archive`]). Otherwise, the copies are matched by their size.
+    pub kept: Vec<PathBuf>,
+    /// Sizes in bytes of the copies linked into the ELF file.
+    pub linked: Vec<u64>,
+    /// Set if more libraries contain a copy of the same size as a linked one
+    /// than copies of that size have been linked. All of them are listed in
+    /// `kept` as they can't be told apart.
+    pub ambiguous: bool,
+}
+
+impl RuntimeDuplicate {
+    /// Returns the number of bytes occupied by the additional copies linked
+    /// into the ELF file. This could be saved by linking a single copy of the
+    /// runtime (e.g. by combining the Rust libraries into a single one).
+    pub fn wasted(&self) -> u64 {
+        let max = self.linked.iter().max().copied().unwrap_or(0);
+        self.linked.iter().sum::<u64>() - max
+    }
+}
+
+/// Number of symbols of a library that have been linked into the ELF file. The
+/// remaining symbols have been dropped by the linker, either because they
+/// weren't referenced or by the garbage collection of unused sections
+/// (`--gc-sections`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedSymbols {
+    pub path: PathBuf,
+    /// Number of symbols defined in the library.
+    pub total: usize,
+    /// Number of symbols of the library found in the ELF file.
+    pub linked: usize,
+}
+
+impl LinkedSymbols {
+    /// Returns the number of symbols of the library that haven't been linked.
+    pub fn dropped(&self) -> usize {
+        self.total - self.linked
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -43,6 +133,7 @@ pub struct LangDetector {
     default_lang: SymbolLang,
     default_mangled_lang: SymbolLang,
     libs: Vec<ParsedLibrary>,
+    dwarf: Option<DwarfInfo>,
 }
 
 impl LangDetector {
@@ -55,12 +146,28 @@ impl LangDetector {
             default_lang,
             default_mangled_lang,
             libs: Vec::new(),
+            dwarf: None,
         }
     }
 
-    /// Parses and stores the symbols contained in the library with the supplied nm utility. This
-    /// can then be used by the [`detect`] method for determining if a symbol stems from a library
-    /// or not.
+    /// Sets the DWARF debugging information of the ELF file. The language of a
+    /// symbol is then taken from the compilation unit containing its address
+    /// if it couldn't be matched to any of the libraries. Symbols without
+    /// debugging information still fall back to the default languages.
+    pub fn set_dwarf(&mut self, dwarf: DwarfInfo) {
+        self.dwarf = Some(dwarf);
+    }
+
+    /// Returns the DWARF debugging information set with
+    /// [`LangDetector::set_dwarf`].
+    pub fn dwarf(&self) -> Option<&DwarfInfo> {
+        self.dwarf.as_ref()
+    }
+
+    /// Parses and stores the symbols contained in the library with the supplied nm utility. The
+    /// demangled names are derived in-process from the mangled ones. This can then be used by the
+    /// [`detect`] method for determining if a symbol stems from a library or not. The archive
+    /// member (object file) containing every symbol is recorded as well.
     ///
     /// [`detect`]: LangDetector::detect
     pub fn add_lib<T>(&mut self, nm: T, lib: &Library) -> Result<(), Error>
@@ -72,87 +179,178 @@ impl LangDetector {
         // ErrorKind::Nm error.
         let _ = File::open(&lib.path)?;
 
-        let mangled_out = Command::new(nm.as_ref())
+        let out = Command::new(nm.as_ref())
             .arg("--print-size")
             .arg(&lib.path)
             .output()
             .map_err(|io_error| Error::new(ErrorKind::Io).with(io_error))?;
 
-        if !mangled_out.status.success() {
+        if !out.status.success() {
             return Err(Error::new(ErrorKind::Nm));
         }
 
-        let mangled_str = std::str::from_utf8(&mangled_out.stdout)
+        let out_str = std::str::from_utf8(&out.stdout)
             .map_err(|str_error| Error::new(ErrorKind::Nm).with(str_error))?;
 
-        let demangled_out = Command::new(nm.as_ref())
-            .arg("--print-size")
-            .arg("--demangle")
-            .arg(&lib.path)
-            .output()
-            .map_err(|io_error| Error::new(ErrorKind::Io).with(io_error))?;
+        self.libs.push(ParsedLibrary {
+            path: lib.path.clone(),
+            lang: lib.lang,
+            syms: parse_lib_symbols(out_str),
+        });
 
-        if !demangled_out.status.success() {
-            return Err(Error::new(ErrorKind::Nm));
-        }
+        Ok(())
+    }
 
-        let demangled_str = std::str::from_utf8(&demangled_out.stdout)
-            .map_err(|str_error| Error::new(ErrorKind::Nm).with(str_error))?;
+    /// Same as [`add_lib`] but reads the library in-process (see [`ArFile`])
+    /// instead of invoking nm. Only the ELF object files of the archive are
+    /// parsed. Other members like the metadata (`lib.rmeta`) and LLVM bitcode
+    /// of Rust libraries are skipped. Returns an [`ErrorKind::InvalidArchive`]
+    /// error if the library isn't a valid archive.
+    ///
+    /// [`add_lib`]: LangDetector::add_lib
+    pub fn add_archive(&mut self, lib: &Library) -> Result<(), Error> {
+        let ar = ArFile::open(&lib.path)?;
 
-        let mut parsed_lib = ParsedLibrary {
+        let mut syms = Vec::new();
+        for member in ar.members() {
+            let elf = match ElfFile::parse(ar.member_data(member).to_vec()) {
+                Ok(elf) => elf,
+                Err(_) => continue,
+            };
+            // Object files without a symbol table don't define any symbols.
+            for raw in elf.raw_symbols().unwrap_or_default() {
+                let mut s = Symbol::from_rawsymbol(raw);
+                if is_lib_symbol(&s) {
+                    s.object = Some(member.name.clone());
+                    syms.push(s);
+                }
+            }
+        }
+
+        self.libs.push(ParsedLibrary {
             path: lib.path.clone(),
             lang: lib.lang,
-            syms: Vec::new(),
-        };
+            syms,
+        });
 
-        for (mangled, demangled) in mangled_str.lines().zip(demangled_str.lines()) {
-            let s = match Symbol::from_rawsymbols_lang(mangled, demangled, SymbolLang::Rust) {
-                Ok(s) => s,
-                // TODO:
-                // Differentiate between the various reasons for an error. Some
-                // might be expected (e.g lines like "mulvdi3.o:") while others
-                // should not fail and should inform the user.
-                Err(_) => continue,
-            };
+        Ok(())
+    }
 
-            // The symbols that have distinct mangled and demangled names are added to the parsed
-            // library without any further checks. Symbols, where the mangled and demangled names
-            // match, are further checked to be valid C identifiers. I.e., underscores, lower- or
-            // uppercase letters, or numbers (not allowed for the first character). Additionally,
-            // the dot "." character is also allowed as it seems to be used for symbols in RAM like
-            // "000194f0 00000018 b object.8916". This logic thus excludes symbols like
-            // ".Lanon.4575732b5f0a476c725a4805a4f03b6f.638" for example, which seem to be unused
-            // symbols from Rust static libraries.
-            if s.mangled == s.demangled {
-                // TODO:
-                // Rewrite this using a simple regex and check the performance
-                // difference
-                let mut chars = s.mangled.chars();
-                if let Some(c) = chars.next() {
-                    if matches!(c, 'a'..='z' | 'A'..='Z' | '_') {
-                        // TODO:
-                        // Reuse the iterator here?
-                        if s.mangled
-                            .chars()
-                            .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '.' | '0'..='9'))
-                        {
-                            parsed_lib.syms.push(s);
-                        }
-                    }
+    /// Finds the symbols that are contained in more than one of the added
+    /// Rust libraries (see [`RuntimeDuplicate`]) and determines which of the
+    /// copies have been linked into the ELF file containing `syms`. Symbols
+    /// appearing multiple times within the same library are only counted once
+    /// for that library.
+    ///
+    /// A linked copy is attributed to the library its archive (see
+    /// [`Symbol::archive`]) refers to. Copies without a matching archive are
+    /// attributed to the libraries containing a copy of the same size.
+    pub fn runtime_duplicates(&self, syms: &[Symbol]) -> Vec<RuntimeDuplicate> {
+        // Mangled name -> copies of the symbol with the library containing it
+        let mut copies: BTreeMap<&str, Vec<(&Path, &Symbol)>> = BTreeMap::new();
+        for lib in self.libs.iter().filter(|l| l.lang == SymbolLang::Rust) {
+            for s in lib.syms.iter() {
+                let libs = copies.entry(&s.mangled).or_default();
+                if !libs.iter().any(|(path, _)| *path == lib.path) {
+                    libs.push((&lib.path, s));
                 }
-            } else {
-                parsed_lib.syms.push(s);
             }
         }
 
-        self.libs.push(parsed_lib);
+        let mut linked: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+        for s in syms.iter() {
+            linked.entry(&s.mangled).or_default().push(s);
+        }
 
-        Ok(())
+        copies
+            .into_iter()
+            .filter(|(_, libs)| libs.len() >= 2)
+            .map(|(mangled, libs)| {
+                let linked = linked.get(mangled).cloned().unwrap_or_default();
+
+                let mut kept = Vec::new();
+                // Sizes of the linked copies that couldn't be attributed
+                let mut sizes = Vec::new();
+                for s in linked.iter() {
+                    let lib = s.archive.as_ref().and_then(|archive| {
+                        libs.iter().find(|(path, _)| matches_archive(path, archive))
+                    });
+                    match lib {
+                        Some((path, _)) => kept.push(*path),
+                        None => sizes.push(s.size),
+                    }
+                }
+
+                let mut ambiguous = false;
+                let mut unique = sizes.clone();
+                unique.sort_unstable();
+                unique.dedup();
+                for size in unique {
+                    let count = sizes.iter().filter(|s| **s == size).count();
+                    let candidates = libs
+                        .iter()
+                        .filter(|(path, s)| (s.size == size) && !kept.contains(path))
+                        .map(|(path, _)| *path)
+                        .collect::<Vec<_>>();
+                    ambiguous |= candidates.len() > count;
+                    kept.extend(candidates);
+                }
+
+                RuntimeDuplicate {
+                    mangled: String::from(mangled),
+                    demangled: libs[0].1.demangled.clone(),
+                    kept: libs
+                        .iter()
+                        .filter(|(path, _)| kept.contains(path))
+                        .map(|(path, _)| path.to_path_buf())
+                        .collect(),
+                    libs: libs
+                        .iter()
+                        .map(|(path, s)| (path.to_path_buf(), s.size))
+                        .collect(),
+                    linked: linked.iter().map(|s| s.size).collect(),
+                    ambiguous,
+                }
+            })
+            .collect()
+    }
+
+    /// Counts the symbols of every added library that are related (using
+    /// [`Symbol::related`]) to a symbol of the ELF file which has been matched
+    /// to the library (see [`Symbol::lib`]).
+    pub fn linked_symbols(&self, syms: &[Symbol]) -> Vec<LinkedSymbols> {
+        // (Library, mangled name) -> symbols of the ELF file
+        let mut matched: HashMap<(&Path, &str), Vec<&Symbol>> = HashMap::new();
+        for s in syms.iter() {
+            if let Some(lib) = &s.lib {
+                matched.entry((lib, &s.mangled)).or_default().push(s);
+            }
+        }
+
+        self.libs
+            .iter()
+            .map(|lib| LinkedSymbols {
+                path: lib.path.clone(),
+                total: lib.syms.len(),
+                linked: lib
+                    .syms
+                    .iter()
+                    .filter(|lib_sym| {
+                        matched
+                            .get(&(lib.path.as_path(), lib_sym.mangled.as_str()))
+                            .into_iter()
+                            .flatten()
+                            .any(|s| s.related(lib_sym))
+                    })
+                    .count(),
+            })
+            .collect()
     }
 
     /// Detect the origin language of symbol. First, this checks if the symbol
     /// is related (using [`Symbol::related`]) to any of the symbols parsed from
-    /// the libraries with [`add_lib`].
+    /// the libraries with [`add_lib`] and stores the path of the first matching
+    /// library in [`Symbol::lib`] and the archive member in [`Symbol::object`].
     /// If it isn't related to any of them, the language is set to the default stored in the
     /// `default_lang` member of Self if the mangled and demangled name of the symbol is the
     /// same. Otherwise, it is set to `default_mangled_lang`.
@@ -168,21 +366,96 @@ impl LangDetector {
         T: TryInto<RawSymbol>,
         Error: From<<T as TryInto<RawSymbol>>::Error>,
     {
-        let mut sym = Symbol::from_rawsymbols(mangled, demangled)?;
+        let sym = Symbol::from_rawsymbols(mangled, demangled)?;
+        Ok(self.detect_symbol(sym))
+    }
 
+    /// Same as [`detect`] but for an already created [`Symbol`] (e.g. one read
+    /// directly from the ELF file). The `lang` member of the symbol is
+    /// overwritten with the detected language.
+    ///
+    /// [`detect`]: LangDetector::detect
+    pub fn detect_symbol(&self, mut sym: Symbol) -> Symbol {
         for lib in self.libs.iter() {
-            if lib.syms.iter().any(|lib_sym| sym.related(lib_sym)) {
+            if let Some(lib_sym) = lib.syms.iter().find(|lib_sym| sym.related(lib_sym)) {
                 sym.lang = lib.lang;
-                return Ok(sym);
+                sym.lib = Some(lib.path.clone());
+                sym.object = lib_sym.object.clone();
+                return sym;
             }
         }
 
+        if let Some(lang) = self.dwarf.as_ref().and_then(|d| d.lang_at(sym.addr)) {
+            sym.lang = lang;
+            return sym;
+        }
+
         if sym.mangled == sym.demangled {
             sym.lang = self.default_lang;
         } else {
             sym.lang = self.default_mangled_lang;
         }
 
-        Ok(sym)
+        sym
+    }
+}
+
+/// Parses the output of nm (`--print-size`) for a static library. The name of
+/// the archive member containing a symbol is stored in [`Symbol::object`].
+fn parse_lib_symbols(out: &str) -> Vec<Symbol> {
+    let mut syms = Vec::new();
+    let mut object = None;
+
+    for line in out.lines() {
+        // Every member of the archive starts with a header like "c_lib.o:".
+        if let Some(member) = line.strip_suffix(':') {
+            if !member.is_empty() && !member.contains(char::is_whitespace) {
+                object = Some(String::from(member));
+                continue;
+            }
+        }
+
+        let mut s = match RawSymbol::from_str(line) {
+            Ok(raw) => Symbol::from_rawsymbol(raw),
+            // TODO:
+            // Differentiate between the various reasons for an error. Some
+            // might be expected while others should not fail and should
+            // inform the user.
+            Err(_) => continue,
+        };
+        s.object = object.clone();
+
+        if is_lib_symbol(&s) {
+            syms.push(s);
+        }
+    }
+
+    syms
+}
+
+/// Checks if a symbol parsed from a library should be used for detecting the
+/// language.
+///
+/// The symbols that have distinct mangled and demangled names are added to the parsed library
+/// without any further checks. Symbols, where the mangled and demangled names match, are further
+/// checked to be valid C identifiers. I.e., underscores, lower- or uppercase letters, or numbers
+/// (not allowed for the first character). Additionally, the dot "." character is also allowed as
+/// it seems to be used for symbols in RAM like "000194f0 00000018 b object.8916". This logic thus
+/// excludes symbols like ".Lanon.4575732b5f0a476c725a4805a4f03b6f.638" for example, which seem to
+/// be unused symbols from Rust static libraries.
+fn is_lib_symbol(s: &Symbol) -> bool {
+    if s.mangled != s.demangled {
+        return true;
+    }
+
+    // TODO:
+    // Rewrite this using a simple regex and check the performance
+    // difference
+    let mut chars = s.mangled.chars();
+    match chars.next() {
+        Some('a'..='z' | 'A'..='Z' | '_') => {
+            chars.all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '.' | '0'..='9'))
+        }
+        _ => false,
     }
 }
@@ -287,3 +287,306 @@ mod langdetector_tests {
         assert_eq!(s.lang, SymbolLang::Rust);
     }
 }
+
+mod runtime_tests {
+    use super::super::*;
+    use crate::sym::SymbolType;
+
+    fn sym(name: &str, size: u64) -> Symbol {
+        Symbol::new(
+            0,
+            size,
+            SymbolType::TextSection,
+            String::from(name),
+            String::from(name),
+            SymbolLang::Rust,
+        )
+    }
+
+    fn lib(path: &str, lang: SymbolLang, syms: Vec<Symbol>) -> ParsedLibrary {
+        ParsedLibrary {
+            path: PathBuf::from(path),
+            lang,
+            syms,
+        }
+    }
+
+    fn detector() -> LangDetector {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        detector.libs.push(lib(
+            "liba.a",
+            SymbolLang::Rust,
+            vec![sym("memcpy", 20), sym("panic", 8), sym("a_fn", 4)],
+        ));
+        detector.libs.push(lib(
+            "libb.a",
+            SymbolLang::Rust,
+            vec![sym("memcpy", 24), sym("panic", 8), sym("panic", 8)],
+        ));
+        detector
+            .libs
+            .push(lib("libc.a", SymbolLang::C, vec![sym("memcpy", 16)]));
+        detector
+    }
+
+    #[test]
+    fn runtime_duplicates() {
+        let dups = detector().runtime_duplicates(&[sym("memcpy", 24), sym("a_fn", 4)]);
+        assert_eq!(dups.len(), 2);
+
+        assert_eq!(dups[0].mangled, "memcpy");
+        assert_eq!(
+            dups[0].libs,
+            vec![(PathBuf::from("liba.a"), 20), (PathBuf::from("libb.a"), 24)]
+        );
+        assert_eq!(dups[0].kept, vec![PathBuf::from("libb.a")]);
+        assert_eq!(dups[0].linked, vec![24]);
+        assert_eq!(dups[0].wasted(), 0);
+        assert!(!dups[0].ambiguous);
+
+        assert_eq!(dups[1].mangled, "panic");
+        assert_eq!(dups[1].libs.len(), 2);
+        assert!(dups[1].kept.is_empty());
+        assert!(dups[1].linked.is_empty());
+    }
+
+    #[test]
+    fn runtime_duplicates_linked_twice() {
+        let dups = detector().runtime_duplicates(&[sym("panic", 8), sym("panic", 8)]);
+        assert_eq!(dups[1].mangled, "panic");
+        assert_eq!(
+            dups[1].kept,
+            vec![PathBuf::from("liba.a"), PathBuf::from("libb.a")]
+        );
+        assert_eq!(dups[1].wasted(), 8);
+        assert!(!dups[1].ambiguous);
+    }
+
+    #[test]
+    fn runtime_duplicates_ambiguous() {
+        let dups = detector().runtime_duplicates(&[sym("panic", 8)]);
+        assert_eq!(dups[1].mangled, "panic");
+        assert_eq!(
+            dups[1].kept,
+            vec![PathBuf::from("liba.a"), PathBuf::from("libb.a")]
+        );
+        assert!(dups[1].ambiguous);
+    }
+
+    #[test]
+    fn runtime_duplicates_archive() {
+        let mut panic = sym("panic", 8);
+        panic.archive = Some(PathBuf::from("libb.a"));
+        let dups = detector().runtime_duplicates(&[panic]);
+        assert_eq!(dups[1].mangled, "panic");
+        assert_eq!(dups[1].kept, vec![PathBuf::from("libb.a")]);
+        assert_eq!(dups[1].linked, vec![8]);
+        assert!(!dups[1].ambiguous);
+    }
+
+    #[test]
+    fn runtime_duplicates_no_rust_libs() {
+        let detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        assert!(detector.runtime_duplicates(&[sym("memcpy", 24)]).is_empty());
+    }
+}
+
+mod linkedsymbols_tests {
+    use super::super::*;
+    use crate::sym::SymbolType;
+
+    fn sym(name: &str, size: u64, lib: Option<&str>) -> Symbol {
+        let mut s = Symbol::new(
+            0,
+            size,
+            SymbolType::TextSection,
+            String::from(name),
+            String::from(name),
+            SymbolLang::C,
+        );
+        s.lib = lib.map(PathBuf::from);
+        s
+    }
+
+    #[test]
+    fn linked_symbols() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        detector.libs.push(ParsedLibrary {
+            path: PathBuf::from("liba.a"),
+            lang: SymbolLang::C,
+            syms: vec![sym("a1", 4, None), sym("a2", 8, None), sym("a3", 2, None)],
+        });
+        detector.libs.push(ParsedLibrary {
+            path: PathBuf::from("libb.a"),
+            lang: SymbolLang::C,
+            syms: vec![sym("b1", 4, None)],
+        });
+
+        let syms = [
+            sym("a1", 4, Some("liba.a")),
+            // Different size
+            sym("a2", 6, Some("liba.a")),
+            // Not matched to the library
+            sym("a3", 2, None),
+            sym("b1", 4, Some("libb.a")),
+        ];
+        let linked = detector.linked_symbols(&syms);
+        assert_eq!(
+            linked,
+            vec![
+                LinkedSymbols {
+                    path: PathBuf::from("liba.a"),
+                    total: 3,
+                    linked: 1,
+                },
+                LinkedSymbols {
+                    path: PathBuf::from("libb.a"),
+                    total: 1,
+                    linked: 1,
+                },
+            ]
+        );
+        assert_eq!(linked[0].dropped(), 2);
+        assert_eq!(linked[1].dropped(), 0);
+    }
+
+    #[test]
+    fn detect_symbol_lib() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        detector.libs.push(ParsedLibrary {
+            path: PathBuf::from("libcpp.a"),
+            lang: SymbolLang::Cpp,
+            syms: vec![sym("lib_fn", 4, None)],
+        });
+
+        let s = detector.detect_symbol(sym("lib_fn", 4, None));
+        assert_eq!(s.lang, SymbolLang::Cpp);
+        assert_eq!(s.lib, Some(PathBuf::from("libcpp.a")));
+        let s = detector.detect_symbol(sym("main", 4, None));
+        assert_eq!(s.lang, SymbolLang::C);
+        assert_eq!(s.lib, None);
+    }
+}
+
+mod parselibsymbols_tests {
+    use super::super::*;
+
+    const NM_OUT: &str = "
+c_lib.o:
+00000000 d $d
+00000001 0000001a T c_add
+00000000 00000040 b c_lib_bss_arr
+
+mulvdi3.o:
+00000000 00000030 T __mulvdi3
+         U abort
+";
+
+    #[test]
+    fn members() {
+        let syms = parse_lib_symbols(NM_OUT);
+        let names = syms
+            .iter()
+            .map(|s| (s.mangled.as_str(), s.object.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("c_add", Some("c_lib.o")),
+                ("c_lib_bss_arr", Some("c_lib.o")),
+                ("__mulvdi3", Some("mulvdi3.o")),
+            ]
+        );
+    }
+
+    #[test]
+    fn detect_symbol_object() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        detector.libs.push(ParsedLibrary {
+            path: PathBuf::from("libc_lib.a"),
+            lang: SymbolLang::C,
+            syms: parse_lib_symbols(NM_OUT),
+        });
+
+        let s = detector
+            .detect(
+                "00008000 00000030 T __mulvdi3",
+                "00008000 00000030 T __mulvdi3",
+            )
+            .unwrap();
+        assert_eq!(s.lib, Some(PathBuf::from("libc_lib.a")));
+        assert_eq!(s.object.as_deref(), Some("mulvdi3.o"));
+    }
+}
+
+mod addarchive_tests {
+    use super::super::*;
+    use crate::sym::SymbolType;
+
+    #[test]
+    fn add_archive_c_lib() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        let lib = Library::new(
+            SymbolLang::C,
+            "test_data/c_app_c_lib_rust_lib/libs/libc_lib.a",
+        );
+        detector.add_archive(&lib).unwrap();
+        assert_eq!(detector.libs[0].lang, SymbolLang::C);
+        let syms = &detector.libs[0].syms;
+        assert_eq!(syms.len(), 4);
+        assert!(syms.iter().all(|s| s.object.as_deref() == Some("c_lib.o")));
+        let c_add = syms.iter().find(|s| s.mangled == "c_add").unwrap();
+        assert_eq!(c_add.size, 0x1a);
+        assert_eq!(c_add.sym_type, SymbolType::TextSection);
+    }
+
+    #[test]
+    fn add_archive_rlib() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        let lib = Library::new(SymbolLang::Rust, "test_data/ar/libringbuf.rlib");
+        detector.add_archive(&lib).unwrap();
+        let syms = &detector.libs[0].syms;
+        assert_eq!(syms.len(), 2);
+        assert!(syms.iter().any(|s| s.demangled == "ringbuf::RingBuf::push"));
+        assert!(syms.iter().any(|s| s.demangled == "ringbuf_checksum"));
+    }
+
+    #[test]
+    fn add_archive_bsd() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        let lib = Library::new(SymbolLang::C, "test_data/ar/libbsd.a");
+        detector.add_archive(&lib).unwrap();
+        let syms = &detector.libs[0].syms;
+        assert_eq!(syms.len(), 8);
+        assert_eq!(
+            syms.iter()
+                .filter(|s| s.object.as_deref() == Some("a_very_long_object_file_name.o"))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn add_archive_not_archive() {
+        let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+        let lib = Library::new(SymbolLang::C, "readme.md");
+        let err = detector.add_archive(&lib).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArchive);
+    }
+}
+
+mod library_tests {
+    use super::super::*;
+
+    #[test]
+    fn matches_archive() {
+        let lib = Library::new(SymbolLang::C, "/home/user/project/build/libs/libfoo.a");
+        assert!(lib.matches_archive(Path::new("/home/user/project/build/libs/libfoo.a")));
+        assert!(lib.matches_archive(Path::new("libs/libfoo.a")));
+        assert!(lib.matches_archive(Path::new("../build/libs/libfoo.a")));
+        assert!(lib.matches_archive(Path::new("./libfoo.a")));
+        assert!(!lib.matches_archive(Path::new("/other/libs/libfoo.a")));
+        assert!(!lib.matches_archive(Path::new("libs/libbar.a")));
+        assert!(!lib.matches_archive(Path::new("oo.a")));
+    }
+}
@@ -0,0 +1,359 @@
+//! Determine the source language of symbols with the DWARF debugging
+//! information of ELF files built with `-g`. Every compilation unit records the
+//! language it was written in (`DW_AT_language`) as well as the address ranges
+//! of the code generated from it. The source files of the functions and
+//! variables (`DW_AT_decl_file`) are read from the line number information.
+
+use crate::elf::ElfFile;
+use crate::error::{Error, ErrorKind};
+use crate::sym::SymbolLang;
+use gimli::{
+    constants, AttributeValue, DebuggingInformationEntry, DwLang, EndianSlice, Operation,
+    RunTimeEndian,
+};
+use std::cmp::max;
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(test)]
+#[path = "./dwarf_tests.rs"]
+mod dwarf_tests;
+
+/// A compilation unit of the DWARF debugging information.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CompUnit {
+    /// Name of the compilation unit (usually the path to the source file).
+    pub name: Option<String>,
+    /// Path to the source file of the compilation unit. It is derived from the
+    /// name and the compilation directory (`DW_AT_comp_dir`). The codegen unit
+    /// is removed from the names of Rust compilation units.
+    pub file: Option<String>,
+    /// Language of the compilation unit. `None` if the language is missing or
+    /// not one of the languages supported by this crate.
+    pub lang: Option<SymbolLang>,
+    /// Address ranges (start inclusive, end exclusive) of the code generated
+    /// from the compilation unit. Statically allocated variables are included
+    /// with a range of a single byte at their address.
+    pub ranges: Vec<(u64, u64)>,
+}
+
+/// The compilation units of an ELF file together with a lookup table for
+/// finding the compilation unit of an address.
+#[derive(Debug, Default)]
+pub struct DwarfInfo {
+    units: Vec<CompUnit>,
+    /// Address ranges (start, end, index of compilation unit) sorted by the
+    /// start address. The ranges don't overlap so that at most one of them
+    /// contains an address.
+    ranges: Vec<(u64, u64, usize)>,
+    /// Source files (`DW_AT_decl_file`) of the functions and statically
+    /// allocated variables sorted by their address.
+    files: Vec<(u64, String)>,
+}
+
+impl DwarfInfo {
+    /// Reads the DWARF debugging information of the ELF file at the given path.
+    /// See [`DwarfInfo::parse`].
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        DwarfInfo::parse(&ElfFile::open(path)?)
+    }
+
+    /// Parses the compilation units contained in the debugging sections of the
+    /// ELF file. An ELF file without debugging information results in an empty
+    /// [`DwarfInfo`]. Returns an [`ErrorKind::InvalidDwarf`] error if the
+    /// debugging information couldn't be parsed.
+    pub fn parse(elf: &ElfFile) -> Result<Self, Error> {
+        let endian = if elf.is_little_endian() {
+            RunTimeEndian::Little
+        } else {
+            RunTimeEndian::Big
+        };
+
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, Error> {
+            Ok(EndianSlice::new(
+                elf.section_data(id.name()).unwrap_or(&[]),
+                endian,
+            ))
+        })?;
+
+        let to_error = |e: gimli::Error| Error::new(ErrorKind::InvalidDwarf).with(e);
+
+        let mut info = DwarfInfo::default();
+        let mut headers = dwarf.units();
+        while let Some(header) = headers.next().map_err(to_error)? {
+            let unit = dwarf.unit(header).map_err(to_error)?;
+            let mut entries = unit.entries();
+            let root = match entries.next_dfs().map_err(to_error)? {
+                Some((_, root)) => root,
+                None => continue,
+            };
+
+            let lang = match root
+                .attr_value(constants::DW_AT_language)
+                .map_err(to_error)?
+            {
+                Some(AttributeValue::Language(lang)) => symbol_lang(lang),
+                _ => None,
+            };
+
+            let name = match root.attr_value(constants::DW_AT_name).map_err(to_error)? {
+                Some(value) => dwarf
+                    .attr_string(&unit, value)
+                    .ok()
+                    .map(|s| s.to_string_lossy().into_owned()),
+                None => None,
+            };
+
+            let comp_dir = unit.comp_dir.map(|dir| dir.to_string_lossy().into_owned());
+            let file = name.as_deref().map(|name| {
+                let name = name.split("/@/").next().unwrap_or(name);
+                normalize_path(comp_dir.as_deref(), name)
+            });
+
+            let mut ranges = Vec::new();
+
+            // Only the code is covered by the ranges of the compilation unit.
+            // Statically allocated variables are added with their address.
+            while let Some((_, entry)) = entries.next_dfs().map_err(to_error)? {
+                let addr = match entry.tag() {
+                    constants::DW_TAG_variable => match entry
+                        .attr_value(constants::DW_AT_location)
+                        .map_err(to_error)?
+                    {
+                        Some(AttributeValue::Exprloc(expr)) => {
+                            let mut ops = expr.operations(unit.encoding());
+                            match ops.next() {
+                                Ok(Some(Operation::Address { address })) if address != 0 => {
+                                    ranges.push((address, address + 1));
+                                    address
+                                }
+                                _ => continue,
+                            }
+                        }
+                        _ => continue,
+                    },
+                    constants::DW_TAG_subprogram => match entry
+                        .attr_value(constants::DW_AT_low_pc)
+                        .map_err(to_error)?
+                    {
+                        Some(AttributeValue::Addr(address)) if address != 0 => address,
+                        _ => continue,
+                    },
+                    _ => continue,
+                };
+
+                if let Some(path) = decl_file(&dwarf, &unit, entry, comp_dir.as_deref()) {
+                    info.files.push((addr, path));
+                }
+            }
+
+            let mut iter = dwarf.unit_ranges(&unit).map_err(to_error)?;
+            while let Some(range) = iter.next().map_err(to_error)? {
+                // Ranges of code removed by the linker (e.g. with
+                // `--gc-sections`) are relocated to address 0.
+                if (range.begin != 0) && (range.end > range.begin) {
+                    ranges.push((range.begin, range.end));
+                }
+            }
+
+            info.units.push(CompUnit {
+                name,
+                file,
+                lang,
+                ranges,
+            });
+        }
+
+        let mut ranges = Vec::new();
+        for (idx, unit) in info.units.iter().enumerate() {
+            ranges.extend(unit.ranges.iter().map(|(start, end)| (*start, *end, idx)));
+        }
+        ranges.sort_unstable();
+        // Overlapping parts are attributed to the range starting first.
+        for (start, end, idx) in ranges {
+            let start = match info.ranges.last() {
+                Some((_, prev_end, _)) => max(start, *prev_end),
+                None => start,
+            };
+            if start < end {
+                info.ranges.push((start, end, idx));
+            }
+        }
+        info.files.sort();
+        info.files.dedup_by_key(|(addr, _)| *addr);
+
+        Ok(info)
+    }
+
+    /// Returns all compilation units.
+    pub fn units(&self) -> &[CompUnit] {
+        &self.units
+    }
+
+    /// Returns the compilation unit whose code contains the given address.
+    pub fn unit_at(&self, addr: u64) -> Option<&CompUnit> {
+        let idx = self.ranges.partition_point(|(start, _, _)| *start <= addr);
+        let (_, end, unit) = self.ranges.get(idx.checked_sub(1)?)?;
+        if addr < *end {
+            Some(&self.units[*unit])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the language of the compilation unit whose code contains the
+    /// given address.
+    pub fn lang_at(&self, addr: u64) -> Option<SymbolLang> {
+        self.unit_at(addr).and_then(|unit| unit.lang)
+    }
+
+    /// Returns the path to the source file of the function or variable at the
+    /// given address. The source file of the compilation unit is returned if
+    /// the address isn't the start of a function or variable with a
+    /// `DW_AT_decl_file` attribute (e.g. for string literals).
+    pub fn file_at(&self, addr: u64) -> Option<&str> {
+        match self.files.binary_search_by_key(&addr, |(addr, _)| *addr) {
+            Ok(idx) => Some(self.files[idx].1.as_str()),
+            Err(_) => self.unit_at(addr).and_then(|unit| unit.file.as_deref()),
+        }
+    }
+}
+
+type Reader<'a> = EndianSlice<'a, RunTimeEndian>;
+
+/// Returns the path to the source file declaring the entry. The declaration of
+/// out-of-line definitions (e.g. Cpp methods) and inlined instances is
+/// followed if the entry doesn't have a `DW_AT_decl_file` attribute itself.
+fn decl_file(
+    dwarf: &gimli::Dwarf<Reader>,
+    unit: &gimli::Unit<Reader>,
+    entry: &DebuggingInformationEntry<Reader>,
+    comp_dir: Option<&str>,
+) -> Option<String> {
+    let mut entry = entry.clone();
+    // The declaration might refer to another declaration (e.g. an abstract
+    // instance of an inlined method). The depth is limited for malformed
+    // debugging information.
+    let mut idx = None;
+    for _ in 0..4 {
+        match entry.attr_value(constants::DW_AT_decl_file).ok()? {
+            Some(AttributeValue::FileIndex(i)) | Some(AttributeValue::Udata(i)) => {
+                idx = Some(i);
+                break;
+            }
+            _ => {}
+        }
+        let origin = match entry.attr_value(constants::DW_AT_specification).ok()? {
+            Some(origin) => Some(origin),
+            None => entry.attr_value(constants::DW_AT_abstract_origin).ok()?,
+        };
+        entry = match origin {
+            Some(AttributeValue::UnitRef(offset)) => unit.entry(offset).ok()?,
+            _ => return None,
+        };
+    }
+
+    let header = unit.line_program.as_ref()?.header();
+    let file = header.file(idx?)?;
+    let mut path = String::new();
+    if let Some(dir) = file.directory(header) {
+        path = dwarf
+            .attr_string(unit, dir)
+            .ok()?
+            .to_string_lossy()
+            .into_owned();
+    }
+    let name = dwarf.attr_string(unit, file.path_name()).ok()?;
+    let path = Path::new(&path).join(&*name.to_string_lossy());
+
+    Some(normalize_path(comp_dir, &path.to_string_lossy()))
+}
+
+/// Joins a relative path with the directory and removes the `.` and `..`
+/// components. Absolute paths are only normalized.
+///
+/// # Example
+/// ```ignore
+/// assert_eq!(
+///     normalize_path(Some("/zephyr/build"), "../kernel/./sched.c"),
+///     "/zephyr/kernel/sched.c"
+/// );
+/// ```
+fn normalize_path(dir: Option<&str>, path: &str) -> String {
+    let joined = Path::new(dir.unwrap_or("")).join(path);
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized.to_string_lossy().into_owned()
+}
+
+/// Returns the name of the Rust crate that generated the code of a compilation
+/// unit. rustc names the compilation units after the source file and the
+/// codegen unit (e.g. `src/lib.rs/@/rust_lib.1a2b3c-cgu.0`). Monomorphizations
+/// are placed in the codegen units of the crate that instantiated them.
+/// Returns `None` for compilation units of other compilers.
+///
+/// # Example
+/// ```
+/// # use atlas::dwarf::unit_crate;
+/// assert_eq!(unit_crate("src/lib.rs/@/rust_lib.1a2b3c-cgu.0"), Some("rust_lib"));
+/// assert_eq!(unit_crate("app.c"), None);
+/// ```
+pub fn unit_crate(name: &str) -> Option<&str> {
+    unit_crate_version(name).map(|(name, _)| name)
+}
+
+/// Same as [`unit_crate`] but additionally returns the disambiguator of the
+/// crate. It differs if multiple versions of the same crate are linked into an
+/// application and matches the disambiguator of Rust v0 symbols (see
+/// [`crate::demangle::crate_version`]).
+///
+/// # Example
+/// ```
+/// # use atlas::dwarf::unit_crate_version;
+/// assert_eq!(
+///     unit_crate_version("src/lib.rs/@/ringbuf.a3789542a9d61af9-cgu.0"),
+///     Some(("ringbuf", "a3789542a9d61af9"))
+/// );
+/// ```
+pub fn unit_crate_version(name: &str) -> Option<(&str, &str)> {
+    let (_, cgu) = name.rsplit_once("/@/")?;
+    let (name, rest) = cgu.split_once('.')?;
+    let (version, _) = rest.split_once("-cgu.")?;
+    if name.is_empty() {
+        None
+    } else {
+        Some((name, version))
+    }
+}
+
+/// Maps the DWARF language of a compilation unit to a [`SymbolLang`].
+fn symbol_lang(lang: DwLang) -> Option<SymbolLang> {
+    match lang {
+        constants::DW_LANG_C89
+        | constants::DW_LANG_C
+        | constants::DW_LANG_C99
+        | constants::DW_LANG_C11
+        | constants::DW_LANG_C17 => Some(SymbolLang::C),
+        constants::DW_LANG_C_plus_plus
+        | constants::DW_LANG_C_plus_plus_03
+        | constants::DW_LANG_C_plus_plus_11
+        | constants::DW_LANG_C_plus_plus_14
+        | constants::DW_LANG_C_plus_plus_17
+        | constants::DW_LANG_C_plus_plus_20 => Some(SymbolLang::Cpp),
+        constants::DW_LANG_Rust => Some(SymbolLang::Rust),
+        constants::DW_LANG_Mips_Assembler => Some(SymbolLang::Asm),
+        _ => None,
+    }
+}
@@ -0,0 +1,168 @@
+mod dwarfinfo_tests {
+    use super::super::*;
+
+    #[test]
+    fn open_not_found() {
+        let err = DwarfInfo::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn open_without_debug_info() {
+        let info = DwarfInfo::open("test_data/c_app_lma/app").unwrap();
+        assert!(info.units().is_empty());
+        assert!(info.unit_at(0x08000000).is_none());
+    }
+
+    #[test]
+    fn units() {
+        let info = DwarfInfo::open("test_data/c_app_dwarf/app").unwrap();
+        let lang = |name: &str| {
+            info.units()
+                .iter()
+                .find(|u| matches!(&u.name, Some(n) if n.contains(name)))
+                .unwrap()
+                .lang
+        };
+        assert_eq!(lang("app.c"), Some(SymbolLang::C));
+        assert_eq!(lang("cpp_lib.cpp"), Some(SymbolLang::Cpp));
+        assert_eq!(lang("asm_lib.S"), Some(SymbolLang::Asm));
+        assert_eq!(lang("rust_lib.rs/@/"), Some(SymbolLang::Rust));
+    }
+
+    #[test]
+    fn lang_at() {
+        let info = DwarfInfo::open("test_data/c_app_dwarf/app").unwrap();
+        // main
+        assert_eq!(info.lang_at(0x401118), Some(SymbolLang::C));
+        assert_eq!(info.lang_at(0x401168), Some(SymbolLang::C));
+        // cpp_add
+        assert_eq!(info.lang_at(0x4011b2), Some(SymbolLang::Cpp));
+        // asm_double
+        assert_eq!(info.lang_at(0x4011f0), Some(SymbolLang::Asm));
+        // rust_mult (#[no_mangle])
+        assert_eq!(info.lang_at(0x401210), Some(SymbolLang::Rust));
+        // RUST_LIB_RESULTS (static variable)
+        assert_eq!(info.lang_at(0x404050), Some(SymbolLang::Rust));
+        // _start has no debugging information.
+        assert_eq!(info.lang_at(0x401020), None);
+    }
+
+    #[test]
+    fn unit_file() {
+        let info = DwarfInfo::open("test_data/c_app_dwarf/app").unwrap();
+        let dir = std::env::current_dir()
+            .unwrap()
+            .join("test_data/c_app_dwarf");
+        let files = info
+            .units()
+            .iter()
+            .map(|u| u.file.clone().unwrap())
+            .collect::<Vec<_>>();
+        for name in &["app.c", "cpp_lib.cpp", "asm_lib.S", "rust_lib.rs"] {
+            let path = dir.join(name).to_string_lossy().into_owned();
+            assert!(files.contains(&path), "{} not in {:?}", path, files);
+        }
+    }
+
+    #[test]
+    fn file_at() {
+        let info = DwarfInfo::open("test_data/c_app_dwarf/app").unwrap();
+        let file = |addr: u64| {
+            info.file_at(addr)
+                .map(|f| Path::new(f).file_name().unwrap().to_str().unwrap())
+        };
+        // main
+        assert_eq!(file(0x401118), Some("app.c"));
+        // cpp_add
+        assert_eq!(file(0x4011b2), Some("cpp_lib.cpp"));
+        // cpp_lib::Accumulator::add (DW_AT_specification)
+        assert_eq!(file(0x401182), Some("cpp_lib.cpp"));
+        // asm_double
+        assert_eq!(file(0x4011f0), Some("asm_lib.S"));
+        // rust_mult (#[no_mangle])
+        assert_eq!(file(0x401210), Some("rust_lib.rs"));
+        // RUST_LIB_RESULTS (static variable)
+        assert_eq!(file(0x404050), Some("rust_lib.rs"));
+        // Inside of main falls back to the compilation unit.
+        assert_eq!(file(0x401168), Some("app.c"));
+        // _start has no debugging information.
+        assert_eq!(file(0x401020), None);
+    }
+}
+
+mod normalize_path_tests {
+    use super::super::*;
+
+    #[test]
+    fn relative() {
+        assert_eq!(
+            normalize_path(Some("/zephyr/build"), "../kernel/./sched.c"),
+            "/zephyr/kernel/sched.c"
+        );
+    }
+
+    #[test]
+    fn absolute() {
+        assert_eq!(
+            normalize_path(Some("/build"), "/zephyr/subsys/net/../net/ip.c"),
+            "/zephyr/subsys/net/ip.c"
+        );
+        assert_eq!(normalize_path(None, "src/main.c"), "src/main.c");
+    }
+}
+
+mod symbol_lang_tests {
+    use super::super::*;
+
+    #[test]
+    fn supported() {
+        assert_eq!(symbol_lang(constants::DW_LANG_C99), Some(SymbolLang::C));
+        assert_eq!(
+            symbol_lang(constants::DW_LANG_C_plus_plus_11),
+            Some(SymbolLang::Cpp)
+        );
+        assert_eq!(symbol_lang(constants::DW_LANG_Rust), Some(SymbolLang::Rust));
+        assert_eq!(
+            symbol_lang(constants::DW_LANG_Mips_Assembler),
+            Some(SymbolLang::Asm)
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(symbol_lang(constants::DW_LANG_Fortran90), None);
+    }
+}
+
+mod unit_crate_tests {
+    use super::super::*;
+
+    #[test]
+    fn rust() {
+        assert_eq!(
+            unit_crate("src/lib.rs/@/rust_lib.1a2b3c-cgu.0"),
+            Some("rust_lib")
+        );
+        assert_eq!(
+            unit_crate("/rustc/59807616e/library/core/src/lib.rs/@/core.c1f1a4ba-cgu.0"),
+            Some("core")
+        );
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(
+            unit_crate_version("ringbuf.rs/@/ringbuf.a3789542a9d61af9-cgu.0"),
+            Some(("ringbuf", "a3789542a9d61af9"))
+        );
+        assert_eq!(unit_crate_version("app.c"), None);
+    }
+
+    #[test]
+    fn other() {
+        assert_eq!(unit_crate("app.c"), None);
+        assert_eq!(unit_crate("src/@/lib.rs"), None);
+        assert_eq!(unit_crate("src/lib.rs/@/.cgu"), None);
+    }
+}
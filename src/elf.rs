@@ -0,0 +1,600 @@
+//! In-process reader for the symbol table of ELF files. This allows the
+//! symbols of the linked executable to be analyzed without invoking the
+//! [nm](https://sourceware.org/binutils/docs/binutils/nm.html) utility.
+//!
+//! Only the parts of the format needed by this crate are parsed (file header,
+//! section headers, program headers, `.symtab` and its associated string
+//! table). Both ELF classes (32-bit and 64-bit) and byte orders are supported.
+
+use crate::error::{Error, ErrorKind};
+use crate::sym::{MemoryRegion, RawSymbol, SymbolType};
+use std::convert::TryInto;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+#[cfg(test)]
+#[path = "./elf_tests.rs"]
+mod elf_tests;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+const ELFCLASS32: u8 = 1;
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const ELFDATA2MSB: u8 = 2;
+const EM_ARM: u16 = 40;
+
+const PT_LOAD: u32 = 1;
+
+const SHT_SYMTAB: u32 = 2;
+const SHT_NOBITS: u32 = 8;
+
+const SHF_WRITE: u64 = 0x1;
+const SHF_ALLOC: u64 = 0x2;
+const SHF_EXECINSTR: u64 = 0x4;
+
+const SHN_UNDEF: u16 = 0;
+const SHN_LORESERVE: u16 = 0xff00;
+const SHN_ABS: u16 = 0xfff1;
+const SHN_COMMON: u16 = 0xfff2;
+
+const STB_LOCAL: u8 = 0;
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+const STB_GNU_UNIQUE: u8 = 10;
+
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+const STT_SECTION: u8 = 3;
+const STT_FILE: u8 = 4;
+const STT_GNU_IFUNC: u8 = 10;
+
+/// Byte order of the multi-byte fields in the ELF file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Endian {
+    Little,
+    Big,
+}
+
+/// Class of the ELF file which determines the size of addresses and offsets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElfClass {
+    Elf32,
+    Elf64,
+}
+
+/// A section header of an ELF file with its name already resolved from the
+/// section header string table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionHeader {
+    pub name: String,
+    pub sh_type: u32,
+    pub flags: u64,
+    pub addr: u64,
+    pub offset: u64,
+    pub size: u64,
+    pub link: u32,
+    pub entsize: u64,
+}
+
+impl SectionHeader {
+    /// Derives the [`MemoryRegion`] of the section from its type and flags.
+    /// Sections without `SHF_ALLOC` don't occupy any memory on the target and
+    /// are therefore [`MemoryRegion::Unknown`]. Writable (`SHF_WRITE`) and
+    /// uninitialized (`SHT_NOBITS`) sections are placed in RAM. All other
+    /// allocated sections (read-only data and code with `SHF_EXECINSTR`) are
+    /// placed in ROM.
+    pub fn mem_region(&self) -> MemoryRegion {
+        if self.flags & SHF_ALLOC == 0 {
+            MemoryRegion::Unknown
+        } else if (self.flags & SHF_WRITE != 0) || (self.sh_type == SHT_NOBITS) {
+            MemoryRegion::Ram
+        } else {
+            MemoryRegion::Rom
+        }
+    }
+
+    /// Returns the [`SymbolType`] of symbols defined in the section (e.g.
+    /// [`SymbolType::TextSection`] for `.text`).
+    pub fn sym_type(&self) -> SymbolType {
+        SymbolType::from_str(&section_type(self).to_string()).unwrap_or(SymbolType::Unknown)
+    }
+
+    /// Checks if the given address lies within the memory occupied by the
+    /// section at runtime. Always returns false for sections that aren't
+    /// allocated.
+    pub fn contains(&self, addr: u64) -> bool {
+        (self.flags & SHF_ALLOC != 0) && (addr >= self.addr) && (addr - self.addr < self.size)
+    }
+}
+
+/// A program header of an ELF file describing a segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramHeader {
+    pub p_type: u32,
+    pub flags: u32,
+    pub offset: u64,
+    /// Virtual memory address (VMA) of the segment at runtime.
+    pub vaddr: u64,
+    /// Load memory address (LMA) of the segment, i.e. where its contents are
+    /// stored in the image.
+    pub paddr: u64,
+    pub filesz: u64,
+    pub memsz: u64,
+}
+
+/// An entry of the ELF symbol table with its name already resolved from the
+/// associated string table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElfSymbol {
+    pub name: String,
+    pub value: u64,
+    pub size: u64,
+    pub info: u8,
+    pub shndx: u16,
+}
+
+impl ElfSymbol {
+    fn bind(&self) -> u8 {
+        self.info >> 4
+    }
+
+    fn kind(&self) -> u8 {
+        self.info & 0xf
+    }
+}
+
+/// A parsed ELF file containing the raw bytes, the section headers, and the
+/// program headers.
+#[derive(Debug)]
+pub struct ElfFile {
+    data: Vec<u8>,
+    class: ElfClass,
+    endian: Endian,
+    machine: u16,
+    sections: Vec<SectionHeader>,
+    segments: Vec<ProgramHeader>,
+}
+
+impl ElfFile {
+    /// Reads and parses the ELF file at the given path. Returns an
+    /// [`ErrorKind::Io`] error if the file couldn't be read and an
+    /// [`ErrorKind::InvalidElf`] error if it isn't a valid ELF file.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let data = fs::read(path.as_ref())?;
+        ElfFile::parse(data)
+    }
+
+    /// Parses the ELF file contained in `data`.
+    pub fn parse(data: Vec<u8>) -> Result<Self, Error> {
+        if data.len() < 6 || &data[0..4] != ELF_MAGIC {
+            return Err(Error::new(ErrorKind::InvalidElf));
+        }
+
+        let class = match data[4] {
+            ELFCLASS32 => ElfClass::Elf32,
+            ELFCLASS64 => ElfClass::Elf64,
+            _ => return Err(Error::new(ErrorKind::InvalidElf)),
+        };
+
+        let endian = match data[5] {
+            ELFDATA2LSB => Endian::Little,
+            ELFDATA2MSB => Endian::Big,
+            _ => return Err(Error::new(ErrorKind::InvalidElf)),
+        };
+
+        let mut elf = ElfFile {
+            data,
+            class,
+            endian,
+            machine: 0,
+            sections: Vec::new(),
+            segments: Vec::new(),
+        };
+
+        elf.machine = elf.read_u16(18)?;
+        let (shoff, shentsize, shnum, shstrndx) = match class {
+            ElfClass::Elf32 => (
+                elf.read_u32(32)? as usize,
+                elf.read_u16(46)? as usize,
+                elf.read_u16(48)? as usize,
+                elf.read_u16(50)? as usize,
+            ),
+            ElfClass::Elf64 => (
+                elf.read_u64(40)? as usize,
+                elf.read_u16(58)? as usize,
+                elf.read_u16(60)? as usize,
+                elf.read_u16(62)? as usize,
+            ),
+        };
+
+        let mut sections = Vec::with_capacity(shnum);
+        let mut name_offsets = Vec::with_capacity(shnum);
+        for i in 0..shnum {
+            let base = entry_offset(shoff, i, shentsize)?;
+            name_offsets.push(elf.read_u32(base)?);
+            sections.push(elf.read_section_header(base)?);
+        }
+
+        if let Some(shstrtab) = sections.get(shstrndx).cloned() {
+            for (section, name_offset) in sections.iter_mut().zip(name_offsets) {
+                section.name = elf.read_str(&shstrtab, name_offset)?;
+            }
+        }
+
+        let (phoff, phentsize, phnum) = match class {
+            ElfClass::Elf32 => (
+                elf.read_u32(28)? as usize,
+                elf.read_u16(42)? as usize,
+                elf.read_u16(44)? as usize,
+            ),
+            ElfClass::Elf64 => (
+                elf.read_u64(32)? as usize,
+                elf.read_u16(54)? as usize,
+                elf.read_u16(56)? as usize,
+            ),
+        };
+
+        let mut segments = Vec::with_capacity(phnum);
+        for i in 0..phnum {
+            segments.push(elf.read_program_header(entry_offset(phoff, i, phentsize)?)?);
+        }
+
+        elf.sections = sections;
+        elf.segments = segments;
+        Ok(elf)
+    }
+
+    /// Returns the class (32-bit or 64-bit) of the ELF file.
+    pub fn class(&self) -> ElfClass {
+        self.class
+    }
+
+    /// Checks if the multi-byte fields of the ELF file are stored in
+    /// little-endian byte order.
+    pub fn is_little_endian(&self) -> bool {
+        self.endian == Endian::Little
+    }
+
+    /// Returns the contents of the section with the given name. `None` is
+    /// returned if the section doesn't exist or has no contents in the file
+    /// (e.g. `.bss`). Compressed sections are returned as is.
+    pub fn section_data(&self, name: &str) -> Option<&[u8]> {
+        let section = self
+            .sections
+            .iter()
+            .find(|s| s.name == name && s.sh_type != SHT_NOBITS)?;
+        self.bytes(section.offset as usize, section.size as usize)
+            .ok()
+    }
+
+    /// Returns the section headers of the ELF file.
+    pub fn sections(&self) -> &[SectionHeader] {
+        &self.sections
+    }
+
+    /// Returns the program headers of the ELF file.
+    pub fn segments(&self) -> &[ProgramHeader] {
+        &self.segments
+    }
+
+    /// Returns the load address (LMA) of the initialized contents at the given
+    /// virtual address if they are loaded from a different address. This is
+    /// the case for initialized data that is stored in ROM and copied to RAM
+    /// at startup. Returns `None` if the address isn't backed by the contents
+    /// of a `PT_LOAD` segment (e.g. BSS) or if the VMA and LMA are the same.
+    pub fn load_addr(&self, addr: u64) -> Option<u64> {
+        self.segments
+            .iter()
+            .filter(|seg| seg.p_type == PT_LOAD)
+            .filter(|seg| seg.vaddr != seg.paddr)
+            .find(|seg| (addr >= seg.vaddr) && (addr - seg.vaddr < seg.filesz))
+            .map(|seg| seg.paddr + (addr - seg.vaddr))
+    }
+
+    /// Returns all entries of the `.symtab` section (except the reserved
+    /// first entry). Returns an [`ErrorKind::InvalidElf`] error if the file
+    /// doesn't contain a symbol table (e.g. because it has been stripped).
+    pub fn symbols(&self) -> Result<Vec<ElfSymbol>, Error> {
+        let symtab = self
+            .sections
+            .iter()
+            .find(|s| s.sh_type == SHT_SYMTAB)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidElf))?;
+        let strtab = self
+            .sections
+            .get(symtab.link as usize)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidElf))?;
+
+        let entsize = match (symtab.entsize, self.class) {
+            (0, ElfClass::Elf32) => 16,
+            (0, ElfClass::Elf64) => 24,
+            (entsize, _) => entsize as usize,
+        };
+        let count = symtab.size as usize / entsize;
+
+        let mut syms = Vec::with_capacity(count.saturating_sub(1));
+        for i in 1..count {
+            let base = entry_offset(symtab.offset as usize, i, entsize)?;
+            // The whole entry has to be within the file, which also keeps the
+            // offsets of its fields from overflowing.
+            let len = match self.class {
+                ElfClass::Elf32 => 16,
+                ElfClass::Elf64 => 24,
+            };
+            self.bytes(base, len)?;
+            let name = self.read_str(strtab, self.read_u32(base)?)?;
+            let sym = match self.class {
+                ElfClass::Elf32 => ElfSymbol {
+                    name,
+                    value: self.read_u32(base + 4)? as u64,
+                    size: self.read_u32(base + 8)? as u64,
+                    info: self.read_u8(base + 12)?,
+                    shndx: self.read_u16(base + 14)?,
+                },
+                ElfClass::Elf64 => ElfSymbol {
+                    name,
+                    info: self.read_u8(base + 4)?,
+                    shndx: self.read_u16(base + 6)?,
+                    value: self.read_u64(base + 8)?,
+                    size: self.read_u64(base + 16)?,
+                },
+            };
+            syms.push(sym);
+        }
+
+        Ok(syms)
+    }
+
+    /// Returns the symbols in the same way as they would be listed by
+    /// `nm --print-size --size-sort`. I.e., debugging, absolute, undefined, and
+    /// symbols without a size are omitted. The names are left mangled.
+    pub fn raw_symbols(&self) -> Result<Vec<RawSymbol>, Error> {
+        Ok(self
+            .section_symbols()?
+            .into_iter()
+            .map(|(raw, _)| raw)
+            .collect())
+    }
+
+    /// Same as [`raw_symbols`] but additionally returns the section header
+    /// referenced by the section index (`st_shndx`) of every symbol. `None` is
+    /// returned for symbols with a reserved section index (e.g. common
+    /// symbols).
+    ///
+    /// [`raw_symbols`]: ElfFile::raw_symbols
+    pub fn section_symbols(&self) -> Result<Vec<(RawSymbol, Option<&SectionHeader>)>, Error> {
+        let syms = self
+            .symbols()?
+            .into_iter()
+            .filter(|s| s.size != 0)
+            .filter(|s| !matches!(s.kind(), STT_SECTION | STT_FILE))
+            .filter(|s| !matches!(s.shndx, SHN_UNDEF | SHN_ABS))
+            .map(|s| {
+                let sym_type = self.sym_type(&s);
+                // The lowest bit of ARM function addresses only marks Thumb code
+                // and is cleared by nm as well.
+                let addr = if self.machine == EM_ARM && s.kind() == STT_FUNC {
+                    s.value & !1
+                } else {
+                    s.value
+                };
+                let section = if s.shndx < SHN_LORESERVE {
+                    self.sections.get(s.shndx as usize)
+                } else {
+                    None
+                };
+                (RawSymbol::new(addr, s.size, sym_type, s.name), section)
+            })
+            .collect();
+
+        Ok(syms)
+    }
+
+    /// Returns the allocated section that contains the given address. This is
+    /// used for symbols where the section index isn't known (e.g. symbols
+    /// parsed from the output of the nm utility).
+    pub fn section_at(&self, addr: u64) -> Option<&SectionHeader> {
+        self.sections.iter().find(|s| s.contains(addr))
+    }
+
+    /// Determines the [`SymbolType`] of a symbol by applying the same rules as
+    /// the nm utility to its binding, type, and the section it is defined in.
+    fn sym_type(&self, sym: &ElfSymbol) -> SymbolType {
+        let c = match (sym.shndx, sym.bind(), sym.kind()) {
+            (SHN_COMMON, _, _) => 'C',
+            (SHN_UNDEF, STB_WEAK, STT_OBJECT) => 'v',
+            (SHN_UNDEF, STB_WEAK, _) => 'w',
+            (SHN_UNDEF, _, _) => 'U',
+            (_, _, STT_GNU_IFUNC) => 'i',
+            (_, STB_WEAK, STT_OBJECT) => 'V',
+            (_, STB_WEAK, _) => 'W',
+            (_, STB_GNU_UNIQUE, _) => 'u',
+            (SHN_ABS, _, _) => 'A',
+            (shndx, bind, _) if shndx < SHN_LORESERVE => {
+                let c = match self.sections.get(shndx as usize) {
+                    Some(section) => section_type(section),
+                    None => '?',
+                };
+                match bind {
+                    STB_GLOBAL => c.to_ascii_uppercase(),
+                    STB_LOCAL => c,
+                    _ => '?',
+                }
+            }
+            _ => '?',
+        };
+
+        SymbolType::from_str(&c.to_string()).unwrap_or(SymbolType::Unknown)
+    }
+
+    /// Reads the program header at `base`.
+    fn read_program_header(&self, base: usize) -> Result<ProgramHeader, Error> {
+        // The whole entry has to be within the file, which also keeps the
+        // offsets of its fields from overflowing.
+        let len = match self.class {
+            ElfClass::Elf32 => 32,
+            ElfClass::Elf64 => 56,
+        };
+        self.bytes(base, len)?;
+        Ok(match self.class {
+            ElfClass::Elf32 => ProgramHeader {
+                p_type: self.read_u32(base)?,
+                offset: self.read_u32(base + 4)? as u64,
+                vaddr: self.read_u32(base + 8)? as u64,
+                paddr: self.read_u32(base + 12)? as u64,
+                filesz: self.read_u32(base + 16)? as u64,
+                memsz: self.read_u32(base + 20)? as u64,
+                flags: self.read_u32(base + 24)?,
+            },
+            ElfClass::Elf64 => ProgramHeader {
+                p_type: self.read_u32(base)?,
+                flags: self.read_u32(base + 4)?,
+                offset: self.read_u64(base + 8)?,
+                vaddr: self.read_u64(base + 16)?,
+                paddr: self.read_u64(base + 24)?,
+                filesz: self.read_u64(base + 32)?,
+                memsz: self.read_u64(base + 40)?,
+            },
+        })
+    }
+
+    /// Reads the section header at `base` without resolving its name.
+    fn read_section_header(&self, base: usize) -> Result<SectionHeader, Error> {
+        // The whole entry has to be within the file, which also keeps the
+        // offsets of its fields from overflowing.
+        let len = match self.class {
+            ElfClass::Elf32 => 40,
+            ElfClass::Elf64 => 64,
+        };
+        self.bytes(base, len)?;
+        Ok(match self.class {
+            ElfClass::Elf32 => SectionHeader {
+                name: String::new(),
+                sh_type: self.read_u32(base + 4)?,
+                flags: self.read_u32(base + 8)? as u64,
+                addr: self.read_u32(base + 12)? as u64,
+                offset: self.read_u32(base + 16)? as u64,
+                size: self.read_u32(base + 20)? as u64,
+                link: self.read_u32(base + 24)?,
+                entsize: self.read_u32(base + 36)? as u64,
+            },
+            ElfClass::Elf64 => SectionHeader {
+                name: String::new(),
+                sh_type: self.read_u32(base + 4)?,
+                flags: self.read_u64(base + 8)?,
+                addr: self.read_u64(base + 16)?,
+                offset: self.read_u64(base + 24)?,
+                size: self.read_u64(base + 32)?,
+                link: self.read_u32(base + 40)?,
+                entsize: self.read_u64(base + 56)?,
+            },
+        })
+    }
+
+    fn bytes(&self, offset: usize, len: usize) -> Result<&[u8], Error> {
+        let end = offset
+            .checked_add(len)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidElf))?;
+        self.data
+            .get(offset..end)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidElf))
+    }
+
+    fn read_u8(&self, offset: usize) -> Result<u8, Error> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    fn read_u16(&self, offset: usize) -> Result<u16, Error> {
+        let bytes = self.bytes(offset, 2)?.try_into().unwrap();
+        Ok(match self.endian {
+            Endian::Little => u16::from_le_bytes(bytes),
+            Endian::Big => u16::from_be_bytes(bytes),
+        })
+    }
+
+    fn read_u32(&self, offset: usize) -> Result<u32, Error> {
+        let bytes = self.bytes(offset, 4)?.try_into().unwrap();
+        Ok(match self.endian {
+            Endian::Little => u32::from_le_bytes(bytes),
+            Endian::Big => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn read_u64(&self, offset: usize) -> Result<u64, Error> {
+        let bytes = self.bytes(offset, 8)?.try_into().unwrap();
+        Ok(match self.endian {
+            Endian::Little => u64::from_le_bytes(bytes),
+            Endian::Big => u64::from_be_bytes(bytes),
+        })
+    }
+
+    /// Reads a null-terminated string at `offset` within the string table
+    /// `strtab`.
+    fn read_str(&self, strtab: &SectionHeader, offset: u32) -> Result<String, Error> {
+        let bytes = self.bytes(strtab.offset as usize, strtab.size as usize)?;
+        let bytes = bytes
+            .get(offset as usize..)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidElf))?;
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+}
+
+/// Returns the offset of the entry at `index` of a table starting at `offset`
+/// with entries of `entsize` bytes. Returns an [`ErrorKind::InvalidElf`] error
+/// if the offset overflows (e.g. for malformed header fields).
+fn entry_offset(offset: usize, index: usize, entsize: usize) -> Result<usize, Error> {
+    index
+        .checked_mul(entsize)
+        .and_then(|o| o.checked_add(offset))
+        .ok_or_else(|| Error::new(ErrorKind::InvalidElf))
+}
+
+/// Returns the nm type character (lowercase) for symbols defined in the given
+/// section. Like nm, well-known section names take precedence over the section
+/// flags.
+fn section_type(section: &SectionHeader) -> char {
+    const NAMES: [(&str, char); 10] = [
+        (".bss", 'b'),
+        (".data", 'd'),
+        (".debug", 'N'),
+        (".fini", 't'),
+        (".init", 't'),
+        (".rodata", 'r'),
+        (".sbss", 's'),
+        (".sdata", 'g'),
+        (".text", 't'),
+        (".pdata", 'p'),
+    ];
+
+    if let Some((_, c)) = NAMES
+        .iter()
+        .find(|(name, _)| section.name.starts_with(name))
+    {
+        return *c;
+    }
+
+    let alloc = section.flags & SHF_ALLOC != 0;
+    let has_contents = section.sh_type != SHT_NOBITS;
+    if section.flags & SHF_EXECINSTR != 0 {
+        't'
+    } else if alloc && has_contents {
+        if section.flags & SHF_WRITE == 0 {
+            'r'
+        } else {
+            'd'
+        }
+    } else if !has_contents {
+        'b'
+    } else if section.name.starts_with(".debug") {
+        'N'
+    } else if section.flags & SHF_WRITE == 0 {
+        'n'
+    } else {
+        '?'
+    }
+}
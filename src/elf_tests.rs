@@ -0,0 +1,303 @@
+mod elffile_tests {
+    use super::super::*;
+
+    #[test]
+    fn open_not_found() {
+        let err = ElfFile::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn parse_invalid_magic() {
+        let err = ElfFile::parse(vec![0; 64]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+    }
+
+    #[test]
+    fn parse_truncated() {
+        let err = ElfFile::parse(b"\x7fELF\x01\x01".to_vec()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+    }
+
+    /// Returns the header of a little-endian 64-bit ELF file with `shnum`
+    /// section headers of `shentsize` bytes at `shoff`.
+    fn header64(shoff: u64, shentsize: u16, shnum: u16) -> Vec<u8> {
+        let mut data = vec![0; 64];
+        data[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        data[40..48].copy_from_slice(&shoff.to_le_bytes());
+        data[58..60].copy_from_slice(&shentsize.to_le_bytes());
+        data[60..62].copy_from_slice(&shnum.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parse_section_header_offset_overflow() {
+        let err = ElfFile::parse(header64(u64::MAX, 64, 2)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+        let err = ElfFile::parse(header64(u64::MAX - 8, 0, 1)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+    }
+
+    #[test]
+    fn parse_section_name_offset_overflow() {
+        // A single section header which is its own string table at an offset
+        // that overflows when the name offset is added.
+        let mut data = header64(64, 64, 1);
+        data.extend_from_slice(&[0xff; 64]);
+        data[64..68].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = ElfFile::parse(data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+    }
+
+    #[test]
+    fn open_not_elf() {
+        let err = ElfFile::open("readme.md").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+    }
+
+    #[test]
+    fn sections() {
+        let elf = ElfFile::open("test_data/c_app/app").unwrap();
+        assert_eq!(elf.class(), ElfClass::Elf32);
+        let sections = elf.sections();
+        assert_eq!(sections.len(), 18);
+        assert_eq!(sections[2].name, ".text");
+        assert_eq!(sections[2].addr, 0x0000800c);
+        assert_eq!(sections[2].size, 0x0000040c);
+        assert_eq!(sections[2].flags, SHF_ALLOC | SHF_EXECINSTR);
+        assert_eq!(sections[10].name, ".bss");
+        assert_eq!(sections[10].sh_type, SHT_NOBITS);
+        assert_eq!(sections[15].name, ".symtab");
+        assert_eq!(sections[15].sh_type, SHT_SYMTAB);
+    }
+
+    #[test]
+    fn symbols() {
+        let elf = ElfFile::open("test_data/c_app/app").unwrap();
+        let syms = elf.symbols().unwrap();
+        // The reserved null symbol is skipped.
+        assert_eq!(syms.len(), 147);
+        assert_eq!(syms[0].name, "");
+        assert_eq!(syms[0].kind(), STT_SECTION);
+        assert_eq!(syms[0].shndx, 1);
+        assert_eq!(syms[25].name, "register_fini");
+        assert_eq!(syms[25].value, 0x0000802d);
+        assert_eq!(syms[25].size, 20);
+        assert_eq!(syms[25].bind(), STB_LOCAL);
+        assert_eq!(syms[25].kind(), STT_FUNC);
+        assert_eq!(syms[25].shndx, 2);
+    }
+
+    #[test]
+    fn raw_symbols() {
+        let elf = ElfFile::open("test_data/c_app/app").unwrap();
+        let syms = elf.raw_symbols().unwrap();
+        assert_eq!(syms.len(), 39);
+
+        // Thumb bit of the function address is cleared.
+        assert!(syms.contains(&RawSymbol::new(
+            0x0000802c,
+            0x00000014,
+            SymbolType::TextSection,
+            String::from("register_fini")
+        )));
+
+        assert!(syms.contains(&RawSymbol::new(
+            0x000184b0,
+            0x00000428,
+            SymbolType::DataSection,
+            String::from("impure_data")
+        )));
+        assert!(syms.contains(&RawSymbol::new(
+            0x000188dc,
+            0x00000001,
+            SymbolType::BssSection,
+            String::from("completed.8911")
+        )));
+        assert!(syms.contains(&RawSymbol::new(
+            0x000081c4,
+            0x000000a0,
+            SymbolType::TextSection,
+            String::from("memset")
+        )));
+        assert!(syms.contains(&RawSymbol::new(
+            0x0000843c,
+            0x00000004,
+            SymbolType::ReadOnlyDataSection,
+            String::from("_global_impure_ptr")
+        )));
+    }
+
+    #[test]
+    fn elf64() {
+        let elf = ElfFile::open("test_data/c_app_x86_64/app").unwrap();
+        assert_eq!(elf.class(), ElfClass::Elf64);
+        let text = elf.sections().iter().find(|s| s.name == ".text").unwrap();
+        assert_eq!(text.flags, SHF_ALLOC | SHF_EXECINSTR);
+
+        let syms = elf.raw_symbols().unwrap();
+        assert_eq!(syms.len(), 10);
+        assert!(syms.contains(&RawSymbol::new(
+            0x0000000000401134,
+            0x0000000000000075,
+            SymbolType::TextSection,
+            String::from("main")
+        )));
+        assert!(syms.contains(&RawSymbol::new(
+            0x000000000040111a,
+            0x000000000000001a,
+            SymbolType::TextSection,
+            String::from("triple_mult")
+        )));
+        assert!(syms.contains(&RawSymbol::new(
+            0x0000000000404020,
+            0x000000000000004e,
+            SymbolType::DataSection,
+            String::from("c_app_static_arr")
+        )));
+        assert!(syms.contains(&RawSymbol::new(
+            0x00000000004040a0,
+            0x0000000000000080,
+            SymbolType::BssSection,
+            String::from("c_app_bss_arr")
+        )));
+    }
+
+    #[test]
+    fn section_symbols() {
+        let elf = ElfFile::open("test_data/c_app/app").unwrap();
+        let syms = elf.section_symbols().unwrap();
+        assert_eq!(syms.len(), 39);
+
+        let impure_data = RawSymbol::new(
+            0x000184b0,
+            0x00000428,
+            SymbolType::DataSection,
+            String::from("impure_data"),
+        );
+        let (_, section) = syms.iter().find(|(raw, _)| *raw == impure_data).unwrap();
+        assert_eq!(section.unwrap().name, ".data");
+
+        let completed = RawSymbol::new(
+            0x000188dc,
+            0x00000001,
+            SymbolType::BssSection,
+            String::from("completed.8911"),
+        );
+        let (_, section) = syms.iter().find(|(raw, _)| *raw == completed).unwrap();
+        assert_eq!(section.unwrap().name, ".bss");
+    }
+
+    #[test]
+    fn section_at() {
+        let elf = ElfFile::open("test_data/c_app/app").unwrap();
+        assert_eq!(elf.section_at(0x0000800c).unwrap().name, ".text");
+        assert_eq!(elf.section_at(0x0000802c).unwrap().name, ".text");
+        assert_eq!(elf.section_at(0x000188dc).unwrap().name, ".bss");
+        assert_eq!(elf.section_at(0x0000843c).unwrap().name, ".rodata");
+        assert!(elf.section_at(0).is_none());
+    }
+
+    #[test]
+    fn load_addr() {
+        let elf = ElfFile::open("test_data/c_app_lma/app").unwrap();
+        let loads = elf
+            .segments()
+            .iter()
+            .filter(|seg| seg.p_type == PT_LOAD)
+            .count();
+        assert_eq!(loads, 3);
+
+        // .data is located in RAM but loaded from FLASH.
+        assert_eq!(elf.load_addr(0x20000000), Some(0x0800010e));
+        assert_eq!(elf.load_addr(0x2000003c), Some(0x0800014a));
+        // .bss has no contents and .text is executed in place.
+        assert_eq!(elf.load_addr(0x20000040), None);
+        assert_eq!(elf.load_addr(0x08000000), None);
+
+        // VMA and LMA are the same for hosted executables.
+        let elf = ElfFile::open("test_data/c_app/app").unwrap();
+        assert_eq!(elf.load_addr(0x000184b0), None);
+    }
+
+    #[test]
+    fn raw_symbols_weak() {
+        let elf = ElfFile::open("test_data/c_app_c_lib_rust_lib/app").unwrap();
+        let syms = elf.raw_symbols().unwrap();
+        assert!(syms.contains(&RawSymbol::new(
+            0x00008218,
+            0x0000014c,
+            SymbolType::Weak,
+            String::from("memcpy")
+        )));
+    }
+}
+
+mod section_type_tests {
+    use super::super::*;
+
+    fn section(name: &str, sh_type: u32, flags: u64) -> SectionHeader {
+        SectionHeader {
+            name: String::from(name),
+            sh_type,
+            flags,
+            addr: 0,
+            offset: 0,
+            size: 0,
+            link: 0,
+            entsize: 0,
+        }
+    }
+
+    #[test]
+    fn by_name() {
+        assert_eq!(section_type(&section(".text.main", 1, 0)), 't');
+        assert_eq!(section_type(&section(".init_array", 14, 3)), 't');
+        assert_eq!(section_type(&section(".rodata.str1.1", 1, 0)), 'r');
+        assert_eq!(section_type(&section(".sdata", 1, 3)), 'g');
+        assert_eq!(section_type(&section(".sbss", SHT_NOBITS, 3)), 's');
+        assert_eq!(section_type(&section(".bss.foo", SHT_NOBITS, 3)), 'b');
+    }
+
+    #[test]
+    fn mem_region() {
+        assert_eq!(
+            section(".text", 1, SHF_ALLOC | SHF_EXECINSTR).mem_region(),
+            MemoryRegion::Rom
+        );
+        assert_eq!(
+            section(".rodata", 1, SHF_ALLOC).mem_region(),
+            MemoryRegion::Rom
+        );
+        assert_eq!(
+            section(".sdata", 1, SHF_ALLOC | SHF_WRITE).mem_region(),
+            MemoryRegion::Ram
+        );
+        assert_eq!(
+            section(".sbss", SHT_NOBITS, SHF_ALLOC | SHF_WRITE).mem_region(),
+            MemoryRegion::Ram
+        );
+        assert_eq!(
+            section(".comment", 1, 0).mem_region(),
+            MemoryRegion::Unknown
+        );
+    }
+
+    #[test]
+    fn by_flags() {
+        assert_eq!(
+            section_type(&section("rom_start", 1, SHF_ALLOC | SHF_EXECINSTR)),
+            't'
+        );
+        assert_eq!(section_type(&section("device_area", 1, SHF_ALLOC)), 'r');
+        assert_eq!(
+            section_type(&section("k_mutex_area", 1, SHF_ALLOC | SHF_WRITE)),
+            'd'
+        );
+        assert_eq!(
+            section_type(&section("noinit", SHT_NOBITS, SHF_ALLOC | SHF_WRITE)),
+            'b'
+        );
+        assert_eq!(section_type(&section(".comment", 1, 0)), 'n');
+    }
+}
@@ -18,6 +18,24 @@ pub enum ErrorKind {
     InvalidEnumStr,
     /// Invoking the nm utility returned an error.
     Nm,
+    /// The file is not a valid ELF file or is missing required parts (e.g.
+    /// the symbol table).
+    InvalidElf,
+    /// The DWARF debugging information of the ELF file could not be parsed.
+    InvalidDwarf,
+    /// The linker map file could not be parsed (e.g. unknown format).
+    InvalidMap,
+    /// The static library is not a valid `ar` archive.
+    InvalidArchive,
+    /// The linker script could not be parsed (e.g. missing `MEMORY` command or
+    /// invalid expression).
+    InvalidLinkerScript,
+    /// The region file could not be parsed (e.g. unknown memory or invalid
+    /// size).
+    InvalidRegions,
+    /// The budget file could not be parsed (e.g. unknown language or invalid
+    /// size).
+    InvalidBudget,
     /// Generic IO error.
     Io,
     /// The table could not be formatted (e.g. terminal width to small to fit
@@ -64,7 +82,7 @@ impl Error {
     /// # use std::io;
     /// # use atlas::Atlas;
     /// // Cause an error by trying to open non-existing files.
-    /// let err = Atlas::new("/foo", "/bar").unwrap_err();
+    /// let err = Atlas::new("/foo").unwrap_err();
     /// let cause = err.into_cause().unwrap();
     /// let original_error = cause.downcast::<io::Error>().unwrap();
     /// assert_eq!(original_error.kind(), io::ErrorKind::NotFound);
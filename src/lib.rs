@@ -12,26 +12,61 @@
 
 #[macro_use]
 extern crate prettytable;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::str::FromStr;
+
+pub mod ar;
+pub use ar::ArFile;
+
+pub mod budget;
+pub use budget::{Budget, CheckReport};
+
+pub mod demangle;
 
 pub mod detect;
-pub use detect::{LangDetector, Library};
+pub use detect::{LangDetector, Library, LinkedSymbols, RuntimeDuplicate};
+
+pub mod dwarf;
+pub use dwarf::DwarfInfo;
+
+pub mod elf;
+pub use elf::{ElfFile, SectionHeader};
 
 pub mod error;
 pub use error::{Error, ErrorKind};
 
+pub mod map;
+pub use map::MapFile;
+
+pub mod regions;
+pub use regions::RegionFile;
+
+pub mod script;
+pub use script::{LinkerRegion, LinkerScript};
+
 pub mod sym;
 pub use sym::{MemoryRegion, RawSymbol, Symbol, SymbolLang, SymbolType};
 
 pub mod report;
-pub use report::{CombinedMem, LangReport, SymbolReport};
+pub use report::{
+    CombinedMem, CrateReport, CrateVersion, DiffReport, DuplicateCrate, DuplicateReport, FileNode,
+    FileReport, GenericFn, GenericReport, JsonReport, LangReport, LibReport, LibUsage,
+    ObjectReport, ObjectSize, PaddingGap, PaddingReport, RegionReport, RegionUsage, RuntimeReport,
+    SectionPadding, SectionReport, SectionSize, SymbolChange, SymbolDiff, SymbolReport, TreeNode,
+    TreeReport,
+};
 
 #[cfg(test)]
 #[path = "./lib_tests.rs"]
 mod lib_tests;
 
+/// Symbols and failed nm lines returned by the different ways of analyzing the
+/// ELF file.
+type AnalyzeResult = (Vec<Symbol>, Vec<String>);
+
 /// Conducts the analysis of the ELF file and generates report type for printing
 /// the gathered information.
 ///
@@ -43,54 +78,202 @@ mod lib_tests;
 // - Compare the performance to using other collections (e.g. HashMap, BTreeMap)
 #[derive(Debug)]
 pub struct Atlas {
-    /// Canonicalized path to the nm utility
-    pub nm: PathBuf,
+    /// Canonicalized path to the nm utility used for parsing the ELF file.
+    /// Without it, the ELF file is read in-process.
+    pub nm: Option<PathBuf>,
     /// Absolute path to the ELF binary
     pub elf: PathBuf,
+    /// Canonicalized path to the linker map file of the ELF binary. If set,
+    /// the symbols are attributed to the archives and object files recorded in
+    /// the map file.
+    pub map: Option<PathBuf>,
+    /// Canonicalized path to the linker script of the ELF binary. If set, the
+    /// symbols are assigned to the memory regions declared in its `MEMORY`
+    /// command.
+    pub script: Option<PathBuf>,
+    /// Canonicalized path to the region file declaring additional memory
+    /// regions by address range (see [`crate::regions`]).
+    pub region_file: Option<PathBuf>,
     /// Absolute path to the static libraries
     pub libs: Vec<Library>,
+    /// Use the DWARF debugging information of the ELF file for detecting the
+    /// language of the symbols (see [`LangDetector::set_dwarf`]).
+    pub dwarf: bool,
     /// Vector containing the symbols with their identified origin language.
     pub syms: Option<Vec<Symbol>>,
-    /// Vector containing the strings (mangled and demangled) of all symbols
-    /// whose language couldn't be determined
-    pub fails: Option<Vec<(String, String)>>,
+    /// Vector containing the lines of the nm output that couldn't be parsed
+    /// into symbols. Before version 0.2, this contained pairs of the mangled
+    /// and demangled lines.
+    pub fails: Option<Vec<String>>,
+    /// Vector containing the symbols that are contained in more than one of
+    /// the Rust libraries (see [`LangDetector::runtime_duplicates`]). Empty
+    /// if fewer than two Rust libraries have been parsed.
+    pub runtime: Option<Vec<RuntimeDuplicate>>,
+    /// Vector containing the number of linked symbols of every parsed library
+    /// (see [`LangDetector::linked_symbols`]). Empty if the libraries haven't
+    /// been parsed.
+    pub linked: Option<Vec<LinkedSymbols>>,
+    /// Vector containing the memory regions of the region file followed by
+    /// the ones of the linker script (see [`Atlas::set_region_file`] and
+    /// [`Atlas::set_script`]). Empty if neither has been set.
+    pub regions: Option<Vec<LinkerRegion>>,
+    /// Vector containing the allocated sections of the ELF file in the order
+    /// of the section header table.
+    pub sections: Option<Vec<SectionHeader>>,
+    /// Vector containing a symbol for every address range of the allocated
+    /// sections that isn't covered by any symbol (e.g. literal pools,
+    /// alignment fill, or linker-generated tables). They are named after their
+    /// section and their language is [`SymbolLang::Any`]. If a linker map file
+    /// has been set, they are split along the input sections of the map file
+    /// and named after them instead (e.g. [`map::FILL`]).
+    pub unattributed: Option<Vec<Symbol>>,
 }
 
 impl Atlas {
     /// Creates a new instance of the [`Atlas`] struct by checking and storing
-    /// all the paths needed for analysis. Returns an [`ErrorKind::Io`] error if
-    /// any of the files couldn't be found or a "permission denied" error
-    /// occurred.
-    ///
-    /// It is recommended to use the exact nm utility that was used when
-    /// building the ELF file as otherwise errors could occur while demangling
-    /// of the Rust and Cpp symbols.
-    ///
+    /// the path to the ELF file. Returns an [`ErrorKind::Io`] error if the
+    /// file couldn't be found or a "permission denied" error occurred.
     ///
-    /// All path provided can either be absolute or relative.
-    pub fn new<N, E>(nm: N, elf: E) -> Result<Self, Error>
+    /// The path can either be absolute or relative.
+    pub fn new<E>(elf: E) -> Result<Self, Error>
     where
-        N: AsRef<Path>,
         E: AsRef<Path>,
     {
         let curr = std::env::current_dir().unwrap();
 
-        let nm = curr.join(nm.as_ref()).canonicalize()?;
         let elf = curr.join(elf.as_ref()).canonicalize()?;
 
         // Check permission by opening and closing files
-        let _ = File::open(&nm)?;
         let _ = File::open(&elf)?;
 
         Ok(Atlas {
-            nm,
+            nm: None,
             elf,
+            map: None,
+            script: None,
+            region_file: None,
             libs: Vec::new(),
+            dwarf: false,
             syms: None,
             fails: None,
+            runtime: None,
+            linked: None,
+            regions: None,
+            sections: None,
+            unattributed: None,
         })
     }
 
+    /// Same as [`Atlas::new`] but additionally sets the path to the nm utility
+    /// (see [`Atlas::set_nm`]).
+    pub fn with_nm<N, E>(nm: N, elf: E) -> Result<Self, Error>
+    where
+        N: AsRef<Path>,
+        E: AsRef<Path>,
+    {
+        let mut at = Atlas::new(elf)?;
+        at.set_nm(nm)?;
+        Ok(at)
+    }
+
+    /// Sets the path to the nm utility which is then used by
+    /// [`Atlas::analyze`] for parsing the ELF file.
+    /// Returns an [`ErrorKind::Io`] error if the file couldn't be found or a
+    /// "permission denied" error occurred.
+    ///
+    /// It is recommended to use the exact nm utility that was used when
+    /// building the ELF file as otherwise errors could occur while demangling
+    /// of the Rust and Cpp symbols.
+    pub fn set_nm<N>(&mut self, nm: N) -> Result<(), Error>
+    where
+        N: AsRef<Path>,
+    {
+        let curr = std::env::current_dir().unwrap();
+
+        let nm = curr.join(nm.as_ref()).canonicalize()?;
+
+        // Check permission by opening and closing files
+        let _ = File::open(&nm)?;
+
+        self.nm = Some(nm);
+
+        Ok(())
+    }
+
+    /// Sets the path to the linker map file (GNU ld or LLD) generated while
+    /// linking the ELF file. Returns an [`ErrorKind::Io`] error if the file
+    /// couldn't be found or a "permission denied" error occurred.
+    ///
+    /// With a map file, [`Atlas::analyze`] determines the language of a symbol
+    /// by the library it was actually linked from instead of comparing it to
+    /// the symbols of the libraries. Furthermore, the bytes of input sections
+    /// that aren't covered by any symbol (e.g. fill bytes or string literals)
+    /// are added as additional symbols named after the input section.
+    pub fn set_map<M>(&mut self, map: M) -> Result<(), Error>
+    where
+        M: AsRef<Path>,
+    {
+        let curr = std::env::current_dir().unwrap();
+
+        let map = curr.join(map.as_ref()).canonicalize()?;
+
+        // Check permission by opening and closing files
+        let _ = File::open(&map)?;
+
+        self.map = Some(map);
+
+        Ok(())
+    }
+
+    /// Sets the path to the linker script (e.g. `linker.cmd` or `memory.x`)
+    /// used for linking the ELF file. Returns an [`ErrorKind::Io`] error if the
+    /// file couldn't be found or a "permission denied" error occurred.
+    ///
+    /// With a linker script, [`Atlas::analyze`] assigns every symbol to the
+    /// memory region of the `MEMORY` command containing its address (see
+    /// [`Symbol::linker_region`]) and [`Atlas::report_regions`] reports how
+    /// full the regions are.
+    pub fn set_script<S>(&mut self, script: S) -> Result<(), Error>
+    where
+        S: AsRef<Path>,
+    {
+        let curr = std::env::current_dir().unwrap();
+
+        let script = curr.join(script.as_ref()).canonicalize()?;
+
+        // Check permission by opening and closing files
+        let _ = File::open(&script)?;
+
+        self.script = Some(script);
+
+        Ok(())
+    }
+
+    /// Sets the path to the region file declaring memory regions by address
+    /// range (see [`crate::regions`]). Returns an [`ErrorKind::Io`] error if
+    /// the file couldn't be found or a "permission denied" error occurred.
+    ///
+    /// With a region file, [`Atlas::analyze`] classifies the symbols within
+    /// the declared regions as ROM or RAM by their address. This affects all
+    /// reports using the [`MemoryRegion`] of the symbols (e.g.
+    /// [`Atlas::report_lang`] and [`Atlas::report_syms`]). The regions take
+    /// precedence over overlapping regions of the linker script.
+    pub fn set_region_file<R>(&mut self, region_file: R) -> Result<(), Error>
+    where
+        R: AsRef<Path>,
+    {
+        let curr = std::env::current_dir().unwrap();
+
+        let region_file = curr.join(region_file.as_ref()).canonicalize()?;
+
+        // Check permission by opening and closing files
+        let _ = File::open(&region_file)?;
+
+        self.region_file = Some(region_file);
+
+        Ok(())
+    }
+
     /// Adds libraries to the [`Atlas`] struct which will be used to determine
     /// their origin when calling [`Atlas::analyze`]. The path can be either absolute
     /// or relative.
@@ -112,108 +295,309 @@ impl Atlas {
         Ok(())
     }
 
-    /// Analyzes the ELF file using the nm utility and static libraries, and
-    /// stores the created symbols in the `syms` Vec. Failed symbols are stored
-    /// in the `fails` Vec as a tuple of Strings (mangled, demangled).
+    /// Analyzes the ELF file and stores the created symbols in the `syms` Vec.
+    /// Lines of the nm output that couldn't be parsed are stored in the `fails`
+    /// Vec. The demangled names of the symbols are always derived from the
+    /// mangled ones in-process (see [`demangle::demangle`]).
+    ///
+    /// If the path to the nm utility has been set, it is used for parsing the
+    /// ELF file. Otherwise, the symbol table of the ELF file is read
+    /// in-process. The static libraries are always read in-process (see
+    /// [`LangDetector::add_archive`]). If a linker map file has been set (see
+    /// [`Atlas::set_map`]), only the Rust libraries are parsed to find the
+    /// copies of the Rust runtime (see [`Atlas::runtime`]).
     pub fn analyze(&mut self) -> Result<(), Error> {
+        let map = match &self.map {
+            Some(path) => Some(MapFile::open(path)?),
+            None => None,
+        };
+        let mut regions = match &self.region_file {
+            Some(path) => RegionFile::open(path)?.regions().to_vec(),
+            None => Vec::new(),
+        };
+        if let Some(path) = &self.script {
+            regions.extend_from_slice(LinkerScript::open(path)?.regions());
+        }
+
         let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
-        for lib in &self.libs {
-            detector.add_lib(&self.nm, lib).unwrap();
+        if self.dwarf {
+            detector.set_dwarf(DwarfInfo::open(&self.elf)?);
+        }
+        if map.is_none() {
+            for lib in &self.libs {
+                detector.add_archive(lib)?;
+            }
         }
 
-        let mangled_out = Command::new(&self.nm)
-            .arg("--print-size")
-            .arg("--size-sort")
-            .arg(&self.elf)
-            .output()
-            .map_err(|io_error| Error::new(ErrorKind::Io).with(io_error))?;
+        let elf = ElfFile::open(&self.elf)?;
+        let (mut syms, fails) = match &self.nm {
+            Some(nm) => self.analyze_nm(nm, &elf, &detector)?,
+            None => self.analyze_elf(&elf, &detector)?,
+        };
 
-        if !mangled_out.status.success() {
-            return Err(Error::new(ErrorKind::Nm));
+        let mut unattributed = unattributed_syms(&elf, &syms);
+        if let Some(map) = &map {
+            self.attribute_map(map, &mut syms, &mut unattributed);
+        }
+        if let Some(dwarf) = detector.dwarf() {
+            for s in syms.iter_mut() {
+                s.unit = dwarf.unit_at(s.addr).and_then(|unit| unit.name.clone());
+                s.file = dwarf.file_at(s.addr).map(String::from);
+            }
+        }
+        for s in syms.iter_mut().chain(unattributed.iter_mut()) {
+            if let Some(region) = regions.iter().find(|r| r.contains(s.addr)) {
+                s.linker_region = Some(region.name.clone());
+                if let Some(memory) = region.memory {
+                    s.mem_region = memory;
+                }
+            }
         }
 
-        let mangled_str = std::str::from_utf8(&mangled_out.stdout)
-            .map_err(|str_error| Error::new(ErrorKind::Nm).with(str_error))?;
+        // The symbols *should* already be sorted but the `is_sorted_by_key`
+        // method is not yet stable. Therefore, the symbols are sorted here just
+        // to make sure. The `--size-sort` flag from the nm call should also not
+        // be removed as this gets rid of a lot of symbols that don't have a
+        // size at all (e.g. Kconfigs "00000001 A CONFIG_SHELL").
+        syms.sort_by_key(|s| s.size);
+        self.runtime = Some(match &map {
+            // The libraries aren't needed to attribute the symbols but the
+            // copies of the Rust runtime they contain still have to be known.
+            Some(_) => {
+                let mut detector = LangDetector::new(SymbolLang::C, SymbolLang::Cpp);
+                for lib in self.libs.iter().filter(|l| l.lang() == SymbolLang::Rust) {
+                    detector.add_archive(lib)?;
+                }
+                detector.runtime_duplicates(&syms)
+            }
+            None => detector.runtime_duplicates(&syms),
+        });
+        self.linked = Some(detector.linked_symbols(&syms));
+        self.regions = Some(regions);
+        self.sections = Some(
+            elf.sections()
+                .iter()
+                .filter(|s| s.mem_region() != MemoryRegion::Unknown)
+                .cloned()
+                .collect(),
+        );
+        self.unattributed = Some(unattributed);
+        self.syms = Some(syms);
+        self.fails = Some(fails);
+
+        Ok(())
+    }
 
-        let demangled_out = Command::new(&self.nm)
+    /// Reads the symbols directly from the symbol table of the ELF file. The
+    /// memory region of every symbol is derived from the section referenced by
+    /// its section index.
+    fn analyze_elf(&self, elf: &ElfFile, detector: &LangDetector) -> Result<AnalyzeResult, Error> {
+        let syms = elf
+            .section_symbols()?
+            .into_iter()
+            .map(|(raw, section)| {
+                let mut sym = Symbol::from_rawsymbol(raw).with_section(section);
+                sym.lma = elf.load_addr(sym.addr);
+                detector.detect_symbol(sym)
+            })
+            .collect();
+
+        Ok((syms, Vec::new()))
+    }
+
+    /// Parses the output of the nm utility. As nm doesn't print the section of
+    /// a symbol, the memory region is derived from the section containing the
+    /// address of the symbol.
+    fn analyze_nm(
+        &self,
+        nm: &Path,
+        elf: &ElfFile,
+        detector: &LangDetector,
+    ) -> Result<AnalyzeResult, Error> {
+        let out = Command::new(nm)
             .arg("--print-size")
             .arg("--size-sort")
-            .arg("--demangle")
             .arg(&self.elf)
             .output()
             .map_err(|io_error| Error::new(ErrorKind::Io).with(io_error))?;
 
-        if !demangled_out.status.success() {
+        if !out.status.success() {
             return Err(Error::new(ErrorKind::Nm));
         }
 
-        let demangled_str = std::str::from_utf8(&demangled_out.stdout)
+        let out_str = std::str::from_utf8(&out.stdout)
             .map_err(|str_error| Error::new(ErrorKind::Nm).with(str_error))?;
 
         let mut syms = Vec::new();
         let mut fails = Vec::new();
 
-        for (mangled, demangled) in mangled_str.lines().zip(demangled_str.lines()) {
-            let detected = match detector.detect(mangled, demangled) {
-                Ok(g) => g,
-                Err(_) => {
-                    fails.push((String::from(mangled), String::from(demangled)));
-                    continue;
+        for line in out_str.lines() {
+            match RawSymbol::from_str(line) {
+                Ok(raw) => {
+                    let sym = Symbol::from_rawsymbol(raw);
+                    let section = elf.section_at(sym.addr);
+                    let mut sym = sym.with_section(section);
+                    sym.lma = elf.load_addr(sym.addr);
+                    syms.push(detector.detect_symbol(sym));
                 }
-            };
-            syms.push(detected);
+                Err(_) => fails.push(String::from(line)),
+            }
         }
 
-        // The symbols *should* already be sorted but the `is_sorted_by_key`
-        // method is not yet stable. Therefore, the symbols are sorted here just
-        // to make sure. The `--size-sort` flag from the nm call should also not
-        // be removed as this gets rid of a lot of symbols that don't have a
-        // size at all (e.g. Kconfigs "00000001 A CONFIG_SHELL").
-        syms.sort_by_key(|s| s.size);
-        self.syms = Some(syms);
-        self.fails = Some(fails);
+        Ok((syms, fails))
+    }
 
-        Ok(())
+    /// Attributes the symbols to the archives and object files recorded in the
+    /// map file. The language of symbols linked from one of the added
+    /// libraries is set to the language of the library. Afterwards, the
+    /// unattributed address ranges are split along the input sections of the
+    /// map file and named after them (e.g. `.rodata.str1.1` or [`map::FILL`]).
+    /// The parts belonging to an input section that has been linked from one of
+    /// the added libraries are moved to the symbols with the language of the
+    /// library. All other parts stay unattributed.
+    fn attribute_map(&self, map: &MapFile, syms: &mut Vec<Symbol>, unattributed: &mut Vec<Symbol>) {
+        let attribute = |sym: &mut Symbol, input: &map::InputSection| {
+            sym.archive = input.archive.clone();
+            sym.object = input.object.clone();
+            if let Some(archive) = &input.archive {
+                if let Some(lib) = self.libs.iter().find(|l| l.matches_archive(archive)) {
+                    sym.lang = lib.lang();
+                    sym.lib = Some(lib.path().to_path_buf());
+                }
+            }
+        };
+
+        for sym in syms.iter_mut() {
+            if let Some(input) = map.section_at(sym.addr) {
+                attribute(sym, input);
+            }
+        }
+
+        for gap in std::mem::take(unattributed) {
+            let end = gap.addr + gap.size;
+            let mut parts = Vec::new();
+            let mut next = gap.addr;
+            for input in map.sections_in(gap.addr, end) {
+                let s = std::cmp::max(input.addr, next);
+                let e = std::cmp::min(input.addr + input.size, end);
+                if e <= s {
+                    continue;
+                }
+                if s > next {
+                    parts.push((next, s, None));
+                }
+                parts.push((s, e, Some(input)));
+                next = e;
+            }
+            if next < end {
+                parts.push((next, end, None));
+            }
+
+            for (s, e, input) in parts {
+                let mut sym = gap.clone();
+                sym.addr = s;
+                sym.size = e - s;
+                sym.lma = gap.lma.map(|lma| lma + (s - gap.addr));
+                if let Some(input) = input {
+                    sym.mangled = input.name.clone();
+                    sym.demangled = input.name.clone();
+                    attribute(&mut sym, input);
+                }
+                if sym.lang == SymbolLang::Any {
+                    unattributed.push(sym);
+                } else {
+                    syms.push(sym);
+                }
+            }
+        }
     }
 
     /// Creates a language report which contains the absolute and relative
     /// memory usage of C, Cpp, and Rust for the different memory regions (ROM,
-    /// RAM, both).
+    /// RAM, both). Initialized data whose initial value is copied from ROM at
+    /// startup is counted towards both regions.
     pub fn report_lang(&self) -> Option<LangReport> {
+        self.lang_report(|_| true)
+    }
+
+    /// Same as [`Atlas::report_lang`] but only includes the symbols and the
+    /// unattributed bytes within the named memory region of the linker script
+    /// or region file (see [`Symbol::linker_region`]). Returns `None` if there
+    /// is no memory region with this name.
+    pub fn report_lang_in(&self, region: &str) -> Option<LangReport> {
+        if !self.has_region(region) {
+            return None;
+        }
+        self.lang_report(|s| s.linker_region.as_deref() == Some(region))
+    }
+
+    /// Checks if the linker script or region file contains a memory region
+    /// with this name.
+    fn has_region(&self, name: &str) -> bool {
+        self.regions
+            .as_ref()
+            .map(|regions| regions.iter().any(|r| r.name == name))
+            .unwrap_or(false)
+    }
+
+    /// Creates a language report of the symbols and unattributed bytes
+    /// selected by `filter`. The unattributed bytes are only taken from
+    /// [`Atlas::unattributed`].
+    fn lang_report<F>(&self, filter: F) -> Option<LangReport>
+    where
+        F: Fn(&Symbol) -> bool,
+    {
         let syms = self.syms.as_ref()?;
-        let c = CombinedMem::new(
-            syms.iter()
-                .filter(|s| s.lang == SymbolLang::C)
-                .filter(|s| s.sym_type.mem_region() == MemoryRegion::Rom)
-                .fold(0, |acc, s| acc + s.size as u64),
-            syms.iter()
-                .filter(|s| s.lang == SymbolLang::C)
-                .filter(|s| s.sym_type.mem_region() == MemoryRegion::Ram)
-                .fold(0, |acc, s| acc + s.size as u64),
-        );
+        let unattributed = self.unattributed.as_ref()?;
+        let mem =
+            |lang: SymbolLang| combined_mem(syms.iter().filter(|s| (s.lang == lang) && filter(s)));
+
+        Some(
+            LangReport::new(
+                mem(SymbolLang::C),
+                mem(SymbolLang::Cpp),
+                mem(SymbolLang::Rust),
+                mem(SymbolLang::Asm),
+            )
+            .with_unattributed(combined_mem(unattributed.iter().filter(|s| filter(s)))),
+        )
+    }
 
-        let cpp = CombinedMem::new(
-            syms.iter()
-                .filter(|s| s.lang == SymbolLang::Cpp)
-                .filter(|s| s.sym_type.mem_region() == MemoryRegion::Rom)
-                .fold(0, |acc, s| acc + s.size as u64),
-            syms.iter()
-                .filter(|s| s.lang == SymbolLang::Cpp)
-                .filter(|s| s.sym_type.mem_region() == MemoryRegion::Ram)
-                .fold(0, |acc, s| acc + s.size as u64),
-        );
+    /// Creates a report on the memory usage of every Rust crate (see
+    /// [`Symbol::rust_crate`]). Symbols that don't belong to Rust code (see
+    /// [`Symbol::is_rust`]) aren't included.
+    pub fn report_crates(&self) -> Option<CrateReport> {
+        self.crate_report(Symbol::rust_crate)
+    }
 
-        let rust = CombinedMem::new(
-            syms.iter()
-                .filter(|s| s.lang == SymbolLang::Rust)
-                .filter(|s| s.sym_type.mem_region() == MemoryRegion::Rom)
-                .fold(0, |acc, s| acc + s.size as u64),
-            syms.iter()
-                .filter(|s| s.lang == SymbolLang::Rust)
-                .filter(|s| s.sym_type.mem_region() == MemoryRegion::Ram)
-                .fold(0, |acc, s| acc + s.size as u64),
-        );
-        Some(LangReport::new(c, cpp, rust))
+    /// Same as [`Atlas::report_crates`] but generic code is charged to the
+    /// crate that instantiated it instead of the crate defining it (see
+    /// [`Symbol::instantiating_crate`]). The results are most accurate if the
+    /// DWARF debugging information is used (see [`Atlas::dwarf`]).
+    pub fn report_instantiating_crates(&self) -> Option<CrateReport> {
+        self.crate_report(Symbol::instantiating_crate)
+    }
+
+    /// Groups the Rust symbols (see [`Symbol::is_rust`]) by the crate returned
+    /// by `crate_of`.
+    fn crate_report<F>(&self, crate_of: F) -> Option<CrateReport>
+    where
+        F: Fn(&Symbol) -> Option<String>,
+    {
+        let syms = self.syms.as_ref()?;
+
+        let mut crates: BTreeMap<String, Vec<&Symbol>> = BTreeMap::new();
+        for s in syms.iter().filter(|s| s.is_rust()) {
+            let name = crate_of(s).unwrap_or_else(|| String::from(report::UNKNOWN_CRATE));
+            crates.entry(name).or_default().push(s);
+        }
+
+        Some(CrateReport::new(
+            crates
+                .into_iter()
+                .map(|(name, syms)| (name, combined_mem(syms.into_iter())))
+                .collect(),
+        ))
     }
 
     /// Creates a symbol report starting with the largest symbols for the
@@ -227,13 +611,12 @@ impl Atlas {
         lang: Vec<SymbolLang>,
         mem_region: MemoryRegion,
         max_count: Option<usize>,
-    ) -> Option<SymbolReport<impl Iterator<Item = &Symbol> + Clone>> {
+    ) -> Option<SymbolReport<'_, impl Iterator<Item = &Symbol> + Clone>> {
         let iter = self.syms.as_ref()?.iter().rev();
         let iter =
             iter.filter(move |s| (lang.contains(&SymbolLang::Any)) || (lang.contains(&s.lang)));
-        let iter = iter.filter(move |s| {
-            (mem_region == MemoryRegion::Both) || (s.sym_type.mem_region() == mem_region)
-        });
+        let iter = iter
+            .filter(move |s| (mem_region == MemoryRegion::Both) || (s.mem_region == mem_region));
         let iter = iter.take(if let Some(count) = max_count {
             count
         } else {
@@ -242,4 +625,375 @@ impl Atlas {
 
         Some(SymbolReport::new(iter))
     }
+
+    /// Same as [`Atlas::report_syms`] but only includes the symbols within the
+    /// named memory region of the linker script or region file (see
+    /// [`Symbol::linker_region`]) instead of selecting ROM or RAM. Returns
+    /// `None` if there is no memory region with this name.
+    pub fn report_syms_in(
+        &self,
+        lang: Vec<SymbolLang>,
+        region: &str,
+        max_count: Option<usize>,
+    ) -> Option<SymbolReport<'_, impl Iterator<Item = &Symbol> + Clone>> {
+        if !self.has_region(region) {
+            return None;
+        }
+        let region = String::from(region);
+        let iter = self.syms.as_ref()?.iter().rev();
+        let iter =
+            iter.filter(move |s| (lang.contains(&SymbolLang::Any)) || (lang.contains(&s.lang)));
+        let iter = iter.filter(move |s| s.linker_region.as_ref() == Some(&region));
+        let iter = iter.take(max_count.unwrap_or(usize::MAX));
+
+        Some(SymbolReport::new(iter))
+    }
+
+    /// Creates a tree report of the namespaces and modules of the symbols of
+    /// the selected languages and memory region (see [`TreeReport`]). The
+    /// arguments work like the ones of [`Atlas::report_syms`].
+    pub fn report_tree(
+        &self,
+        lang: Vec<SymbolLang>,
+        mem_region: MemoryRegion,
+    ) -> Option<TreeReport> {
+        Some(TreeReport::new(
+            self.report_syms(lang, mem_region, None)?.into_iter(),
+        ))
+    }
+
+    /// Creates a report on the memory usage per language of every source file
+    /// and directory (see [`FileReport`]). The source files are only known if
+    /// the DWARF debugging information is used (see [`Atlas::dwarf`]).
+    /// Otherwise, all symbols are reported as [`report::UNKNOWN_FILE`].
+    pub fn report_files(&self) -> Option<FileReport> {
+        let syms = self.syms.as_ref()?;
+
+        let mut files: BTreeMap<Option<&str>, Vec<&Symbol>> = BTreeMap::new();
+        for s in syms.iter() {
+            files.entry(s.file.as_deref()).or_default().push(s);
+        }
+
+        Some(FileReport::new(
+            files
+                .into_iter()
+                .map(|(path, syms)| {
+                    let mem = |lang: SymbolLang| {
+                        combined_mem(syms.iter().copied().filter(|s| s.lang == lang))
+                    };
+                    let langs = LangReport::new(
+                        mem(SymbolLang::C),
+                        mem(SymbolLang::Cpp),
+                        mem(SymbolLang::Rust),
+                        mem(SymbolLang::Asm),
+                    );
+                    (path.map(String::from), langs, syms.len())
+                })
+                .collect(),
+        ))
+    }
+
+    /// Creates a report on the generic functions of the selected languages and
+    /// memory region with the number and total size of their instantiations
+    /// (see [`GenericReport`]). The arguments work like the ones of
+    /// [`Atlas::report_syms`].
+    pub fn report_generics(
+        &self,
+        lang: Vec<SymbolLang>,
+        mem_region: MemoryRegion,
+    ) -> Option<GenericReport> {
+        Some(GenericReport::new(
+            self.report_syms(lang, mem_region, None)?.into_iter(),
+        ))
+    }
+
+    /// Creates a report on the Rust crates that have been linked in multiple
+    /// versions with the number of bytes wasted by the duplicates (see
+    /// [`DuplicateReport`]). The versions can only be told apart for v0
+    /// mangled symbols or if the DWARF debugging information is used (see
+    /// [`Atlas::dwarf`]).
+    pub fn report_duplicates(&self, mem_region: MemoryRegion) -> Option<DuplicateReport> {
+        Some(DuplicateReport::new(
+            self.report_syms(vec![SymbolLang::Any], mem_region, None)?
+                .into_iter(),
+        ))
+    }
+
+    /// Creates a report on the bytes lost to alignment between adjacent
+    /// symbols of the same section in the given memory region (see
+    /// [`PaddingReport`]). The padding is attributed to the language of the
+    /// symbol following the gap. If a linker map file has been set, the fill
+    /// bytes recorded in it (see [`map::FILL`]) are reported as padding.
+    /// Otherwise, the padding is derived from the addresses of the symbols.
+    pub fn report_padding(&self, mem_region: MemoryRegion) -> Option<PaddingReport> {
+        let syms = self
+            .report_syms(vec![SymbolLang::Any], mem_region, None)?
+            .into_iter();
+        if self.map.is_none() {
+            return Some(PaddingReport::new(syms));
+        }
+
+        let fill = self.unattributed.as_ref()?.iter().filter(move |s| {
+            (s.mangled == map::FILL)
+                && ((mem_region == MemoryRegion::Both) || (s.mem_region == mem_region))
+        });
+        Some(PaddingReport::with_fill(syms, fill))
+    }
+
+    /// Creates a report on the memory usage of every added library and the
+    /// number of its symbols that have been linked (see [`LibReport`]). The
+    /// symbols are only counted if the libraries have been parsed (i.e. no
+    /// linker map file has been set).
+    pub fn report_libs(&self) -> Option<LibReport> {
+        let syms = self.syms.as_ref()?;
+        let linked = self.linked.as_deref().unwrap_or_default();
+
+        Some(LibReport::new(
+            self.libs
+                .iter()
+                .map(|lib| {
+                    let mem =
+                        combined_mem(syms.iter().filter(|s| s.lib.as_deref() == Some(lib.path())));
+                    let counts = linked.iter().find(|l| l.path == lib.path());
+                    LibUsage {
+                        path: lib.path().to_path_buf(),
+                        lang: lib.lang(),
+                        mem,
+                        linked: counts.map(|l| l.linked),
+                        dropped: counts.map(|l| l.dropped()),
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// Creates a report on the memory usage of every object file (see
+    /// [`ObjectReport`]). The object files are taken from the linker map file
+    /// or from the archive members of the parsed libraries. Symbols whose
+    /// object file is unknown aren't included. The unattributed bytes of the
+    /// input sections of the map file (e.g. string literals) are counted
+    /// towards their object file as well.
+    pub fn report_objects(&self) -> Option<ObjectReport> {
+        let syms = self.syms.as_ref()?;
+        let unattributed = self.unattributed.as_ref()?;
+
+        let mut objects: BTreeMap<(Option<&Path>, &str), Vec<&Symbol>> = BTreeMap::new();
+        for s in syms.iter().chain(unattributed.iter()) {
+            if let Some(object) = &s.object {
+                let archive = s.archive.as_deref().or(s.lib.as_deref());
+                objects.entry((archive, object)).or_default().push(s);
+            }
+        }
+
+        Some(ObjectReport::new(
+            objects
+                .into_iter()
+                .map(|((archive, object), syms)| ObjectSize {
+                    archive: archive.map(Path::to_path_buf),
+                    object: String::from(object),
+                    mem: combined_mem(syms.into_iter()),
+                })
+                .collect(),
+        ))
+    }
+
+    /// Creates a report on the utilization of the memory regions declared in
+    /// the region file and the linker script (see [`RegionReport`]). A symbol
+    /// is counted towards the region containing its address. The initial
+    /// value of initialized data is additionally counted towards the region
+    /// containing its load address. The bytes of the sections that aren't
+    /// covered by any symbol are counted separately as unattributed bytes
+    /// (see [`Atlas::unattributed`]). The report is empty if neither a region
+    /// file nor a linker script has been set (see [`Atlas::set_region_file`]
+    /// and [`Atlas::set_script`]).
+    pub fn report_regions(&self) -> Option<RegionReport> {
+        let syms = self.syms.as_ref()?;
+        let unattributed = self.unattributed.as_ref()?;
+        let regions = self.regions.as_ref()?;
+
+        Some(RegionReport::new(
+            regions
+                .iter()
+                .map(|region| {
+                    let size = |syms: &[Symbol]| {
+                        syms.iter()
+                            .map(|s| {
+                                let vma = region.contains(s.addr) as u64;
+                                let lma =
+                                    s.lma.filter(|lma| region.contains(*lma)).is_some() as u64;
+                                (vma + lma) * s.size
+                            })
+                            .sum()
+                    };
+                    RegionUsage {
+                        region: region.clone(),
+                        used: size(syms),
+                        unattributed: size(unattributed),
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// Creates a report on the size of every allocated section of the ELF
+    /// file (see [`SectionReport`]). The bytes of a section attributed to a
+    /// language are the sizes of the symbols of the language defined in the
+    /// section. The remaining bytes are unattributed (see
+    /// [`Atlas::unattributed`]). The region of a section is the one of the
+    /// region file or the linker script containing its start address. A region
+    /// declaring its memory overrides the memory derived from the section
+    /// flags.
+    pub fn report_sections(&self) -> Option<SectionReport> {
+        let syms = self.syms.as_ref()?;
+        let unattributed = self.unattributed.as_ref()?;
+        let regions = self.regions.as_ref()?;
+
+        Some(SectionReport::new(
+            self.sections
+                .as_ref()?
+                .iter()
+                .map(|section| {
+                    let region = regions.iter().find(|r| r.contains(section.addr));
+                    let size = |lang: SymbolLang| {
+                        syms.iter()
+                            .filter(|s| {
+                                (s.lang == lang) && (s.section.as_ref() == Some(&section.name))
+                            })
+                            .map(|s| s.size)
+                            .sum()
+                    };
+                    SectionSize {
+                        name: section.name.clone(),
+                        addr: section.addr,
+                        size: section.size,
+                        mem_region: region
+                            .and_then(|r| r.memory)
+                            .unwrap_or_else(|| section.mem_region()),
+                        linker_region: region.map(|r| r.name.clone()),
+                        c: size(SymbolLang::C),
+                        cpp: size(SymbolLang::Cpp),
+                        rust: size(SymbolLang::Rust),
+                        asm: size(SymbolLang::Asm),
+                        unattributed: unattributed
+                            .iter()
+                            .filter(|s| s.section.as_ref() == Some(&section.name))
+                            .map(|s| s.size)
+                            .sum(),
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// Creates a report on the symbols (mostly of the Rust runtime) that are
+    /// contained in more than one of the Rust libraries (see
+    /// [`RuntimeReport`]). The libraries are only parsed if no linker map file
+    /// has been set. Otherwise, the report is empty.
+    pub fn report_runtime(&self) -> Option<RuntimeReport<'_>> {
+        Some(RuntimeReport::new(self.runtime.as_ref()?))
+    }
+
+    /// Creates a report on the differences between this (old) build and the
+    /// given new build of the application. Both instances have to be analyzed
+    /// first, otherwise `None` is returned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut old = Atlas::new("old/app.elf")?;
+    /// let mut new = Atlas::new("new/app.elf")?;
+    /// old.analyze()?;
+    /// new.analyze()?;
+    /// let diff = old.report_diff(&new).unwrap();
+    /// let growth = diff.size_delta(SymbolLang::Rust, MemoryRegion::Rom);
+    /// ```
+    pub fn report_diff(&self, new: &Atlas) -> Option<DiffReport> {
+        Some(DiffReport::new(
+            self.report_lang()?,
+            new.report_lang()?,
+            self.syms.as_ref()?,
+            new.syms.as_ref()?,
+        ))
+    }
+
+    /// Checks the memory usage of the analyzed ELF file against the given
+    /// budget. Returns `None` if the ELF file hasn't been analyzed yet.
+    pub fn check(&self, budget: &Budget) -> Option<CheckReport> {
+        Some(budget.check(&self.report_lang()?, self.syms.as_ref()?))
+    }
+}
+
+/// Creates a symbol for every address range of the allocated sections that
+/// isn't covered by any of the given symbols. Overlapping symbols (e.g.
+/// aliases) are only counted once.
+fn unattributed_syms(elf: &ElfFile, syms: &[Symbol]) -> Vec<Symbol> {
+    let mut ranges = syms
+        .iter()
+        .filter(|s| s.size > 0)
+        .map(|s| (s.addr, s.addr + s.size))
+        .collect::<Vec<_>>();
+    ranges.sort_unstable();
+
+    // Disjoint address ranges covered by symbols sorted by their start address.
+    let mut covered: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (s, e) in ranges {
+        match covered.last_mut() {
+            Some(last) if s <= last.1 => last.1 = std::cmp::max(last.1, e),
+            _ => covered.push((s, e)),
+        }
+    }
+
+    let mut unattributed = Vec::new();
+    for section in elf.sections() {
+        if (section.mem_region() == MemoryRegion::Unknown) || (section.size == 0) {
+            continue;
+        }
+
+        let start = section.addr;
+        let end = section.addr + section.size;
+        let mut gaps = Vec::new();
+        let mut next = start;
+        let first = covered.partition_point(|(_, e)| *e <= start);
+        for (s, e) in covered[first..].iter().take_while(|(s, _)| *s < end) {
+            if *s > next {
+                gaps.push((next, *s));
+            }
+            next = std::cmp::max(next, *e);
+        }
+        if next < end {
+            gaps.push((next, end));
+        }
+
+        for (s, e) in gaps {
+            let mut sym = Symbol::new(
+                s,
+                e - s,
+                section.sym_type(),
+                section.name.clone(),
+                section.name.clone(),
+                SymbolLang::Any,
+            )
+            .with_section(Some(section));
+            sym.lma = elf.load_addr(sym.addr);
+            unattributed.push(sym);
+        }
+    }
+
+    unattributed
+}
+
+/// Sums up the ROM and RAM usage of the symbols. Initialized data that is
+/// copied from ROM at startup is counted towards both regions.
+fn combined_mem<'a>(syms: impl Iterator<Item = &'a Symbol> + Clone) -> CombinedMem {
+    let copied = syms
+        .clone()
+        .filter(|s| s.copied_from_rom())
+        .fold(0, |acc, s| acc + s.size);
+    CombinedMem::with_copied(
+        syms.clone()
+            .filter(|s| s.mem_region == MemoryRegion::Rom)
+            .fold(copied, |acc, s| acc + s.size),
+        syms.filter(|s| s.mem_region == MemoryRegion::Ram)
+            .fold(0, |acc, s| acc + s.size),
+        copied,
+    )
 }
@@ -37,50 +37,50 @@ mod tests {
 
     #[test]
     fn new_str() {
-        let at = Atlas::new(&*NM_PATH, file!());
+        let at = Atlas::new(file!());
         assert!(at.is_ok());
     }
 
     #[test]
     fn new_string() {
-        let at = Atlas::new(&*NM_PATH, String::from(file!()));
+        let at = Atlas::new(String::from(file!()));
         assert!(at.is_ok());
     }
 
     #[test]
     fn new_pathbuf() {
-        let at = Atlas::new(&*NM_PATH, PathBuf::from(file!()));
+        let at = Atlas::new(PathBuf::from(file!()));
         assert!(at.is_ok());
     }
 
     #[test]
     fn new_path() {
-        let at = Atlas::new(&*NM_PATH, Path::new(file!()));
+        let at = Atlas::new(Path::new(file!()));
         assert!(at.is_ok());
     }
 
     #[test]
     fn new_elf_not_found() {
-        let err = Atlas::new(&*NM_PATH, "kljsdflkjsdf").unwrap_err();
+        let err = Atlas::new("kljsdflkjsdf").unwrap_err();
         assert_eq!(err.kind(), ErrorKind::Io);
     }
 
     #[test]
     fn new_permission_denied() {
-        let err = Atlas::new(&*NM_PATH, "/etc/shadow").unwrap_err();
+        let err = Atlas::new("/etc/shadow").unwrap_err();
         assert_eq!(err.kind(), ErrorKind::Io);
     }
 
     #[test]
     fn add_lib_canonicalize() {
-        let mut at = Atlas::new(&*NM_PATH, file!()).unwrap();
+        let mut at = Atlas::new(file!()).unwrap();
         at.add_lib(SymbolLang::Rust, "./test_data/../src/../Cargo.toml")
             .unwrap();
     }
 
     #[test]
     fn add_lib_not_found() {
-        let mut at = Atlas::new(&*NM_PATH, file!()).unwrap();
+        let mut at = Atlas::new(file!()).unwrap();
         let err = at
             .add_lib(SymbolLang::Rust, "lksjdflkjsdflkjsdf")
             .unwrap_err();
@@ -89,23 +89,82 @@ mod tests {
 
     #[test]
     fn permission_denied() {
-        let mut at = Atlas::new(&*NM_PATH, file!()).unwrap();
+        let mut at = Atlas::new(file!()).unwrap();
         let err = at.add_lib(SymbolLang::Rust, "/etc/shadow").unwrap_err();
         assert_eq!(err.kind(), ErrorKind::Io);
     }
 
     #[test]
     fn nm_wrong_file_type() {
-        let mut at = Atlas::new(&*NM_PATH, "readme.md").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "readme.md").unwrap();
         at.add_lib(SymbolLang::Rust, "test_data/c_app_rust_lib/libs/liblib.a")
             .unwrap();
         let err = at.analyze().unwrap_err();
         assert_eq!(err.kind(), ErrorKind::Nm);
     }
 
+    #[test]
+    fn set_nm_not_found() {
+        let mut at = Atlas::new(file!()).unwrap();
+        let err = at.set_nm("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert!(at.nm.is_none());
+    }
+
+    #[test]
+    fn elf_wrong_file_type() {
+        let mut at = Atlas::new("readme.md").unwrap();
+        let err = at.analyze().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidElf);
+    }
+
+    #[test]
+    fn libs_without_nm() {
+        let mut at = Atlas::new("test_data/c_app_c_lib_rust_lib/app").unwrap();
+        // The language of the library is deliberately set to Cpp to tell it
+        // apart from the default language.
+        at.add_lib(
+            SymbolLang::Cpp,
+            "test_data/c_app_c_lib_rust_lib/libs/libc_lib.a",
+        )
+        .unwrap();
+        at.analyze().unwrap();
+
+        let syms = at.syms.as_ref().unwrap();
+        let c_add = syms.iter().find(|s| s.mangled == "c_add").unwrap();
+        assert_eq!(c_add.lang, SymbolLang::Cpp);
+        assert_eq!(c_add.object.as_deref(), Some("c_lib.o"));
+        let main = syms.iter().find(|s| s.mangled == "main").unwrap();
+        assert_eq!(main.lang, SymbolLang::C);
+
+        let lib_rep = at.report_libs().unwrap();
+        assert_eq!(lib_rep.libs()[0].linked, Some(4));
+        assert_eq!(lib_rep.libs()[0].dropped, Some(0));
+    }
+
+    #[test]
+    fn libs_rlib_metadata() {
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
+        at.add_lib(SymbolLang::Rust, "test_data/ar/libringbuf.rlib")
+            .unwrap();
+        at.analyze().unwrap();
+
+        let lib_rep = at.report_libs().unwrap();
+        assert_eq!(lib_rep.libs()[0].linked, Some(0));
+        assert_eq!(lib_rep.libs()[0].dropped, Some(2));
+    }
+
+    #[test]
+    fn libs_not_archive() {
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
+        at.add_lib(SymbolLang::C, "readme.md").unwrap();
+        let err = at.analyze().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidArchive);
+    }
+
     #[test]
     fn report_without_analyze() {
-        let at = Atlas::new(&*NM_PATH, file!()).unwrap();
+        let at = Atlas::new(file!()).unwrap();
         assert!(at.report_lang().is_none());
         assert!(at
             .report_syms(vec![SymbolLang::Rust], MemoryRegion::Rom, None)
@@ -114,7 +173,7 @@ mod tests {
 
     #[test]
     fn analyze_c_no_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app/app").unwrap();
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
         at.analyze().unwrap();
         assert_eq!(at.fails.as_ref().unwrap().len(), 0);
         let syms = at.syms.as_ref().unwrap();
@@ -134,9 +193,804 @@ mod tests {
         assert_eq!(syms[syms.len() - 1].lang, SymbolLang::C);
     }
 
+    #[test]
+    fn analyze_c_app_x86_64() {
+        let mut at = Atlas::new("test_data/c_app_x86_64/app").unwrap();
+        at.analyze().unwrap();
+        assert_eq!(at.fails.as_ref().unwrap().len(), 0);
+        let syms = at.syms.as_ref().unwrap();
+        assert_eq!(syms.len(), 10);
+
+        assert_eq!(syms[syms.len() - 2].addr, 0x0000000000401134);
+        assert_eq!(syms[syms.len() - 2].size, 0x0000000000000075);
+        assert_eq!(syms[syms.len() - 2].sym_type, SymbolType::TextSection);
+        assert_eq!(syms[syms.len() - 2].mangled, "main");
+        assert_eq!(syms[syms.len() - 2].lang, SymbolLang::C);
+
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Rom).as_u64(),
+            0x01 + 0x04 + 0x14 + 0x1a + 0x20 + 0x22 + 0x75
+        );
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Ram).as_u64(),
+            0x01 + 0x4e + 0x80
+        );
+    }
+
+    #[test]
+    fn analyze_sections() {
+        let mut at = Atlas::new("test_data/c_app_c_lib_rust_lib/app").unwrap();
+        at.analyze().unwrap();
+        let syms = at.syms.as_ref().unwrap();
+
+        // Weak symbols are classified according to their section.
+        let memcpy = syms.iter().find(|s| s.mangled == "memcpy").unwrap();
+        assert_eq!(memcpy.sym_type, SymbolType::Weak);
+        assert_eq!(memcpy.section.as_deref(), Some(".text"));
+        assert_eq!(memcpy.mem_region, MemoryRegion::Rom);
+
+        let arr = syms
+            .iter()
+            .find(|s| s.demangled == "rust_lib::RUST_LIB_STATIC_MUT_ARR")
+            .unwrap();
+        assert_eq!(arr.section.as_deref(), Some(".data"));
+        assert_eq!(arr.mem_region, MemoryRegion::Ram);
+
+        assert!(syms.iter().all(|s| s.mem_region != MemoryRegion::Unknown));
+    }
+
+    #[test]
+    fn analyze_copied_from_rom() {
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        at.analyze().unwrap();
+        let syms = at.syms.as_ref().unwrap();
+        assert_eq!(syms.len(), 5);
+
+        let data = syms.iter().find(|s| s.mangled == "c_app_data_arr").unwrap();
+        assert_eq!(data.mem_region, MemoryRegion::Ram);
+        assert_eq!(data.lma, Some(0x000000000800010e));
+        assert!(data.copied_from_rom());
+
+        let bss = syms.iter().find(|s| s.mangled == "c_app_bss_arr").unwrap();
+        assert_eq!(bss.lma, None);
+        assert!(!bss.copied_from_rom());
+
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Rom).as_u64(),
+            0x14 + 0x9d + 0x4e + 0x3d
+        );
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Ram).as_u64(),
+            0x3d + 0x40
+        );
+        assert_eq!(lang_rep.size_copied(SymbolLang::C).as_u64(), 0x3d);
+        assert_eq!(lang_rep.size_copied(SymbolLang::Rust).as_u64(), 0);
+    }
+
+    #[test]
+    fn analyze_dwarf() {
+        let lang = |at: &Atlas, name: &str| {
+            let syms = at.syms.as_ref().unwrap();
+            syms.iter().find(|s| s.mangled == name).unwrap().lang
+        };
+
+        let mut at = Atlas::new("test_data/c_app_dwarf/app").unwrap();
+        at.analyze().unwrap();
+        assert_eq!(lang(&at, "cpp_add"), SymbolLang::C);
+        assert_eq!(lang(&at, "asm_double"), SymbolLang::C);
+
+        let mut at = Atlas::new("test_data/c_app_dwarf/app").unwrap();
+        at.dwarf = true;
+        at.analyze().unwrap();
+        assert_eq!(lang(&at, "main"), SymbolLang::C);
+        assert_eq!(lang(&at, "cpp_add"), SymbolLang::Cpp);
+        assert_eq!(lang(&at, "asm_double"), SymbolLang::Asm);
+        assert_eq!(lang(&at, "rust_mult"), SymbolLang::Rust);
+        assert_eq!(lang(&at, "RUST_LIB_RESULTS"), SymbolLang::Rust);
+        // Symbols without debugging information fall back to the default.
+        assert_eq!(lang(&at, "_start"), SymbolLang::C);
+
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size(SymbolLang::Asm, MemoryRegion::Rom).as_u64(),
+            4
+        );
+    }
+
+    #[test]
+    fn report_diff() {
+        let mut old = Atlas::new("test_data/c_app_diff/app_old").unwrap();
+        let mut new = Atlas::new("test_data/c_app_diff/app_new").unwrap();
+        assert!(old.report_diff(&new).is_none());
+        old.analyze().unwrap();
+        assert!(old.report_diff(&new).is_none());
+        new.analyze().unwrap();
+
+        let diff = old.report_diff(&new).unwrap();
+        let syms = diff
+            .syms()
+            .iter()
+            .map(|s| (s.name.as_str(), s.change(), s.delta()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            syms,
+            vec![
+                ("c_app_bss_arr", SymbolChange::Resized, 192),
+                ("c_app_rodata_arr", SymbolChange::Resized, -31),
+                ("added_mult", SymbolChange::Added, 19),
+                ("removed_sub", SymbolChange::Removed, -18),
+            ]
+        );
+
+        assert_eq!(diff.size_delta(SymbolLang::C, MemoryRegion::Rom), -30);
+        assert_eq!(diff.size_delta(SymbolLang::C, MemoryRegion::Ram), 192);
+        assert_eq!(diff.size_delta(SymbolLang::Any, MemoryRegion::Both), 162);
+        assert_eq!(diff.size_delta(SymbolLang::Rust, MemoryRegion::Both), 0);
+    }
+
+    #[test]
+    fn check() {
+        let budget = Budget::open("test_data/budget/c_app_lma.toml").unwrap();
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        assert!(at.check(&budget).is_none());
+        at.analyze().unwrap();
+
+        let report = at.check(&budget).unwrap();
+        let violations = report
+            .violations()
+            .iter()
+            .map(|v| (v.limit.to_string(), v.symbol.as_deref(), v.over()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            vec![
+                (String::from("Total Rom"), None, 16),
+                (String::from("C Ram"), None, 25),
+                (
+                    String::from("Symbol /^c_app_.*_arr$/"),
+                    Some("c_app_rodata_arr"),
+                    14
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_crates() {
+        let mut at = Atlas::new("test_data/c_app_dwarf/app").unwrap();
+        assert!(at.report_crates().is_none());
+        at.dwarf = true;
+        at.analyze().unwrap();
+
+        let crate_rep = at.report_crates().unwrap();
+        let crates = crate_rep
+            .iter_region(MemoryRegion::Both)
+            .map(|(name, size, _)| (name, size.as_u64()))
+            .collect::<Vec<_>>();
+        // `rust_mult` and `RUST_LIB_RESULTS` are `#[no_mangle]` and thus
+        // don't contain the crate in their name.
+        assert_eq!(crates, vec![("[Unknown]", 0x17 + 0x20), ("rust_lib", 6)]);
+    }
+
+    #[test]
+    fn report_tree() {
+        let mut at = Atlas::new("test_data/c_app_dwarf/app").unwrap();
+        assert!(at
+            .report_tree(vec![SymbolLang::Any], MemoryRegion::Rom)
+            .is_none());
+        at.dwarf = true;
+        at.analyze().unwrap();
+
+        let tree_rep = at
+            .report_tree(vec![SymbolLang::Cpp], MemoryRegion::Rom)
+            .unwrap();
+        let accumulator = tree_rep.node(&["cpp_lib", "Accumulator"]).unwrap();
+        assert_eq!(accumulator.count, 4);
+        assert_eq!(accumulator.size, 0x1f + 0x17 + 0x17 + 0x10);
+        assert!(tree_rep.node(&["main"]).is_none());
+    }
+
+    #[test]
+    fn report_regions() {
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        assert!(at.report_regions().is_none());
+        at.analyze().unwrap();
+        // Without a linker script no regions are known.
+        assert!(at.report_regions().unwrap().regions().is_empty());
+
+        at.set_script("test_data/c_app_lma/link.ld").unwrap();
+        at.analyze().unwrap();
+        let syms = at.syms.as_ref().unwrap();
+        let region = |name: &str| {
+            let s = syms.iter().find(|s| s.mangled == name).unwrap();
+            s.linker_region.as_deref()
+        };
+        assert_eq!(region("_start"), Some("FLASH"));
+        assert_eq!(region("c_app_data_arr"), Some("RAM"));
+        assert_eq!(region("c_app_bss_arr"), Some("RAM"));
+
+        let region_rep = at.report_regions().unwrap();
+        let flash = region_rep.region("FLASH").unwrap();
+        // The initial values of `c_app_data_arr` (61 bytes) are stored in
+        // FLASH as well.
+        assert_eq!(flash.used, 157 + 78 + 20 + 61);
+        assert_eq!(flash.unattributed, 0);
+        assert_eq!(flash.free(), 256 * 1024 - flash.used);
+        let ram = region_rep.region("RAM").unwrap();
+        assert_eq!(ram.used, 64 + 61);
+    }
+
+    #[test]
+    fn set_script_not_found() {
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        let err = at.set_script("test_data/c_app_lma/missing.ld").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert!(at.script.is_none());
+    }
+
+    #[test]
+    fn report_sections() {
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        assert!(at.report_sections().is_none());
+        at.analyze().unwrap();
+        let section_rep = at.report_sections().unwrap();
+        let names = section_rep
+            .sections()
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![".text", ".rodata", ".data", ".bss"]);
+        let text = section_rep.section(".text").unwrap();
+        assert_eq!(text.addr, 0x08000000);
+        assert_eq!(text.size, 0x14 + 0x9d);
+        assert_eq!(text.mem_region, MemoryRegion::Rom);
+        assert_eq!(text.linker_region, None);
+        assert_eq!(text.lang_size(SymbolLang::C), 0x14 + 0x9d);
+        assert_eq!(text.lang_size(SymbolLang::Rust), 0);
+        let data = section_rep.section(".data").unwrap();
+        assert_eq!(data.mem_region, MemoryRegion::Ram);
+        assert_eq!(data.lang_size(SymbolLang::Any), 61);
+
+        // The memory of a region in the region file overrides the one of the
+        // section.
+        at.set_region_file("test_data/regions/c_app_lma.toml")
+            .unwrap();
+        at.set_script("test_data/c_app_lma/link.ld").unwrap();
+        at.analyze().unwrap();
+        let section_rep = at.report_sections().unwrap();
+        let text = section_rep.section(".text").unwrap();
+        assert_eq!(text.linker_region.as_deref(), Some("ITCM"));
+        assert_eq!(text.mem_region, MemoryRegion::Ram);
+        let rodata = section_rep.section(".rodata").unwrap();
+        assert_eq!(rodata.linker_region.as_deref(), Some("FLASH"));
+        assert_eq!(rodata.mem_region, MemoryRegion::Rom);
+    }
+
+    #[test]
+    fn analyze_unattributed() {
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
+        at.analyze().unwrap();
+        let unattributed = at.unattributed.as_ref().unwrap();
+        let init = unattributed.iter().find(|s| s.mangled == ".init").unwrap();
+        assert_eq!(init.addr, 0x8000);
+        assert_eq!(init.size, 12);
+        assert_eq!(init.lang, SymbolLang::Any);
+        assert_eq!(init.mem_region, MemoryRegion::Rom);
+        assert_eq!(unattributed.iter().map(|s| s.size).sum::<u64>(), 258 + 48);
+
+        // The symbols and the unattributed bytes add up to the size of every
+        // section.
+        let section_rep = at.report_sections().unwrap();
+        for section in section_rep.sections() {
+            assert_eq!(
+                section.lang_size(SymbolLang::Any) + section.unattributed,
+                section.size,
+                "{}",
+                section.name
+            );
+        }
+        assert_eq!(section_rep.section(".text").unwrap().unattributed, 198);
+        assert_eq!(section_rep.section(".bss").unwrap().unattributed, 30);
+    }
+
+    #[test]
+    fn report_padding() {
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
+        assert!(at.report_padding(MemoryRegion::Both).is_none());
+        at.analyze().unwrap();
+        let padding_rep = at.report_padding(MemoryRegion::Rom).unwrap();
+        // The Thumb functions of the retargetable locking are 2 bytes long
+        // and aligned to 4 bytes.
+        assert_eq!(padding_rep.gaps().len(), 8);
+        let gap = &padding_rep.gaps()[0];
+        assert_eq!(gap.addr, 0x8362);
+        assert_eq!(gap.size, 2);
+        assert_eq!(gap.addr_align, 4);
+        assert_eq!(gap.section, ".text");
+        assert_eq!(gap.lang, SymbolLang::C);
+        assert_eq!(gap.before, "__retarget_lock_init");
+        assert_eq!(gap.after, "__retarget_lock_init_recursive");
+        assert_eq!(padding_rep.size(SymbolLang::C), 16);
+
+        let padding_rep = at.report_padding(MemoryRegion::Ram).unwrap();
+        // Every 1 byte variable in `.bss` is followed by 3 bytes of padding.
+        let gap = &padding_rep.gaps()[0];
+        assert_eq!(gap.addr, 0x188dd);
+        assert_eq!(gap.size, 3);
+        assert_eq!(gap.addr_align, 32);
+        assert_eq!(gap.before, "completed.8911");
+        assert_eq!(gap.after, "object.8916");
+        let section_size = |name: &str| {
+            padding_rep
+                .section(name)
+                .unwrap()
+                .lang_size(SymbolLang::Any)
+        };
+        assert_eq!(section_size(".bss"), 9 * 3);
+        assert_eq!(section_size(".data"), 2);
+        assert!(padding_rep.section(".text").is_none());
+
+        let padding_rep = at.report_padding(MemoryRegion::Both).unwrap();
+        assert_eq!(padding_rep.gaps().len(), 8 + 9 + 1);
+        assert_eq!(padding_rep.size(SymbolLang::Any), 16 + 27 + 2);
+    }
+
+    #[test]
+    fn report_region_file() {
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        at.analyze().unwrap();
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Rom).as_u64(),
+            316
+        );
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Ram).as_u64(),
+            125
+        );
+        assert!(at.report_lang_in("ITCM").is_none());
+
+        at.set_region_file("test_data/regions/c_app_lma.toml")
+            .unwrap();
+        at.set_script("test_data/c_app_lma/link.ld").unwrap();
+        at.analyze().unwrap();
+        let syms = at.syms.as_ref().unwrap();
+        let sym = |name: &str| syms.iter().find(|s| s.mangled == name).unwrap();
+        // The regions of the region file take precedence over the ones of the
+        // linker script.
+        assert_eq!(sym("add").linker_region.as_deref(), Some("ITCM"));
+        assert_eq!(sym("add").mem_region, MemoryRegion::Ram);
+        assert_eq!(sym("_start").linker_region.as_deref(), Some("FLASH"));
+        assert_eq!(sym("_start").mem_region, MemoryRegion::Rom);
+        assert_eq!(
+            sym("c_app_bss_arr").linker_region.as_deref(),
+            Some("RETAINED")
+        );
+        assert_eq!(sym("c_app_data_arr").linker_region.as_deref(), Some("RAM"));
+
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Rom).as_u64(),
+            316 - 20
+        );
+        assert_eq!(
+            lang_rep.size(SymbolLang::C, MemoryRegion::Ram).as_u64(),
+            125 + 20
+        );
+
+        let itcm_rep = at.report_lang_in("ITCM").unwrap();
+        assert_eq!(itcm_rep.size(SymbolLang::C, MemoryRegion::Ram).as_u64(), 20);
+        assert_eq!(
+            itcm_rep.size(SymbolLang::Any, MemoryRegion::Rom).as_u64(),
+            0
+        );
+        let retained_rep = at.report_lang_in("RETAINED").unwrap();
+        assert_eq!(
+            retained_rep
+                .size(SymbolLang::Any, MemoryRegion::Both)
+                .as_u64(),
+            64
+        );
+
+        let syms_rep = at
+            .report_syms_in(vec![SymbolLang::Any], "ITCM", None)
+            .unwrap();
+        let names = syms_rep
+            .into_iter()
+            .map(|s| s.mangled.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["add"]);
+        assert_eq!(
+            at.report_syms_in(vec![SymbolLang::Rust], "ITCM", None)
+                .unwrap()
+                .into_iter()
+                .count(),
+            0
+        );
+        assert!(at.report_lang_in("NOPE").is_none());
+        assert!(at
+            .report_syms_in(vec![SymbolLang::Any], "NOPE", None)
+            .is_none());
+
+        let region_rep = at.report_regions().unwrap();
+        assert_eq!(region_rep.region("ITCM").unwrap().used, 20);
+        assert_eq!(region_rep.region("RETAINED").unwrap().used, 64);
+    }
+
+    #[test]
+    fn set_region_file_not_found() {
+        let mut at = Atlas::new("test_data/c_app_lma/app").unwrap();
+        let err = at
+            .set_region_file("test_data/regions/missing.toml")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert!(at.region_file.is_none());
+    }
+
+    #[test]
+    fn report_files() {
+        let mut at = Atlas::new("test_data/c_app_dwarf/app").unwrap();
+        assert!(at.report_files().is_none());
+        at.analyze().unwrap();
+        // Without the debugging information no source file is known.
+        let file_rep = at.report_files().unwrap();
+        assert_eq!(file_rep.root().children.len(), 1);
+        assert!(file_rep.node(report::UNKNOWN_FILE).is_some());
+
+        at.dwarf = true;
+        at.analyze().unwrap();
+        let file_rep = at.report_files().unwrap();
+        assert!(file_rep.prefix().ends_with("test_data/c_app_dwarf"));
+        let cpp = file_rep.node("cpp_lib.cpp").unwrap();
+        assert_eq!(
+            cpp.langs.size(SymbolLang::Cpp, MemoryRegion::Both).as_u64(),
+            155
+        );
+        assert_eq!(
+            cpp.langs.size(SymbolLang::C, MemoryRegion::Both).as_u64(),
+            0
+        );
+        let asm = file_rep.node("asm_lib.S").unwrap();
+        assert_eq!(
+            asm.langs.size(SymbolLang::Asm, MemoryRegion::Rom).as_u64(),
+            4
+        );
+        assert!(file_rep.node("rust_lib.rs").is_some());
+        // _start etc. aren't covered by the debugging information.
+        assert!(file_rep.node(report::UNKNOWN_FILE).is_some());
+        assert_eq!(file_rep.root().count, at.syms.as_ref().unwrap().len());
+    }
+
+    #[test]
+    fn report_generics() {
+        let mut at = Atlas::new("test_data/c_app_generics/app").unwrap();
+        assert!(at
+            .report_generics(vec![SymbolLang::Any], MemoryRegion::Rom)
+            .is_none());
+        at.dwarf = true;
+        at.analyze().unwrap();
+
+        let generic_rep = at
+            .report_generics(vec![SymbolLang::Any], MemoryRegion::Rom)
+            .unwrap();
+        let generics = generic_rep.generics();
+        assert_eq!(generics.len(), 5);
+        assert_eq!(generics[0].name, "cpp_lib::Buffer::sum");
+        assert_eq!(generics[0].instances, 3);
+        assert_eq!(generics[0].size, 0x2b + 0x2d + 0x18);
+
+        let largest = generics
+            .iter()
+            .find(|g| g.name == "rust_lib::largest")
+            .unwrap();
+        assert_eq!(largest.lang, SymbolLang::Rust);
+        assert_eq!(largest.instances, 3);
+        assert_eq!(largest.size, 0x13 + 0x1a + 0x16);
+
+        // The complete and base object constructors are aliases.
+        let ctor = generics
+            .iter()
+            .find(|g| g.name == "cpp_lib::Buffer::Buffer")
+            .unwrap();
+        assert_eq!(ctor.instances, 3);
+        assert_eq!(ctor.size, 3 * 8);
+    }
+
+    #[test]
+    fn report_instantiating_crates() {
+        let mut at = Atlas::new("test_data/c_app_rust_generics/app").unwrap();
+        assert!(at.report_instantiating_crates().is_none());
+        at.dwarf = true;
+        at.analyze().unwrap();
+
+        let syms = at.syms.as_ref().unwrap();
+        let s = syms
+            .iter()
+            .find(|s| s.demangled == "rust_sorted_max")
+            .unwrap();
+        assert!(s.unit.as_ref().unwrap().contains("/@/rust_lib."));
+
+        // The sorting code of core is instantiated by rust_lib.
+        let crate_rep = at.report_crates().unwrap();
+        assert_eq!(crate_rep.size("rust_lib", MemoryRegion::Rom).as_u64(), 128);
+        assert_eq!(crate_rep.size("core", MemoryRegion::Rom).as_u64(), 20701);
+        let crate_rep = at.report_instantiating_crates().unwrap();
+        assert_eq!(
+            crate_rep.size("rust_lib", MemoryRegion::Rom).as_u64(),
+            17059
+        );
+        assert_eq!(crate_rep.size("core", MemoryRegion::Rom).as_u64(), 3847);
+        assert_eq!(
+            crate_rep
+                .size(report::UNKNOWN_CRATE, MemoryRegion::Rom)
+                .as_u64(),
+            0
+        );
+    }
+
+    #[test]
+    fn report_duplicates() {
+        let mut at = Atlas::new("test_data/c_app_dup_crates/app").unwrap();
+        assert!(at.report_duplicates(MemoryRegion::Rom).is_none());
+        at.analyze().unwrap();
+
+        // Legacy symbols don't contain the disambiguator of the crate.
+        let dup_rep = at.report_duplicates(MemoryRegion::Rom).unwrap();
+        assert!(dup_rep.crates().is_empty());
+
+        at.dwarf = true;
+        at.analyze().unwrap();
+        let dup_rep = at.report_duplicates(MemoryRegion::Rom).unwrap();
+        assert_eq!(dup_rep.crates().len(), 1);
+        let ringbuf = &dup_rep.crates()[0];
+        assert_eq!(ringbuf.name, "ringbuf");
+        assert_eq!(ringbuf.versions.len(), 2);
+        assert_eq!(ringbuf.versions[0].disambiguator, "bc2dcf194c61c5f2");
+        assert_eq!(ringbuf.versions[0].count, 3);
+        assert_eq!(ringbuf.versions[0].size, 0x5e + 0x15 + 0x7c);
+        assert_eq!(ringbuf.versions[1].disambiguator, "a3789542a9d61af9");
+        assert_eq!(ringbuf.versions[1].count, 2);
+        assert_eq!(dup_rep.wasted(), 0x5e + 0x15);
+    }
+
+    #[test]
+    fn report_duplicates_v0() {
+        let mut at = Atlas::new("test_data/c_app_dup_crates/app_v0").unwrap();
+        at.analyze().unwrap();
+
+        let dup_rep = at.report_duplicates(MemoryRegion::Rom).unwrap();
+        assert_eq!(dup_rep.crates().len(), 1);
+        assert_eq!(dup_rep.crates()[0].name, "ringbuf");
+        assert_eq!(dup_rep.wasted(), 0x5e + 0x15);
+        assert!(at
+            .report_duplicates(MemoryRegion::Ram)
+            .unwrap()
+            .crates()
+            .is_empty());
+    }
+
+    #[test]
+    fn set_map_not_found() {
+        let mut at = Atlas::new(file!()).unwrap();
+        let err = at.set_map("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+        assert!(at.map.is_none());
+    }
+
+    #[test]
+    fn analyze_map_invalid() {
+        let mut at = Atlas::new("test_data/c_app_map/app").unwrap();
+        at.set_map("readme.md").unwrap();
+        let err = at.analyze().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidMap);
+    }
+
+    #[test]
+    fn analyze_map_gnu() {
+        analyze_map("test_data/c_app_map/app", "test_data/c_app_map/app.map");
+    }
+
+    #[test]
+    fn analyze_map_lld() {
+        analyze_map(
+            "test_data/c_app_map/app_lld",
+            "test_data/c_app_map/app_lld.map",
+        );
+    }
+
+    fn analyze_map(elf: &str, map: &str) {
+        let mut at = Atlas::new(elf).unwrap();
+        at.set_map(map).unwrap();
+        // The language of the library is deliberately set to Cpp to tell it
+        // apart from the default language. No nm is needed as the library
+        // doesn't have to be parsed.
+        at.add_lib(SymbolLang::Cpp, "test_data/c_app_map/libs/libmath.a")
+            .unwrap();
+        at.analyze().unwrap();
+        let syms = at.syms.as_ref().unwrap();
+        assert_eq!(syms.len(), 7);
+
+        let start = syms.iter().find(|s| s.mangled == "_start").unwrap();
+        assert_eq!(start.archive, None);
+        assert_eq!(start.object.as_deref(), Some("app.o"));
+        assert_eq!(start.lang, SymbolLang::C);
+
+        let lib_mult = syms.iter().find(|s| s.mangled == "lib_mult").unwrap();
+        assert_eq!(lib_mult.archive, Some(PathBuf::from("libs/libmath.a")));
+        assert_eq!(lib_mult.object.as_deref(), Some("lib.o"));
+        assert_eq!(lib_mult.lang, SymbolLang::Cpp);
+
+        let common = syms
+            .iter()
+            .find(|s| s.mangled == "lib_common_counter")
+            .unwrap();
+        assert_eq!(common.archive, Some(PathBuf::from("libs/libmath.a")));
+        assert_eq!(common.lang, SymbolLang::Cpp);
+
+        // Input sections without symbols
+        let unattributed = at.unattributed.as_ref().unwrap();
+        assert_eq!(unattributed.len(), 1);
+        let fill = unattributed
+            .iter()
+            .find(|s| s.mangled == map::FILL)
+            .unwrap();
+        assert_eq!(fill.addr, 0x08000062);
+        assert_eq!(fill.size, 0xe);
+        assert_eq!(fill.sym_type, SymbolType::TextSection);
+        assert_eq!(fill.mem_region, MemoryRegion::Rom);
+        assert_eq!(fill.object, None);
+        assert_eq!(fill.lang, SymbolLang::Any);
+
+        let rodata = syms.iter().find(|s| s.mangled == ".rodata").unwrap();
+        assert_eq!(rodata.size, 0x8);
+        assert_eq!(rodata.sym_type, SymbolType::ReadOnlyDataSection);
+        assert_eq!(rodata.object.as_deref(), Some("lib.o"));
+        assert_eq!(rodata.lang, SymbolLang::Cpp);
+
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size(SymbolLang::Cpp, MemoryRegion::Rom).as_u64(),
+            0x22 + 0xd + 0x8
+        );
+        assert_eq!(
+            lang_rep.size(SymbolLang::Cpp, MemoryRegion::Ram).as_u64(),
+            0x4
+        );
+        assert_eq!(lang_rep.size_unattributed(MemoryRegion::Rom).as_u64(), 0xe);
+        assert_eq!(lang_rep.size_unattributed(MemoryRegion::Ram).as_u64(), 0);
+
+        // Every byte of a section is either attributed to a language or
+        // unattributed.
+        for section in at.report_sections().unwrap().sections() {
+            assert_eq!(
+                section.lang_size(SymbolLang::Any) + section.unattributed,
+                section.size
+            );
+        }
+    }
+
+    #[test]
+    fn analyze_map_uncovered_input_section() {
+        // Same map file but the alignment gap after `lib_mult` is part of its
+        // input section instead of fill.
+        let mut at = Atlas::new("test_data/c_app_map/app").unwrap();
+        at.set_map("test_data/c_app_map/app_no_fill.map").unwrap();
+        at.analyze().unwrap();
+
+        let unattributed = at.unattributed.as_ref().unwrap();
+        let gap = unattributed
+            .iter()
+            .find(|s| s.mangled == ".text.lib_mult")
+            .unwrap();
+        assert_eq!(gap.addr, 0x08000062);
+        assert_eq!(gap.size, 0xe);
+        assert_eq!(gap.object.as_deref(), Some("lib.o"));
+        assert_eq!(gap.lang, SymbolLang::Any);
+
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(
+            lang_rep.size_unattributed(MemoryRegion::Rom).as_u64(),
+            0xe + 0x8
+        );
+        let text = at.report_sections().unwrap();
+        let text = text.section(".text").unwrap();
+        assert_eq!(text.lang_size(SymbolLang::Any), 0x40 + 0x22 + 0xd);
+        assert_eq!(text.unattributed, 0xe);
+
+        // The gap is charged to the library if it has been added.
+        at.add_lib(SymbolLang::Cpp, "test_data/c_app_map/libs/libmath.a")
+            .unwrap();
+        at.analyze().unwrap();
+        assert!(at.unattributed.as_ref().unwrap().is_empty());
+        let lang_rep = at.report_lang().unwrap();
+        assert_eq!(lang_rep.size_unattributed(MemoryRegion::Rom).as_u64(), 0);
+        assert_eq!(
+            lang_rep.size(SymbolLang::Cpp, MemoryRegion::Rom).as_u64(),
+            0x30 + 0xd + 0x8
+        );
+    }
+
+    #[test]
+    fn report_padding_map() {
+        let mut at = Atlas::new("test_data/c_app_map/app").unwrap();
+        at.set_map("test_data/c_app_map/app.map").unwrap();
+        at.add_lib(SymbolLang::Cpp, "test_data/c_app_map/libs/libmath.a")
+            .unwrap();
+        at.analyze().unwrap();
+
+        let padding_rep = at.report_padding(MemoryRegion::Rom).unwrap();
+        assert_eq!(padding_rep.gaps().len(), 1);
+        let gap = &padding_rep.gaps()[0];
+        assert_eq!(gap.addr, 0x08000062);
+        assert_eq!(gap.size, 0xe);
+        assert_eq!(gap.addr_align, 16);
+        assert_eq!(gap.lang, SymbolLang::Cpp);
+        assert_eq!(gap.before, "lib_mult");
+        assert_eq!(gap.after, "lib_name");
+        assert_eq!(padding_rep.size(SymbolLang::Any), 0xe);
+        assert!(at
+            .report_padding(MemoryRegion::Ram)
+            .unwrap()
+            .gaps()
+            .is_empty());
+    }
+
+    #[test]
+    fn report_libs_map() {
+        let mut at = Atlas::new("test_data/c_app_map/app").unwrap();
+        at.set_map("test_data/c_app_map/app.map").unwrap();
+        at.add_lib(SymbolLang::Cpp, "test_data/c_app_map/libs/libmath.a")
+            .unwrap();
+        at.analyze().unwrap();
+
+        let path = at.libs[0].path().to_path_buf();
+        let syms = at.syms.as_ref().unwrap();
+        let lib_mult = syms.iter().find(|s| s.mangled == "lib_mult").unwrap();
+        assert_eq!(lib_mult.lib.as_ref(), Some(&path));
+        let start = syms.iter().find(|s| s.mangled == "_start").unwrap();
+        assert_eq!(start.lib, None);
+
+        let lib_rep = at.report_libs().unwrap();
+        assert_eq!(lib_rep.libs().len(), 1);
+        let lib = &lib_rep.libs()[0];
+        assert_eq!(lib.path, path);
+        assert_eq!(lib.lang, SymbolLang::Cpp);
+        assert_eq!(lib.size(MemoryRegion::Rom).as_u64(), 0x22 + 0xd + 0x8);
+        assert_eq!(lib.size(MemoryRegion::Ram).as_u64(), 0x4);
+        // The library isn't parsed if a map file has been set.
+        assert_eq!(lib.linked, None);
+        assert_eq!(lib.dropped, None);
+    }
+
+    #[test]
+    fn report_objects_map() {
+        let mut at = Atlas::new("test_data/c_app_map/app").unwrap();
+        at.set_map("test_data/c_app_map/app.map").unwrap();
+        at.analyze().unwrap();
+
+        let object_rep = at.report_objects().unwrap();
+        let objects = object_rep
+            .iter_region(MemoryRegion::Both)
+            .map(|(o, size, _)| (o.object.as_str(), o.archive.clone(), size.as_u64()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            objects,
+            vec![
+                ("app.o", None, 250),
+                (
+                    "lib.o",
+                    Some(PathBuf::from("libs/libmath.a")),
+                    0x22 + 0xd + 0x8 + 0x4
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn analyze_c_app_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
         at.add_lib(SymbolLang::Rust, "test_data/c_app_rust_lib/libs/liblib.a")
             .unwrap();
         at.analyze().unwrap();
@@ -182,7 +1036,7 @@ mod tests {
 
     #[test]
     fn analyze_c_app_c_lib_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
         at.add_lib(
             SymbolLang::C,
             "test_data/c_app_c_lib_rust_lib/libs/libc_lib.a",
@@ -236,7 +1090,7 @@ mod tests {
 
     #[test]
     fn report_lang_c_no_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app/app").unwrap();
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
         at.analyze().unwrap();
         let lang_rep = at.report_lang().unwrap();
         assert_eq!(
@@ -278,22 +1132,39 @@ mod tests {
             0
         );
 
-        assert!((lang_rep.size_pct(SymbolLang::C, MemoryRegion::Both) - 100_f64).abs() < 1e-8);
+        // Bytes not covered by any symbol (e.g. `.init`, literal pools, and
+        // alignment fill).
+        assert_eq!(lang_rep.size_unattributed(MemoryRegion::Both).as_u64(), 306);
+        assert_eq!(lang_rep.size_unattributed(MemoryRegion::Rom).as_u64(), 258);
+        assert_eq!(lang_rep.size_unattributed(MemoryRegion::Ram).as_u64(), 48);
+
+        let pct = |size: f64, unattributed: f64| 100_f64 * size / (size + unattributed);
+        assert!(
+            (lang_rep.size_pct(SymbolLang::C, MemoryRegion::Both) - pct(2154.0, 306.0)).abs()
+                < 1e-8
+        );
         assert!((lang_rep.size_pct(SymbolLang::Cpp, MemoryRegion::Both) - 0_f64).abs() < 1e-8);
         assert!((lang_rep.size_pct(SymbolLang::Rust, MemoryRegion::Both) - 0_f64).abs() < 1e-8);
 
-        assert!((lang_rep.size_pct(SymbolLang::C, MemoryRegion::Rom) - 100_f64).abs() < 1e-8);
+        assert!(
+            (lang_rep.size_pct(SymbolLang::C, MemoryRegion::Rom) - pct(842.0, 258.0)).abs() < 1e-8
+        );
         assert!((lang_rep.size_pct(SymbolLang::Cpp, MemoryRegion::Rom) - 0_f64).abs() < 1e-8);
         assert!((lang_rep.size_pct(SymbolLang::Rust, MemoryRegion::Rom) - 0_f64).abs() < 1e-8);
 
-        assert!((lang_rep.size_pct(SymbolLang::C, MemoryRegion::Ram) - 100_f64).abs() < 1e-8);
+        assert!(
+            (lang_rep.size_pct(SymbolLang::C, MemoryRegion::Ram) - pct(1312.0, 48.0)).abs() < 1e-8
+        );
+        assert!(
+            (lang_rep.size_unattributed_pct(MemoryRegion::Ram) - pct(48.0, 1312.0)).abs() < 1e-8
+        );
         assert!((lang_rep.size_pct(SymbolLang::Cpp, MemoryRegion::Ram) - 0_f64).abs() < 1e-8);
         assert!((lang_rep.size_pct(SymbolLang::Rust, MemoryRegion::Ram) - 0_f64).abs() < 1e-8);
     }
 
     #[test]
     fn report_lang_c_app_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
         at.add_lib(SymbolLang::Rust, "test_data/c_app_rust_lib/libs/liblib.a")
             .unwrap();
         at.analyze().unwrap();
@@ -358,7 +1229,7 @@ mod tests {
 
     #[test]
     fn report_lang_c_app_c_lib_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
         at.add_lib(
             SymbolLang::C,
             "test_data/c_app_c_lib_rust_lib/libs/libc_lib.a",
@@ -431,7 +1302,7 @@ mod tests {
 
     #[test]
     fn report_lang_iter_c_app_no_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app/app").unwrap();
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
         at.analyze().unwrap();
         let lang_rep = at.report_lang().unwrap();
         let mut iter = lang_rep.iter_region(MemoryRegion::Both);
@@ -439,7 +1310,8 @@ mod tests {
         let (lang, size, pct) = iter.next().unwrap();
         assert_eq!(lang, SymbolLang::C);
         assert_eq!(size.as_u64(), 2154);
-        assert!((pct - 100_f64).abs() < 1e-8);
+        // The percentages are relative to the 306 unattributed bytes as well.
+        assert!((pct - 100_f64 * 2154.0 / (2154.0 + 306.0)).abs() < 1e-8);
 
         let (lang, size, pct) = iter.next().unwrap();
         assert_eq!(lang, SymbolLang::Cpp);
@@ -454,7 +1326,7 @@ mod tests {
 
     #[test]
     fn report_lang_iter_c_app_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
         at.add_lib(SymbolLang::Rust, "test_data/c_app_rust_lib/libs/liblib.a")
             .unwrap();
         at.analyze().unwrap();
@@ -479,7 +1351,7 @@ mod tests {
 
     #[test]
     fn report_lang_iter_c_app_c_lib_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
         at.add_lib(
             SymbolLang::C,
             "test_data/c_app_c_lib_rust_lib/libs/libc_lib.a",
@@ -531,7 +1403,7 @@ mod tests {
 
     #[test]
     fn report_syms_iter_c_app_no_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app/app").unwrap();
+        let mut at = Atlas::new("test_data/c_app/app").unwrap();
         at.analyze().unwrap();
         let syms_rep = at
             .report_syms(vec![SymbolLang::Any], MemoryRegion::Both, Some(6))
@@ -551,7 +1423,7 @@ mod tests {
 
     #[test]
     fn report_syms_iter_c_app_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_rust_lib/app").unwrap();
         at.add_lib(SymbolLang::Rust, "test_data/c_app_rust_lib/libs/liblib.a")
             .unwrap();
         at.analyze().unwrap();
@@ -598,7 +1470,7 @@ mod tests {
 
     #[test]
     fn report_syms_iter_c_app_c_lib_rust_lib() {
-        let mut at = Atlas::new(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
+        let mut at = Atlas::with_nm(&*NM_PATH, "test_data/c_app_c_lib_rust_lib/app").unwrap();
         at.add_lib(
             SymbolLang::C,
             "test_data/c_app_c_lib_rust_lib/libs/libc_lib.a",
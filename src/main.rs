@@ -1,8 +1,8 @@
 use atlas::sym::{MemoryRegion, SymbolLang};
-use atlas::Atlas;
-use clap::Parser;
+use atlas::{Atlas, Budget, JsonReport};
+use clap::{ArgGroup, Parser, Subcommand};
 use std::error::Error;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Atlas analyzes an ELF binary and analyzes the memory usage in regards to
 /// languages (C, Cpp, Rust), memory regions (e.g. ROM, RAM), and memory
@@ -11,47 +11,197 @@ use std::path::PathBuf;
 // Add a flag to select symbol types (i.e. show me all symbols in BSS)
 #[derive(Debug, Parser)]
 #[clap(about, author, version)]
+#[clap(group(ArgGroup::new("report").args(&[
+    "summary",
+    "crates",
+    "libs",
+    "regions",
+    "sections",
+    "objects",
+    "tree",
+    "files",
+    "generics",
+    "duplicates",
+    "padding",
+    "runtime",
+])))]
 struct Args {
-    /// Path to NM binary.
-    #[clap(long)]
-    nm: PathBuf,
+    #[clap(subcommand)]
+    command: Option<Command>,
 
-    /// Path to application elf.
-    #[clap(long)]
-    elf: PathBuf,
+    /// Path to NM binary used for reading the ELF file. The ELF file is read
+    /// directly if not specified.
+    #[clap(long, global = true)]
+    nm: Option<PathBuf>,
+
+    /// Path to application elf. Required unless the diff subcommand is used.
+    #[clap(long, global = true)]
+    elf: Option<PathBuf>,
+
+    /// Path to the linker map file (GNU ld or LLD) of the application elf.
+    /// Symbols are then attributed to the libraries they were actually linked
+    /// from.
+    #[clap(long, global = true)]
+    map: Option<PathBuf>,
+
+    /// Path to the linker script (e.g. `linker.cmd` or `memory.x`) of the
+    /// application elf. Its MEMORY command declares the regions reported by
+    /// `--regions`.
+    #[clap(long, global = true)]
+    script: Option<PathBuf>,
+
+    /// Path to a region file (TOML) declaring additional memory regions by
+    /// address range. Symbols within them are classified as ROM or RAM by
+    /// their address.
+    #[clap(long, global = true)]
+    region_file: Option<PathBuf>,
+
+    /// Only include the symbols within the named memory region of the linker
+    /// script or region file. Only supported by the summary and the function
+    /// report.
+    #[clap(long, global = true, value_name = "NAME")]
+    in_region: Option<String>,
 
     /// Path to C library.
-    #[clap(long)]
+    #[clap(long, global = true)]
     clib: Vec<PathBuf>,
 
     /// Path to Cpp library.
-    #[clap(long)]
+    #[clap(long, global = true)]
     cpplib: Vec<PathBuf>,
 
     /// Path to Rust library.
-    #[clap(long)]
+    #[clap(long, global = true)]
     rlib: Vec<PathBuf>,
 
+    /// Detect the languages with the DWARF debugging information of the
+    /// application elf (requires building with `-g`).
+    #[clap(long, global = true)]
+    dwarf: bool,
+
     /// Select the languages included in the function report. Multiple
-    /// selections are possible. (any, c, cpp, rust)
-    #[clap(short, long, default_value = "any")]
+    /// selections are possible. (any, c, cpp, rust, asm)
+    #[clap(short, long, global = true, default_value = "any")]
     lang: Vec<String>,
 
     /// Select the memory region used for the reports. (both, ram, rom)
-    #[clap(short, long, default_value = "rom")]
+    #[clap(short, long, global = true, default_value = "rom")]
     region: String,
 
     /// Max count for printing function reports.
-    #[clap(short, long)]
+    #[clap(short, long, global = true)]
     count: Option<usize>,
 
     /// Print a size summary of the languages.
-    #[clap(short, long)]
+    #[clap(short, long, global = true)]
     summary: bool,
 
+    /// Print a size report of the Rust crates.
+    #[clap(long, global = true)]
+    crates: bool,
+
+    /// Charge generic code to the crate that instantiated it in the report of
+    /// the Rust crates. Works best with `--dwarf`.
+    #[clap(long, global = true, requires = "crates")]
+    instantiated: bool,
+
+    /// Print the ROM and RAM usage of every library passed with `--clib`,
+    /// `--cpplib`, or `--rlib` and how many of its symbols have been linked.
+    #[clap(long, global = true)]
+    libs: bool,
+
+    /// Print the used and free bytes of every memory region declared in the
+    /// linker script or region file passed with `--script` or
+    /// `--region-file`.
+    #[clap(long, global = true)]
+    regions: bool,
+
+    /// Print the address, size, and bytes per language of every allocated
+    /// section, similar to `size -A`.
+    #[clap(long, global = true)]
+    sections: bool,
+
+    /// Print a size report of the object files (archive members) the symbols
+    /// were linked from. Requires `--map` or the libraries the object files
+    /// are contained in.
+    #[clap(long, global = true)]
+    objects: bool,
+
+    /// Print the namespaces and modules of the symbols as a tree collapsed to
+    /// the given depth. Use `--count` to limit the entries per level.
+    #[clap(long, global = true, value_name = "DEPTH")]
+    tree: Option<usize>,
+
+    /// Print the size per language of the source files and directories as a
+    /// tree collapsed to the given depth. Use `--count` to limit the entries
+    /// per level.
+    #[clap(long, global = true, value_name = "DEPTH", requires = "dwarf")]
+    files: Option<usize>,
+
+    /// Print the generic functions (Rust) and templates (Cpp) with the number
+    /// and total size of their instantiations.
+    #[clap(long, global = true)]
+    generics: bool,
+
+    /// Print the Rust crates that have been linked in multiple versions.
+    /// Requires v0 mangled symbols or `--dwarf`.
+    #[clap(long, global = true)]
+    duplicates: bool,
+
+    /// Print the largest gaps caused by alignment between adjacent symbols.
+    #[clap(long, global = true)]
+    padding: bool,
+
+    /// Print the padding of every section per language instead of the largest
+    /// gaps. The JSON output then doesn't contain any gaps.
+    #[clap(long, global = true, requires = "padding")]
+    padding_sections: bool,
+
+    /// Print the symbols (mostly of the Rust runtime) that are contained in
+    /// more than one of the Rust libraries and which copy has been linked.
+    #[clap(long, global = true)]
+    runtime: bool,
+
     /// Print memory sizes in human readable format.
-    #[clap(long)]
+    #[clap(long, global = true)]
     human: bool,
+
+    /// Select the output format. (text, json)
+    #[clap(long, global = true, default_value = "text")]
+    format: String,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compare the memory usage of two builds of the application. Reports the
+    /// added, removed, and resized symbols (or the growth of the languages
+    /// with `--summary`) sorted by their absolute growth.
+    Diff {
+        /// Path to the application elf of the old build.
+        #[clap(long)]
+        old: PathBuf,
+
+        /// Path to the application elf of the new build.
+        #[clap(long)]
+        new: PathBuf,
+
+        /// Path to the linker map file of the old build.
+        #[clap(long)]
+        old_map: Option<PathBuf>,
+
+        /// Path to the linker map file of the new build.
+        #[clap(long)]
+        new_map: Option<PathBuf>,
+    },
+
+    /// Check the memory usage of the application elf against the limits of a
+    /// budget file. Prints the exceeded limits and exits with a non-zero exit
+    /// code if any limit is exceeded.
+    Check {
+        /// Path to the budget file (TOML).
+        #[clap(long)]
+        budget: PathBuf,
+    },
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -67,26 +217,303 @@ fn main() -> Result<(), Box<dyn Error>> {
         .iter()
         .map(|l| l.to_lowercase().as_str().parse::<SymbolLang>())
         .collect::<Result<Vec<_>, _>>()?;
+    let json = match args.format.to_lowercase().as_str() {
+        "text" => false,
+        "json" => true,
+        _ => return Err(format!("invalid format: {}", args.format).into()),
+    };
+    let report = Report::select(&args);
+    if args.in_region.is_some()
+        && (args.command.is_some() || !matches!(report, Report::Summary | Report::Syms))
+    {
+        return Err("--in-region is only supported by the summary and the function report".into());
+    }
 
-    let mut at = Atlas::new(&args.nm, &args.elf)?;
-    for lib in &args.clib {
-        at.add_lib(SymbolLang::C, lib).unwrap();
+    if let Some(Command::Diff {
+        old,
+        new,
+        old_map,
+        new_map,
+    }) = &args.command
+    {
+        let old = analyze(&args, old, old_map.as_ref())?;
+        let new = analyze(&args, new, new_map.as_ref())?;
+        let diff_rep = old.report_diff(&new).unwrap();
+        if json {
+            diff_rep.print_json(&mut std::io::stdout())?;
+        } else if args.summary {
+            diff_rep.print_lang(region, args.human, &mut std::io::stdout())?;
+        } else {
+            diff_rep.print_syms(lang, region, args.count, args.human, &mut std::io::stdout())?;
+        }
+        return Ok(());
     }
-    for lib in &args.cpplib {
-        at.add_lib(SymbolLang::Cpp, lib).unwrap();
+
+    let elf = args.elf.as_ref().ok_or("--elf is required")?;
+    let at = analyze(&args, elf, args.map.as_ref())?;
+    if let Some(name) = &args.in_region {
+        if at.report_lang_in(name).is_none() {
+            return Err(format!("unknown memory region: {}", name).into());
+        }
     }
-    for lib in &args.rlib {
-        at.add_lib(SymbolLang::Rust, lib).unwrap();
+
+    if let Some(Command::Check { budget }) = &args.command {
+        let check_rep = at.check(&Budget::open(budget)?).unwrap();
+        if json {
+            JsonReport::new()
+                .check(&check_rep)
+                .print(&mut std::io::stdout())?;
+        } else if check_rep.passed() {
+            println!("All limits of the budget are met.");
+        } else {
+            check_rep.print(args.human, &mut std::io::stdout())?;
+        }
+        if !check_rep.passed() {
+            std::process::exit(1);
+        }
+        return Ok(());
     }
-    at.analyze()?;
 
-    if args.summary {
-        let lang_rep = at.report_lang().unwrap();
-        lang_rep.print(region, args.human, &mut std::io::stdout())?;
-    } else {
-        let syms_rep = at.report_syms(lang, region, args.count).unwrap();
-        syms_rep.print(args.human, &mut std::io::stdout())?;
+    let report_lang = || match &args.in_region {
+        Some(name) => at.report_lang_in(name),
+        None => at.report_lang(),
+    };
+
+    let report_crates = || {
+        if args.instantiated {
+            at.report_instantiating_crates()
+        } else {
+            at.report_crates()
+        }
+    };
+
+    let fails = at.fails.as_deref().unwrap_or_default();
+    match report {
+        Report::Summary => {
+            let lang_rep = report_lang().unwrap();
+            if json {
+                print_json(JsonReport::new().lang(&lang_rep), fails)?;
+            } else {
+                lang_rep.print(region, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Crates => {
+            let crate_rep = report_crates().unwrap();
+            if json {
+                print_json(JsonReport::new().crates(&crate_rep), fails)?;
+            } else {
+                crate_rep.print(region, args.count, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Libs => {
+            let lib_rep = at.report_libs().unwrap();
+            if json {
+                print_json(JsonReport::new().libs(&lib_rep), fails)?;
+            } else {
+                lib_rep.print(args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Objects => {
+            let object_rep = at.report_objects().unwrap();
+            if json {
+                print_json(JsonReport::new().objects(&object_rep), fails)?;
+            } else {
+                object_rep.print(region, args.count, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Regions => {
+            let region_rep = at.report_regions().unwrap();
+            if json {
+                print_json(JsonReport::new().regions(&region_rep), fails)?;
+            } else {
+                region_rep.print(args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Sections => {
+            let section_rep = at.report_sections().unwrap();
+            if json {
+                print_json(JsonReport::new().sections(&section_rep), fails)?;
+            } else {
+                section_rep.print(args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Tree(depth) => {
+            let tree_rep = at.report_tree(lang, region).unwrap();
+            if json {
+                print_json(JsonReport::new().tree(&tree_rep, depth), fails)?;
+            } else {
+                tree_rep.print(depth, args.count, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Files(depth) => {
+            let file_rep = at.report_files().unwrap();
+            if json {
+                print_json(JsonReport::new().files(&file_rep, depth), fails)?;
+            } else {
+                file_rep.print(
+                    region,
+                    depth,
+                    args.count,
+                    args.human,
+                    &mut std::io::stdout(),
+                )?;
+            }
+        }
+        Report::Generics => {
+            let generic_rep = at.report_generics(lang, region).unwrap();
+            if json {
+                print_json(JsonReport::new().generics(&generic_rep), fails)?;
+            } else {
+                generic_rep.print(args.count, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Duplicates => {
+            let duplicate_rep = at.report_duplicates(region).unwrap();
+            if json {
+                print_json(JsonReport::new().duplicates(&duplicate_rep), fails)?;
+            } else {
+                duplicate_rep.print(args.human, &mut std::io::stdout())?;
+            }
+            if duplicate_rep.unchecked() > 0 {
+                eprintln!(
+                    "warning: {} symbols with legacy Rust mangling weren't checked for \
+                     duplicates, use v0 mangling or --dwarf",
+                    duplicate_rep.unchecked()
+                );
+            }
+        }
+        Report::Padding => {
+            let padding_rep = at.report_padding(region).unwrap();
+            // Only the sections are printed, so the gaps are left out.
+            let count = if args.padding_sections {
+                Some(0)
+            } else {
+                args.count
+            };
+            if json {
+                print_json(JsonReport::new().padding(&padding_rep, count), fails)?;
+            } else if args.padding_sections {
+                padding_rep.print_sections(args.human, &mut std::io::stdout())?;
+            } else {
+                padding_rep.print_gaps(count, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Runtime => {
+            let runtime_rep = at.report_runtime().unwrap();
+            if json {
+                print_json(JsonReport::new().runtime(&runtime_rep), fails)?;
+            } else {
+                runtime_rep.print(args.count, args.human, &mut std::io::stdout())?;
+            }
+        }
+        Report::Syms => match &args.in_region {
+            Some(name) => {
+                let syms_rep = at.report_syms_in(lang, name, args.count).unwrap();
+                if json {
+                    print_json(JsonReport::new().syms(&syms_rep), fails)?;
+                } else {
+                    syms_rep.print(args.human, &mut std::io::stdout())?;
+                }
+            }
+            None => {
+                let syms_rep = at.report_syms(lang, region, args.count).unwrap();
+                if json {
+                    print_json(JsonReport::new().syms(&syms_rep), fails)?;
+                } else {
+                    syms_rep.print(args.human, &mut std::io::stdout())?;
+                }
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// The report selected on the command line. The flags selecting a report are
+/// mutually exclusive. The symbol report is printed if none of them is set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Report {
+    Summary,
+    Crates,
+    Libs,
+    Objects,
+    Regions,
+    Sections,
+    Tree(usize),
+    Files(usize),
+    Generics,
+    Duplicates,
+    Padding,
+    Runtime,
+    Syms,
+}
+
+impl Report {
+    fn select(args: &Args) -> Self {
+        if args.summary {
+            Report::Summary
+        } else if args.crates {
+            Report::Crates
+        } else if args.libs {
+            Report::Libs
+        } else if args.objects {
+            Report::Objects
+        } else if args.regions {
+            Report::Regions
+        } else if args.sections {
+            Report::Sections
+        } else if let Some(depth) = args.tree {
+            Report::Tree(depth)
+        } else if let Some(depth) = args.files {
+            Report::Files(depth)
+        } else if args.generics {
+            Report::Generics
+        } else if args.duplicates {
+            Report::Duplicates
+        } else if args.padding {
+            Report::Padding
+        } else if args.runtime {
+            Report::Runtime
+        } else {
+            Report::Syms
+        }
     }
+}
 
+/// Adds the lines of the nm output that couldn't be parsed to the JSON
+/// document and prints it to `stdout`.
+fn print_json<'a>(json_rep: JsonReport<'a>, fails: &'a [String]) -> Result<(), Box<dyn Error>> {
+    json_rep.fails(fails).print(&mut std::io::stdout())?;
     Ok(())
 }
+
+/// Analyzes one build of the application with the options given on the
+/// command line. The ELF and map file are passed separately as the diff
+/// subcommand analyzes two builds.
+fn analyze(args: &Args, elf: &Path, map: Option<&PathBuf>) -> Result<Atlas, Box<dyn Error>> {
+    let mut at = Atlas::new(elf)?;
+    at.dwarf = args.dwarf;
+    if let Some(nm) = &args.nm {
+        at.set_nm(nm)?;
+    }
+    if let Some(map) = map {
+        at.set_map(map)?;
+    }
+    if let Some(script) = &args.script {
+        at.set_script(script)?;
+    }
+    if let Some(region_file) = &args.region_file {
+        at.set_region_file(region_file)?;
+    }
+    for lib in &args.clib {
+        at.add_lib(SymbolLang::C, lib)?;
+    }
+    for lib in &args.cpplib {
+        at.add_lib(SymbolLang::Cpp, lib)?;
+    }
+    for lib in &args.rlib {
+        at.add_lib(SymbolLang::Rust, lib)?;
+    }
+    at.analyze()?;
+    Ok(at)
+}
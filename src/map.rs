@@ -0,0 +1,314 @@
+//! Parse linker map files generated by GNU ld (`-Map=<file>`) and LLD
+//! (`--Map=<file>`). The map file records the object file (and archive) that
+//! every input section of the ELF file originates from. This allows symbols to
+//! be attributed to their actual origin and also covers bytes that don't belong
+//! to any symbol (e.g. fill bytes or string literals).
+
+use crate::error::{Error, ErrorKind};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+#[path = "./map_tests.rs"]
+mod map_tests;
+
+/// Name used for input sections that only contain fill bytes inserted by the
+/// linker (e.g. for alignment).
+pub const FILL: &str = "*fill*";
+
+/// An input section that has been placed in an output section of the ELF file
+/// by the linker.
+#[derive(PartialEq, Debug, Clone)]
+pub struct InputSection {
+    /// Name of the output section (e.g. `.text`).
+    pub output_section: String,
+    /// Name of the input section (e.g. `.text.main`, `COMMON`, or [`FILL`]).
+    pub name: String,
+    pub addr: u64,
+    pub size: u64,
+    /// Path to the static library containing the object file as recorded in
+    /// the map file. `None` if the object file was passed directly to the
+    /// linker.
+    pub archive: Option<PathBuf>,
+    /// Name of the object file (or archive member) containing the input
+    /// section. `None` for sections created by the linker itself (e.g. fill).
+    pub object: Option<String>,
+}
+
+impl InputSection {
+    /// Checks if the input section only contains fill bytes.
+    pub fn is_fill(&self) -> bool {
+        self.name == FILL
+    }
+
+    /// Checks if the given address lies within the input section.
+    pub fn contains(&self, addr: u64) -> bool {
+        (addr >= self.addr) && (addr - self.addr < self.size)
+    }
+
+    /// Creates an input section from the origin of the section as printed in
+    /// the map file (e.g. `libs/libfoo.a(foo.o)` or `main.o`).
+    fn new(output_section: &str, name: &str, addr: u64, size: u64, origin: &str) -> Self {
+        let (archive, object) = split_origin(origin);
+        InputSection {
+            output_section: String::from(output_section),
+            name: String::from(name),
+            addr,
+            size,
+            archive,
+            object,
+        }
+    }
+
+    /// Creates an input section containing fill bytes.
+    fn fill(output_section: &str, addr: u64, size: u64) -> Self {
+        InputSection::new(output_section, FILL, addr, size, "")
+    }
+}
+
+/// A parsed linker map file.
+#[derive(Debug)]
+pub struct MapFile {
+    /// Input sections sorted by their address.
+    sections: Vec<InputSection>,
+}
+
+impl MapFile {
+    /// Reads and parses the map file at the given path. Returns an
+    /// [`ErrorKind::Io`] error if the file couldn't be read and an
+    /// [`ErrorKind::InvalidMap`] error if its format couldn't be recognized.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let data = fs::read(path.as_ref())?;
+        let s = std::str::from_utf8(&data)
+            .map_err(|str_error| Error::new(ErrorKind::InvalidMap).with(str_error))?;
+        MapFile::parse(s)
+    }
+
+    /// Parses the content of a map file. The format (GNU ld or LLD) is
+    /// detected automatically.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let mut sections = if let Some(header) = s.lines().find(|l| is_lld_header(l)) {
+            parse_lld(header, s)
+        } else if s.lines().any(|l| l.trim() == GNU_MEMORY_MAP) {
+            parse_gnu(s)
+        } else {
+            return Err(Error::new(ErrorKind::InvalidMap));
+        };
+        sections.sort_by_key(|s| s.addr);
+
+        Ok(MapFile { sections })
+    }
+
+    /// Returns all input sections sorted by their address. Input sections
+    /// without a size are omitted.
+    pub fn sections(&self) -> &[InputSection] {
+        &self.sections
+    }
+
+    /// Returns the input sections overlapping the address range from `start`
+    /// to `end` (exclusive) sorted by their address. As the input sections
+    /// don't overlap each other, the first one is found with a binary search.
+    pub fn sections_in(&self, start: u64, end: u64) -> impl Iterator<Item = &InputSection> {
+        let first = self.sections.partition_point(|s| s.addr + s.size <= start);
+        self.sections[first..]
+            .iter()
+            .take_while(move |s| s.addr < end)
+    }
+
+    /// Returns the input section containing the given address. Fill sections
+    /// are never returned.
+    pub fn section_at(&self, addr: u64) -> Option<&InputSection> {
+        self.sections_in(addr, addr + 1).find(|s| !s.is_fill())
+    }
+}
+
+/// Title of the part of a GNU ld map file containing the memory map.
+const GNU_MEMORY_MAP: &str = "Linker script and memory map";
+
+/// Title of the optional cross reference table at the end of a GNU ld map file.
+const GNU_CROSS_REFERENCE: &str = "Cross Reference Table";
+
+/// Parses the input sections of a GNU ld map file. Lines before the memory map
+/// (e.g. discarded input sections) are skipped.
+fn parse_gnu(s: &str) -> Vec<InputSection> {
+    lazy_static! {
+        // " .text.main     0x00008000       0x20 main.o"
+        static ref INPUT: Regex = Regex::new(
+            r"^ (\S+)\s+0x([0-9a-fA-F]+)\s+0x([0-9a-fA-F]+)(?:\s+(.*?))?\s*$"
+        )
+        .unwrap();
+        // "                0x00008000       0x20 main.o" (following a long name)
+        static ref INPUT_CONT: Regex = Regex::new(
+            r"^\s+0x([0-9a-fA-F]+)\s+0x([0-9a-fA-F]+)(?:\s+(.*?))?\s*$"
+        )
+        .unwrap();
+    }
+
+    let mut sections: Vec<InputSection> = Vec::new();
+    let mut output = String::new();
+    // Input sections with long names are split over two lines.
+    let mut pending: Option<String> = None;
+
+    let lines = s
+        .lines()
+        .skip_while(|l| l.trim() != GNU_MEMORY_MAP)
+        .take_while(|l| l.trim() != GNU_CROSS_REFERENCE);
+
+    for line in lines {
+        let name = pending.take();
+
+        if line.starts_with(|c: char| !c.is_whitespace()) {
+            // Output sections (or commands like LOAD) start at the first column.
+            output = String::from(line.split_whitespace().next().unwrap_or(""));
+        } else if let Some(caps) = INPUT.captures(line) {
+            let addr = u64::from_str_radix(&caps[2], 16).unwrap_or(0);
+            let size = u64::from_str_radix(&caps[3], 16).unwrap_or(0);
+            push_gnu(&mut sections, &output, &caps[1], addr, size, caps.get(4));
+        } else if let Some(name) = name.filter(|_| INPUT_CONT.is_match(line)) {
+            let caps = INPUT_CONT.captures(line).unwrap();
+            let addr = u64::from_str_radix(&caps[1], 16).unwrap_or(0);
+            let size = u64::from_str_radix(&caps[2], 16).unwrap_or(0);
+            push_gnu(&mut sections, &output, &name, addr, size, caps.get(3));
+        } else if line.starts_with(' ') && !line[1..].starts_with(' ') {
+            pending = line.split_whitespace().next().map(String::from);
+        }
+    }
+
+    sections
+}
+
+/// Adds an input section of a GNU ld map file. Sections without a size are
+/// skipped. The origin of fill sections contains the fill pattern instead of
+/// an object file and is ignored.
+fn push_gnu(
+    sections: &mut Vec<InputSection>,
+    output: &str,
+    name: &str,
+    addr: u64,
+    size: u64,
+    origin: Option<regex::Match>,
+) {
+    if size == 0 {
+        return;
+    }
+
+    if name == FILL {
+        sections.push(InputSection::fill(output, addr, size));
+    } else {
+        let origin = origin.map(|m| m.as_str()).unwrap_or("");
+        sections.push(InputSection::new(output, name, addr, size, origin));
+    }
+}
+
+/// Checks if the line is the header of an LLD map file. Older versions of LLD
+/// only print a single address column.
+fn is_lld_header(line: &str) -> bool {
+    let cols = line.split_whitespace().collect::<Vec<_>>();
+    matches!(
+        cols.as_slice(),
+        ["VMA", "LMA", "Size", "Align", "Out", "In", "Symbol"]
+            | ["Address", "Size", "Align", "Out", "In", "Symbol"]
+    )
+}
+
+/// Parses the input sections of an LLD map file. The nesting (output section,
+/// input section, symbol) is determined by the column the name starts in. LLD
+/// doesn't list fill bytes explicitly. Therefore, the gaps between the input
+/// sections of an output section are added as fill sections.
+fn parse_lld(header: &str, s: &str) -> Vec<InputSection> {
+    let numeric_cols = header.split_whitespace().count() - 3;
+    let in_col = header.find(" In").map(|i| i + 1).unwrap_or(0);
+    let sym_col = header.find(" Symbol").map(|i| i + 1).unwrap_or(0);
+
+    let mut sections: Vec<InputSection> = Vec::new();
+    // Name, address, size, and index of the first input section of the current
+    // output section.
+    let mut output = (String::new(), 0, 0, 0);
+
+    for line in s.lines().skip_while(|l| *l != header).skip(1) {
+        let mut rest = line;
+        let mut values = Vec::with_capacity(numeric_cols);
+        for _ in 0..numeric_cols {
+            rest = rest.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            values.push(u64::from_str_radix(&rest[..end], 16).ok());
+            rest = &rest[end..];
+        }
+        let (addr, size) = match (values.first(), values.get(numeric_cols - 2)) {
+            (Some(Some(addr)), Some(Some(size))) => (*addr, *size),
+            _ => continue,
+        };
+
+        let col = line.len() - rest.trim_start().len();
+        let name = rest.trim();
+        // Assignments in the linker script (e.g. "_sdata = .") are listed as
+        // well but don't occupy any memory.
+        if name.contains(" = ") || name.is_empty() {
+            continue;
+        }
+
+        // The symbols of the input sections aren't needed.
+        if col >= sym_col {
+            continue;
+        } else if col >= in_col {
+            if size == 0 {
+                continue;
+            }
+            let (origin, input) = match name.rfind(":(") {
+                Some(idx) => (&name[..idx], name[idx + 2..].trim_end_matches(')')),
+                None => ("", name),
+            };
+            sections.push(InputSection::new(&output.0, input, addr, size, origin));
+        } else {
+            add_lld_fill(&mut sections, &output);
+            output = (String::from(name), addr, size, sections.len());
+        }
+    }
+    add_lld_fill(&mut sections, &output);
+
+    sections
+}
+
+/// Adds fill sections for the gaps between the input sections of the given
+/// output section (name, address, size, index of the first input section).
+fn add_lld_fill(sections: &mut Vec<InputSection>, output: &(String, u64, u64, usize)) {
+    let (name, addr, size, first) = output;
+    let mut fills = Vec::new();
+    let mut next = *addr;
+
+    for section in &sections[*first..] {
+        if section.addr > next {
+            fills.push(InputSection::fill(name, next, section.addr - next));
+        }
+        next = std::cmp::max(next, section.addr + section.size);
+    }
+    if (*first < sections.len()) && (addr + size > next) {
+        fills.push(InputSection::fill(name, next, addr + size - next));
+    }
+
+    sections.extend(fills);
+    sections[*first..].sort_by_key(|s| s.addr);
+}
+
+/// Splits the origin of an input section into the path to the archive and the
+/// name of the object file. Both GNU ld and LLD use the `archive(member)`
+/// notation. Sections created by the linker have no origin (empty string for
+/// GNU ld and `<internal>` for LLD).
+fn split_origin(origin: &str) -> (Option<PathBuf>, Option<String>) {
+    let origin = origin.trim();
+    if origin.is_empty() || origin == "<internal>" {
+        return (None, None);
+    }
+
+    match origin.strip_suffix(')').and_then(|o| o.split_once('(')) {
+        Some((archive, member)) if !archive.is_empty() => {
+            (Some(PathBuf::from(archive)), Some(String::from(member)))
+        }
+        _ => (None, Some(String::from(origin))),
+    }
+}
@@ -0,0 +1,150 @@
+mod mapfile_tests {
+    use super::super::*;
+
+    #[test]
+    fn open_not_found() {
+        let err = MapFile::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn open_invalid() {
+        let err = MapFile::open("readme.md").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidMap);
+    }
+
+    #[test]
+    fn gnu() {
+        let map = MapFile::open("test_data/c_app_map/app.map").unwrap();
+        check_sections(&map);
+
+        // Discarded input sections are not part of the memory map.
+        assert!(map.sections().iter().all(|s| s.name != ".comment"));
+    }
+
+    #[test]
+    fn lld() {
+        let map = MapFile::open("test_data/c_app_map/app_lld.map").unwrap();
+        check_sections(&map);
+
+        // Input sections created by the linker have no origin.
+        let symtab = map.sections().iter().find(|s| s.name == ".symtab").unwrap();
+        assert_eq!(symtab.archive, None);
+        assert_eq!(symtab.object, None);
+    }
+
+    /// Checks the input sections that are the same for both linkers.
+    fn check_sections(map: &MapFile) {
+        let text = map
+            .sections()
+            .iter()
+            .filter(|s| s.output_section == ".text")
+            .collect::<Vec<_>>();
+        assert_eq!(text.len(), 4);
+
+        assert_eq!(text[0].name, ".text._start");
+        assert_eq!(text[0].addr, 0x08000000);
+        assert_eq!(text[0].size, 0x40);
+        assert_eq!(text[0].archive, None);
+        assert_eq!(text[0].object.as_deref(), Some("app.o"));
+
+        assert_eq!(text[1].name, ".text.lib_mult");
+        assert_eq!(text[1].archive, Some(PathBuf::from("libs/libmath.a")));
+        assert_eq!(text[1].object.as_deref(), Some("lib.o"));
+
+        assert!(text[2].is_fill());
+        assert_eq!(text[2].addr, 0x08000062);
+        assert_eq!(text[2].size, 0xe);
+        assert_eq!(text[2].archive, None);
+        assert_eq!(text[2].object, None);
+
+        assert_eq!(text[3].name, ".text.lib_name");
+        assert_eq!(text[3].addr, 0x08000070);
+        assert_eq!(text[3].size, 0xd);
+
+        let common = map.sections().iter().find(|s| s.name == "COMMON").unwrap();
+        assert_eq!(common.output_section, ".bss");
+        assert_eq!(common.addr, 0x20000080);
+        assert_eq!(common.size, 0x4);
+        assert_eq!(common.archive, Some(PathBuf::from("libs/libmath.a")));
+
+        // Input sections without a size are omitted.
+        assert!(map.sections().iter().all(|s| s.size != 0));
+        assert!(map.sections().windows(2).all(|s| s[0].addr <= s[1].addr));
+    }
+
+    #[test]
+    fn section_at() {
+        let map = MapFile::open("test_data/c_app_map/app.map").unwrap();
+        assert_eq!(map.section_at(0x08000010).unwrap().name, ".text._start");
+        assert_eq!(map.section_at(0x0800007c).unwrap().name, ".text.lib_name");
+        // Fill sections are skipped.
+        assert!(map.section_at(0x08000062).is_none());
+        assert!(map.section_at(0x30000000).is_none());
+    }
+
+    #[test]
+    fn sections_in() {
+        let map = MapFile::open("test_data/c_app_map/app.map").unwrap();
+        let names = map
+            .sections_in(0x08000050, 0x08000071)
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![".text.lib_mult", FILL, ".text.lib_name"]);
+        assert_eq!(map.sections_in(0x08000040, 0x08000040).count(), 0);
+    }
+
+    #[test]
+    fn lld_old_header() {
+        let s = "\
+ Address          Size             Align Out     In      Symbol
+0000000000201000 0000000000000015     4 .text
+0000000000201000 000000000000000e     4         test.o:(.text)
+0000000000201000 0000000000000000     0                 _start
+0000000000201010 0000000000000005     4         libfoo.a(foo.o):(.text.foo)
+0000000000201010 0000000000000000     0                 foo
+";
+        let map = MapFile::parse(s).unwrap();
+        let sections = map.sections();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].name, ".text");
+        assert_eq!(sections[0].size, 0xe);
+        assert!(sections[1].is_fill());
+        assert_eq!(sections[1].addr, 0x20100e);
+        assert_eq!(sections[1].size, 0x2);
+        assert_eq!(sections[2].name, ".text.foo");
+        assert_eq!(sections[2].object.as_deref(), Some("foo.o"));
+    }
+}
+
+mod split_origin_tests {
+    use super::super::*;
+
+    #[test]
+    fn object() {
+        assert_eq!(
+            split_origin("CMakeFiles/app.dir/src/main.c.obj"),
+            (
+                None,
+                Some(String::from("CMakeFiles/app.dir/src/main.c.obj"))
+            )
+        );
+    }
+
+    #[test]
+    fn archive() {
+        assert_eq!(
+            split_origin("zephyr/libzephyr.a(cbprintf_complete.c.obj)"),
+            (
+                Some(PathBuf::from("zephyr/libzephyr.a")),
+                Some(String::from("cbprintf_complete.c.obj"))
+            )
+        );
+    }
+
+    #[test]
+    fn linker() {
+        assert_eq!(split_origin(""), (None, None));
+        assert_eq!(split_origin("<internal>"), (None, None));
+    }
+}
@@ -0,0 +1,109 @@
+//! Declare memory regions by address range in a region file. This is intended
+//! for memories that aren't (or not reliably) declared in the linker script,
+//! like external QSPI flash, retained RAM, ITCM/DTCM, or memory shared with
+//! another core. The region file is written in TOML.
+//!
+//! ```toml
+//! [[region]]
+//! name = "QSPI"
+//! origin = 0x90000000
+//! length = "8 MiB"
+//! memory = "rom"
+//!
+//! [[region]]
+//! name = "ITCM"
+//! origin = 0x00000000
+//! length = "64 KiB"
+//! memory = "ram"
+//! ```
+//!
+//! Symbols within a region are classified as the declared `memory` (`rom` or
+//! `ram`) by their address instead of their section or symbol type. A
+//! function placed in RAM (e.g. `.ramfunc`) is thus counted as RAM. Lengths
+//! are either given in bytes or as strings with a unit (e.g. `"64 KiB"`).
+
+use crate::budget::Size;
+use crate::error::{Error, ErrorKind};
+use crate::script::LinkerRegion;
+use crate::sym::MemoryRegion;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[cfg(test)]
+#[path = "./regions_tests.rs"]
+mod regions_tests;
+
+/// User-defined memory regions read from a region file.
+#[derive(Debug)]
+pub struct RegionFile {
+    regions: Vec<LinkerRegion>,
+}
+
+impl RegionFile {
+    /// Reads and parses the region file at the given path. Returns an
+    /// [`ErrorKind::Io`] error if the file couldn't be read. See
+    /// [`RegionFile::parse`] for the other errors.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let data = fs::read(path.as_ref())?;
+        let s = std::str::from_utf8(&data)
+            .map_err(|str_error| Error::new(ErrorKind::InvalidRegions).with(str_error))?;
+        RegionFile::parse(s)
+    }
+
+    /// Parses the content of a region file. Returns an
+    /// [`ErrorKind::InvalidRegions`] error if the TOML is invalid, a length
+    /// can't be parsed, or a memory other than `rom` or `ram` is used.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let file: RegionFileLayout =
+            toml::from_str(s).map_err(|e| Error::new(ErrorKind::InvalidRegions).with(e))?;
+
+        let regions = file
+            .region
+            .into_iter()
+            .map(|r| {
+                let memory = match r.memory.parse::<MemoryRegion>() {
+                    Ok(memory @ (MemoryRegion::Rom | MemoryRegion::Ram)) => memory,
+                    _ => {
+                        return Err(Error::new(ErrorKind::InvalidRegions)
+                            .with(format!("invalid memory {} of region {}", r.memory, r.name)))
+                    }
+                };
+                Ok(LinkerRegion {
+                    length: r.length.bytes(ErrorKind::InvalidRegions)?,
+                    name: r.name,
+                    attributes: String::new(),
+                    origin: r.origin,
+                    memory: Some(memory),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(RegionFile { regions })
+    }
+
+    /// Returns all regions in the order they are declared.
+    pub fn regions(&self) -> &[LinkerRegion] {
+        &self.regions
+    }
+}
+
+/// Layout of the region file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegionFileLayout {
+    #[serde(default)]
+    region: Vec<RegionLayout>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegionLayout {
+    name: String,
+    origin: u64,
+    length: Size,
+    memory: String,
+}
@@ -0,0 +1,96 @@
+mod regionfile_tests {
+    use super::super::*;
+
+    #[test]
+    fn open_not_found() {
+        let err = RegionFile::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn open() {
+        let file = RegionFile::open("test_data/regions/c_app_lma.toml").unwrap();
+        let names = file
+            .regions()
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["ITCM", "RETAINED"]);
+    }
+
+    #[test]
+    fn parse() {
+        let file = RegionFile::parse(
+            r#"
+            [[region]]
+            name = "QSPI"
+            origin = 0x90000000
+            length = "8 MiB"
+            memory = "rom"
+
+            [[region]]
+            name = "DTCM"
+            origin = 0x20000000
+            length = 131072
+            memory = "RAM"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            file.regions(),
+            &[
+                LinkerRegion {
+                    name: String::from("QSPI"),
+                    attributes: String::new(),
+                    origin: 0x90000000,
+                    length: 8 * 1024 * 1024,
+                    memory: Some(MemoryRegion::Rom),
+                },
+                LinkerRegion {
+                    name: String::from("DTCM"),
+                    attributes: String::new(),
+                    origin: 0x20000000,
+                    length: 128 * 1024,
+                    memory: Some(MemoryRegion::Ram),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert!(RegionFile::parse("").unwrap().regions().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let region = |origin: &str, length: &str, memory: &str| {
+            format!(
+                "[[region]]\nname = \"X\"\norigin = {}\nlength = {}\nmemory = {}",
+                origin, length, memory
+            )
+        };
+        let kind = |s: &str| RegionFile::parse(s).unwrap_err().kind();
+        assert_eq!(
+            kind(&region("0", "4", "\"both\"")),
+            ErrorKind::InvalidRegions
+        );
+        assert_eq!(
+            kind(&region("0", "4", "\"flash\"")),
+            ErrorKind::InvalidRegions
+        );
+        assert_eq!(
+            kind(&region("0", "\"4 XB\"", "\"rom\"")),
+            ErrorKind::InvalidRegions
+        );
+        assert_eq!(
+            kind(&region("\"0\"", "4", "\"rom\"")),
+            ErrorKind::InvalidRegions
+        );
+        assert_eq!(kind("[[region]]\nname = \"X\""), ErrorKind::InvalidRegions);
+        assert_eq!(
+            kind(&(region("0", "4", "\"rom\"") + "\nend = 8")),
+            ErrorKind::InvalidRegions
+        );
+    }
+}
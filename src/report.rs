@@ -1,11 +1,20 @@
 //! Create reports on the memory usage of languages and/or functions after
 //! analysis of the ELF binary.
 
+use crate::budget::CheckReport;
+use crate::demangle;
+use crate::detect::RuntimeDuplicate;
 use crate::error::{Error, ErrorKind};
+use crate::script::LinkerRegion;
 use crate::sym::{MemoryRegion, Symbol, SymbolLang};
 use bytesize::ByteSize;
 use prettytable::{format, Cell, Row, Table};
-use std::{fmt::Debug, io::Write, ops::Add};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{io::Write, ops::Add};
 
 #[cfg(test)]
 #[path = "./report_tests.rs"]
@@ -16,15 +25,28 @@ mod report_tests;
 pub struct CombinedMem {
     rom: ByteSize,
     ram: ByteSize,
+    /// Part of the ROM usage that is copied to RAM at startup (i.e. the initial
+    /// values of initialized data). This is included in both `rom` and `ram`.
+    copied: ByteSize,
 }
 
 impl CombinedMem {
     /// Creates a new instance with the sizes of the ROM and RAM usages provided
     /// in bytes.
     pub fn new(rom: u64, ram: u64) -> Self {
+        CombinedMem::with_copied(rom, ram, 0)
+    }
+
+    /// Same as [`new`] but additionally sets the size in bytes of the data
+    /// that is copied from ROM to RAM at startup. The copied data has to be
+    /// included in the `rom` and `ram` sizes as well.
+    ///
+    /// [`new`]: CombinedMem::new
+    pub fn with_copied(rom: u64, ram: u64, copied: u64) -> Self {
         CombinedMem {
             rom: ByteSize::b(rom),
             ram: ByteSize::b(ram),
+            copied: ByteSize::b(copied),
         }
     }
 }
@@ -36,6 +58,7 @@ impl Add for CombinedMem {
         Self {
             rom: self.rom + other.rom,
             ram: self.ram + other.ram,
+            copied: self.copied + other.copied,
         }
     }
 }
@@ -47,12 +70,33 @@ pub struct LangReport {
     c: CombinedMem,
     cpp: CombinedMem,
     rust: CombinedMem,
+    asm: CombinedMem,
+    /// Bytes of the sections that aren't covered by any symbol.
+    unattributed: CombinedMem,
 }
 
 impl LangReport {
     /// Creates a new [`LangReport`].
-    pub(crate) fn new(c: CombinedMem, cpp: CombinedMem, rust: CombinedMem) -> Self {
-        LangReport { c, cpp, rust }
+    pub(crate) fn new(
+        c: CombinedMem,
+        cpp: CombinedMem,
+        rust: CombinedMem,
+        asm: CombinedMem,
+    ) -> Self {
+        LangReport {
+            c,
+            cpp,
+            rust,
+            asm,
+            unattributed: CombinedMem::default(),
+        }
+    }
+
+    /// Sets the size of the bytes that aren't covered by any symbol (see
+    /// [`crate::Atlas::unattributed`]).
+    pub(crate) fn with_unattributed(mut self, unattributed: CombinedMem) -> Self {
+        self.unattributed = unattributed;
+        self
     }
 
     /// Get the size in bytes of the specified language and memory region.
@@ -69,22 +113,40 @@ impl LangReport {
             SymbolLang::C => self.c,
             SymbolLang::Cpp => self.cpp,
             SymbolLang::Rust => self.rust,
-            SymbolLang::Any => self.c + self.cpp + self.rust,
+            SymbolLang::Asm => self.asm,
+            SymbolLang::Any => self.c + self.cpp + self.rust + self.asm,
         };
-        match mem_region {
-            MemoryRegion::Rom => mem.rom,
-            MemoryRegion::Ram => mem.ram,
-            MemoryRegion::Both => mem.rom + mem.ram,
-            _ => panic!("Invalid memory type!"),
+        region_size(&mem, mem_region)
+    }
+
+    /// Get the size in bytes of the specified memory region that isn't
+    /// covered by any symbol (e.g. literal pools or alignment fill). This
+    /// isn't included in the size of [`SymbolLang::Any`].
+    pub fn size_unattributed(&self, mem_region: MemoryRegion) -> ByteSize {
+        region_size(&self.unattributed, mem_region)
+    }
+
+    /// Get the size in bytes of the initialized data of the specified language
+    /// which is stored in ROM and copied to RAM at startup. This part is
+    /// included in the ROM as well as the RAM size returned by [`size`].
+    ///
+    /// [`size`]: LangReport::size
+    pub fn size_copied(&self, lang: SymbolLang) -> ByteSize {
+        match lang {
+            SymbolLang::C => self.c.copied,
+            SymbolLang::Cpp => self.cpp.copied,
+            SymbolLang::Rust => self.rust.copied,
+            SymbolLang::Asm => self.asm.copied,
+            SymbolLang::Any => self.c.copied + self.cpp.copied + self.rust.copied + self.asm.copied,
         }
     }
 
     /// Get the percentage value of the given language in regards to the sum
-    /// of all languages. Like in the [`size`] method, [`MemoryRegion::Both`]
-    /// can be used to specify that all memory should be included. However,
-    /// using [`SymbolLang::Any`] wouldn't make any sense as the method
-    /// calculates the percentage of the given language to the sum of all
-    /// languages. Thus, this would always return `100`%.
+    /// of all languages and the unattributed bytes. Like in the [`size`]
+    /// method, [`MemoryRegion::Both`] can be used to specify that all memory
+    /// should be included. Using [`SymbolLang::Any`] returns the part that is
+    /// attributed to any language, which is `100`% if all bytes are covered
+    /// by symbols.
     ///
     /// # Example
     /// ```ignore
@@ -94,84 +156,1763 @@ impl LangReport {
     ///
     /// [`size`]: LangReport::size
     pub fn size_pct(&self, lang: SymbolLang, mem_region: MemoryRegion) -> f64 {
-        let sum = self.size(SymbolLang::Any, mem_region).as_u64() as f64;
+        let sum = (self.size(SymbolLang::Any, mem_region) + self.size_unattributed(mem_region))
+            .as_u64() as f64;
         let size = self.size(lang, mem_region).as_u64() as f64;
 
-        100_f64 * size / sum
+        100_f64 * size / sum
+    }
+
+    /// Get the percentage value of the unattributed bytes in regards to the
+    /// sum of all languages and the unattributed bytes.
+    pub fn size_unattributed_pct(&self, mem_region: MemoryRegion) -> f64 {
+        let unattributed = self.size_unattributed(mem_region);
+        let sum = (self.size(SymbolLang::Any, mem_region) + unattributed).as_u64() as f64;
+
+        100_f64 * unattributed.as_u64() as f64 / sum
+    }
+
+    /// Writes a table to the supplied `writer` with a summary of the memory
+    /// usage for every language in the given memory region. The size can either
+    /// be printed in exact bytes or in human-readable KiB, MiB, etc. The last
+    /// column contains the part of the size which is copied from ROM to RAM at
+    /// startup. The bytes that aren't covered by any symbol are listed as
+    /// `Unattributed` in the last row unless there are none. Supply the method
+    /// with a handle to `stdout` if you want to print the table to the
+    /// terminal.
+    ///
+    /// # Example
+    /// ```ignore
+    /// report.print(MemoryRegion::Ram, true, &mut std::io::stdout())?;
+    /// ```
+    pub fn print(
+        &self,
+        mem_type: MemoryRegion,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: ByteSize| {
+            if human_readable {
+                size.to_string_as(true)
+            } else {
+                size.as_u64().to_string()
+            }
+        };
+
+        for x in self.iter_region(mem_type) {
+            let lang_string = x.0.to_string();
+            let size_string = to_string(x.1);
+            let copied_string = to_string(self.size_copied(x.0));
+            let _ = table.add_row(row!(
+                lang_string,
+                size_string,
+                format!("{:.1}", x.2),
+                copied_string
+            ));
+        }
+        let unattributed = self.size_unattributed(mem_type);
+        if unattributed.as_u64() > 0 {
+            let _ = table.add_row(row!(
+                "Unattributed",
+                to_string(unattributed),
+                format!("{:.1}", self.size_unattributed_pct(mem_type)),
+                to_string(self.unattributed.copied)
+            ));
+        }
+
+        table.set_titles(row![
+            mem_type.to_string(),
+            "Size [Bytes]",
+            "%age",
+            "Copied from ROM [Bytes]"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        // `?` uses `From<std::io::error> for Error` to convert the Error variant. This unpacks the
+        // Ok variant with then has to be repackaged.
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the ROM, RAM, and copied size of every language.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().lang(self).print(writer)
+    }
+
+    /// Creates an iterator which returns a tuple for every language containing
+    /// its size in bytes and the percentage relative to the sum of all
+    /// languages. The items returned by the iterator are already sorted
+    /// according to the size with the largest being the first. Use the
+    /// `.rev()` method on the iterator if you want it to start with the largest
+    /// one.
+    pub fn iter_region(
+        &self,
+        mem_region: MemoryRegion,
+    ) -> std::vec::IntoIter<(SymbolLang, ByteSize, f64)> {
+        // NOTE:
+        // In order to be able to sort something, you HAVE to have all the data.
+        // Therefore, putting everything in a Vec and then sorting it in place is
+        // is probably not the stupidest thing to do. However, I'm not sure if
+        // it is a good idea to then turn this vector into a consuming iterator.
+        let mut data = vec![
+            (
+                SymbolLang::C,
+                self.size(SymbolLang::C, mem_region),
+                self.size_pct(SymbolLang::C, mem_region),
+            ),
+            (
+                SymbolLang::Cpp,
+                self.size(SymbolLang::Cpp, mem_region),
+                self.size_pct(SymbolLang::Cpp, mem_region),
+            ),
+            (
+                SymbolLang::Rust,
+                self.size(SymbolLang::Rust, mem_region),
+                self.size_pct(SymbolLang::Rust, mem_region),
+            ),
+            (
+                SymbolLang::Asm,
+                self.size(SymbolLang::Asm, mem_region),
+                self.size_pct(SymbolLang::Asm, mem_region),
+            ),
+        ];
+
+        // Sort by size in reverse order (largest to smallest)
+        data.sort_by(|a, b| b.1.cmp(&a.1));
+        data.into_iter()
+    }
+}
+
+impl Add for LangReport {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            c: self.c + other.c,
+            cpp: self.cpp + other.cpp,
+            rust: self.rust + other.rust,
+            asm: self.asm + other.asm,
+            unattributed: self.unattributed + other.unattributed,
+        }
+    }
+}
+
+/// Name used for Rust symbols whose crate couldn't be determined.
+pub const UNKNOWN_CRATE: &str = "[Unknown]";
+
+/// Name used for symbols whose source file couldn't be determined.
+pub const UNKNOWN_FILE: &str = "[Unknown]";
+
+/// Struct used for reporting the memory usage (ROM/RAM) per Rust crate,
+/// similar to `cargo bloat --crates`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CrateReport {
+    crates: Vec<(String, CombinedMem)>,
+}
+
+impl CrateReport {
+    /// Creates a new [`CrateReport`] from the names and sizes of the crates.
+    pub(crate) fn new(crates: Vec<(String, CombinedMem)>) -> Self {
+        CrateReport { crates }
+    }
+
+    /// Get the size in bytes of the crate in the specified memory region.
+    /// Returns a size of 0 for crates that aren't contained in the report.
+    pub fn size(&self, name: &str, mem_region: MemoryRegion) -> ByteSize {
+        self.crates
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, mem)| region_size(mem, mem_region))
+            .unwrap_or_default()
+    }
+
+    /// Get the percentage value of the crate in regards to the sum of all
+    /// Rust crates in the given memory region.
+    pub fn size_pct(&self, name: &str, mem_region: MemoryRegion) -> f64 {
+        let sum = self
+            .crates
+            .iter()
+            .map(|(_, mem)| region_size(mem, mem_region).as_u64())
+            .sum::<u64>() as f64;
+        let size = self.size(name, mem_region).as_u64() as f64;
+
+        100_f64 * size / sum
+    }
+
+    /// Creates an iterator which returns a tuple for every crate containing
+    /// its name, size in bytes, and the percentage relative to the sum of all
+    /// crates. The items are sorted according to the size with the largest
+    /// being the first. Crates without any memory usage in the region are
+    /// omitted.
+    pub fn iter_region(
+        &self,
+        mem_region: MemoryRegion,
+    ) -> std::vec::IntoIter<(&str, ByteSize, f64)> {
+        let mut data = self
+            .crates
+            .iter()
+            .map(|(name, mem)| {
+                (
+                    name.as_str(),
+                    region_size(mem, mem_region),
+                    self.size_pct(name, mem_region),
+                )
+            })
+            .filter(|d| d.1.as_u64() > 0)
+            .collect::<Vec<_>>();
+
+        data.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        data.into_iter()
+    }
+
+    /// Writes a table to the supplied `writer` with the size of every crate in
+    /// the given memory region starting with the largest one. `max_count` can
+    /// be used to limit the amount of crates in the table.
+    pub fn print(
+        &self,
+        mem_region: MemoryRegion,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        for x in self
+            .iter_region(mem_region)
+            .take(max_count.unwrap_or(usize::MAX))
+        {
+            let size_string = if human_readable {
+                x.1.to_string_as(true)
+            } else {
+                x.1.as_u64().to_string()
+            };
+            let _ = table.add_row(row!(x.0, size_string, format!("{:.1}", x.2)));
+        }
+
+        table.set_titles(row![
+            format!("Crate ({})", mem_region),
+            "Size [Bytes]",
+            "%age"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the ROM, RAM, and copied size of every crate.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().crates(self).print(writer)
+    }
+}
+
+/// Returns the size of the memory usage in the given region.
+fn region_size(mem: &CombinedMem, mem_region: MemoryRegion) -> ByteSize {
+    match mem_region {
+        MemoryRegion::Rom => mem.rom,
+        MemoryRegion::Ram => mem.ram,
+        MemoryRegion::Both => mem.rom + mem.ram,
+        _ => panic!("Invalid memory type!"),
+    }
+}
+
+/// Memory usage of a static library added with [`crate::Atlas::add_lib`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibUsage {
+    pub path: PathBuf,
+    pub lang: SymbolLang,
+    /// ROM and RAM usage of the symbols matched to the library (see
+    /// [`Symbol::lib`]).
+    pub mem: CombinedMem,
+    /// Number of symbols of the library that have been linked. `None` if the
+    /// library hasn't been parsed (e.g. if a linker map file has been set).
+    pub linked: Option<usize>,
+    /// Number of symbols of the library that have been dropped by the linker.
+    /// `None` if the library hasn't been parsed.
+    pub dropped: Option<usize>,
+}
+
+impl LibUsage {
+    /// Get the size in bytes of the library in the specified memory region.
+    pub fn size(&self, mem_region: MemoryRegion) -> ByteSize {
+        region_size(&self.mem, mem_region)
+    }
+}
+
+/// Struct used for reporting how much every static library contributed to the
+/// ELF file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LibReport {
+    libs: Vec<LibUsage>,
+}
+
+impl LibReport {
+    /// Creates a new [`LibReport`] sorted by the total size (ROM and RAM) of
+    /// the libraries with the largest being the first.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_libs`] method.
+    pub(crate) fn new(mut libs: Vec<LibUsage>) -> Self {
+        libs.sort_by(|a, b| {
+            b.size(MemoryRegion::Both)
+                .cmp(&a.size(MemoryRegion::Both))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        LibReport { libs }
+    }
+
+    /// Returns the libraries starting with the largest one.
+    pub fn libs(&self) -> &[LibUsage] {
+        &self.libs
+    }
+
+    /// Writes a table to the supplied `writer` with the ROM and RAM usage of
+    /// every library as well as the number of its symbols that have been
+    /// linked and dropped. The counts are left empty for libraries that
+    /// haven't been parsed.
+    pub fn print(&self, human_readable: bool, writer: &mut impl Write) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: ByteSize| {
+            if human_readable {
+                size.to_string_as(true)
+            } else {
+                size.as_u64().to_string()
+            }
+        };
+        let count_string = |count: Option<usize>| count.map(|c| c.to_string()).unwrap_or_default();
+
+        for lib in &self.libs {
+            let _ = table.add_row(row!(
+                lib.path.file_name().unwrap_or_default().to_string_lossy(),
+                lib.lang,
+                to_string(lib.size(MemoryRegion::Rom)),
+                to_string(lib.size(MemoryRegion::Ram)),
+                count_string(lib.linked),
+                count_string(lib.dropped)
+            ));
+        }
+
+        table.set_titles(row![
+            "Library",
+            "Language",
+            "ROM [Bytes]",
+            "RAM [Bytes]",
+            "Linked",
+            "Dropped"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the sizes and symbol counts of every library.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().libs(self).print(writer)
+    }
+}
+
+/// Memory usage of an object file, either an archive member or an object file
+/// passed directly to the linker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectSize {
+    /// Path to the static library containing the object file. `None` if the
+    /// object file was passed directly to the linker.
+    pub archive: Option<PathBuf>,
+    pub object: String,
+    /// ROM and RAM usage of the symbols linked from the object file.
+    pub mem: CombinedMem,
+}
+
+impl ObjectSize {
+    /// Get the size in bytes of the object file in the specified memory
+    /// region.
+    pub fn size(&self, mem_region: MemoryRegion) -> ByteSize {
+        region_size(&self.mem, mem_region)
+    }
+}
+
+/// Struct used for reporting the memory usage (ROM/RAM) per object file (see
+/// [`Symbol::object`]). This narrows the size down to a single translation
+/// unit.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ObjectReport {
+    objects: Vec<ObjectSize>,
+}
+
+impl ObjectReport {
+    /// Creates a new [`ObjectReport`] from the sizes of the object files.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_objects`] method.
+    pub(crate) fn new(objects: Vec<ObjectSize>) -> Self {
+        ObjectReport { objects }
+    }
+
+    /// Returns all object files in no particular order.
+    pub fn objects(&self) -> &[ObjectSize] {
+        &self.objects
+    }
+
+    /// Creates an iterator which returns a tuple for every object file
+    /// containing the object file, its size in bytes, and the percentage
+    /// relative to the sum of all object files. The items are sorted
+    /// according to the size with the largest being the first. Object files
+    /// without any memory usage in the region are omitted.
+    pub fn iter_region(
+        &self,
+        mem_region: MemoryRegion,
+    ) -> std::vec::IntoIter<(&ObjectSize, ByteSize, f64)> {
+        let sum = self
+            .objects
+            .iter()
+            .map(|o| o.size(mem_region).as_u64())
+            .sum::<u64>() as f64;
+
+        let mut data = self
+            .objects
+            .iter()
+            .map(|o| {
+                let size = o.size(mem_region);
+                (o, size, 100_f64 * size.as_u64() as f64 / sum)
+            })
+            .filter(|d| d.1.as_u64() > 0)
+            .collect::<Vec<_>>();
+
+        data.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.0.archive.cmp(&b.0.archive))
+                .then_with(|| a.0.object.cmp(&b.0.object))
+        });
+        data.into_iter()
+    }
+
+    /// Writes a table to the supplied `writer` with the size of every object
+    /// file in the given memory region starting with the largest one.
+    /// `max_count` can be used to limit the amount of object files in the
+    /// table.
+    pub fn print(
+        &self,
+        mem_region: MemoryRegion,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        for (o, size, pct) in self
+            .iter_region(mem_region)
+            .take(max_count.unwrap_or(usize::MAX))
+        {
+            let size_string = if human_readable {
+                size.to_string_as(true)
+            } else {
+                size.as_u64().to_string()
+            };
+            let archive = o
+                .archive
+                .as_ref()
+                .and_then(|a| a.file_name())
+                .unwrap_or_default()
+                .to_string_lossy();
+            let _ = table.add_row(row!(o.object, archive, size_string, format!("{:.1}", pct)));
+        }
+
+        table.set_titles(row![
+            format!("Object ({})", mem_region),
+            "Library",
+            "Size [Bytes]",
+            "%age"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the ROM, RAM, and copied size of every object file.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().objects(self).print(writer)
+    }
+}
+
+/// Utilization of a memory region of the linker script.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionUsage {
+    pub region: LinkerRegion,
+    /// Number of bytes occupied by the symbols.
+    pub used: u64,
+    /// Number of bytes occupied by the sections but not covered by any
+    /// symbol.
+    pub unattributed: u64,
+}
+
+impl RegionUsage {
+    /// Returns the number of bytes that are still available. Both the used
+    /// and the unattributed bytes are occupied. Overflowing regions have no
+    /// free bytes.
+    pub fn free(&self) -> u64 {
+        self.region
+            .length
+            .saturating_sub(self.used + self.unattributed)
+    }
+
+    /// Returns the percentage of the region that is occupied by the used and
+    /// the unattributed bytes. This is more than `100` if the region
+    /// overflows.
+    pub fn used_pct(&self) -> f64 {
+        100_f64 * (self.used + self.unattributed) as f64 / self.region.length as f64
+    }
+}
+
+/// Struct used for reporting how full the memory regions declared in the
+/// linker script are, similar to the `--print-memory-usage` option of GNU ld.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RegionReport {
+    regions: Vec<RegionUsage>,
+}
+
+impl RegionReport {
+    /// Creates a new [`RegionReport`] from the usage of every region.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_regions`] method.
+    pub(crate) fn new(regions: Vec<RegionUsage>) -> Self {
+        RegionReport { regions }
+    }
+
+    /// Returns the usage of all regions in the order they are declared in the
+    /// linker script.
+    pub fn regions(&self) -> &[RegionUsage] {
+        &self.regions
+    }
+
+    /// Returns the usage of the region with the given name.
+    pub fn region(&self, name: &str) -> Option<&RegionUsage> {
+        self.regions.iter().find(|r| r.region.name == name)
+    }
+
+    /// Writes a table to the supplied `writer` with the origin, size, used,
+    /// unattributed, and free bytes, and the utilization of every region.
+    pub fn print(&self, human_readable: bool, writer: &mut impl Write) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: u64| {
+            if human_readable {
+                ByteSize::b(size).to_string_as(true)
+            } else {
+                size.to_string()
+            }
+        };
+
+        for r in &self.regions {
+            let _ = table.add_row(row!(
+                r.region.name,
+                format!("{:#010x}", r.region.origin),
+                to_string(r.region.length),
+                to_string(r.used),
+                to_string(r.unattributed),
+                to_string(r.free()),
+                format!("{:.1}", r.used_pct())
+            ));
+        }
+
+        table.set_titles(row![
+            "Region",
+            "Origin",
+            "Size [Bytes]",
+            "Used [Bytes]",
+            "Unattributed [Bytes]",
+            "Free [Bytes]",
+            "%age"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the origin, length, used, and free bytes of every region.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().regions(self).print(writer)
+    }
+}
+
+/// An allocated output section of the ELF file with the number of bytes
+/// covered by the symbols of every language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionSize {
+    /// Name of the section (e.g. `.text`).
+    pub name: String,
+    /// Start address of the section at runtime.
+    pub addr: u64,
+    /// Size of the section in bytes.
+    pub size: u64,
+    /// Memory the section is placed in.
+    pub mem_region: MemoryRegion,
+    /// Name of the region of the region file or the linker script containing
+    /// the start address of the section.
+    pub linker_region: Option<String>,
+    pub c: u64,
+    pub cpp: u64,
+    pub rust: u64,
+    pub asm: u64,
+    /// Number of bytes that aren't covered by any symbol.
+    pub unattributed: u64,
+}
+
+impl SectionSize {
+    /// Get the number of bytes covered by the symbols of the specified
+    /// language. [`SymbolLang::Any`] returns the sum of all languages without
+    /// the unattributed bytes.
+    pub fn lang_size(&self, lang: SymbolLang) -> u64 {
+        match lang {
+            SymbolLang::C => self.c,
+            SymbolLang::Cpp => self.cpp,
+            SymbolLang::Rust => self.rust,
+            SymbolLang::Asm => self.asm,
+            SymbolLang::Any => self.c + self.cpp + self.rust + self.asm,
+        }
+    }
+}
+
+/// Struct used for reporting the size of every allocated section, similar to
+/// `size -A`, with the bytes attributed to every language.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SectionReport {
+    sections: Vec<SectionSize>,
+}
+
+impl SectionReport {
+    /// Creates a new [`SectionReport`] from the size of every section.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_sections`] method.
+    pub(crate) fn new(sections: Vec<SectionSize>) -> Self {
+        SectionReport { sections }
+    }
+
+    /// Returns all sections in the order of the section header table.
+    pub fn sections(&self) -> &[SectionSize] {
+        &self.sections
+    }
+
+    /// Returns the section with the given name.
+    pub fn section(&self, name: &str) -> Option<&SectionSize> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    /// Writes a table to the supplied `writer` with the address, memory,
+    /// region, size, and the bytes of every language as well as the
+    /// unattributed bytes of all sections.
+    pub fn print(&self, human_readable: bool, writer: &mut impl Write) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: u64| {
+            if human_readable {
+                ByteSize::b(size).to_string_as(true)
+            } else {
+                size.to_string()
+            }
+        };
+
+        for s in &self.sections {
+            let _ = table.add_row(row!(
+                s.name,
+                format!("{:#010x}", s.addr),
+                s.mem_region,
+                s.linker_region.as_deref().unwrap_or("-"),
+                to_string(s.size),
+                to_string(s.c),
+                to_string(s.cpp),
+                to_string(s.rust),
+                to_string(s.asm),
+                to_string(s.unattributed)
+            ));
+        }
+
+        table.set_titles(row![
+            "Section",
+            "Address",
+            "Memory",
+            "Region",
+            "Size [Bytes]",
+            "C",
+            "Cpp",
+            "Rust",
+            "Asm",
+            "Unattributed"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the address, size, and the bytes of every language of all
+    /// sections.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().sections(self).print(writer)
+    }
+}
+
+/// A node of the [`TreeReport`] (e.g. a namespace, module, type, or symbol).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TreeNode {
+    /// Element of the demangled path represented by this node.
+    pub name: String,
+    /// Aggregated size in bytes of all symbols below this node.
+    pub size: u64,
+    /// Number of symbols below this node.
+    pub count: usize,
+    /// Child nodes sorted by size with the largest being the first.
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    /// Converts the nodes collected while building the tree into [`TreeNode`]s
+    /// sorted by size.
+    fn from_builder(name: String, node: NodeBuilder) -> Self {
+        let mut children = node
+            .children
+            .into_iter()
+            .map(|(name, child)| TreeNode::from_builder(name, child))
+            .collect::<Vec<_>>();
+        children.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        TreeNode {
+            name,
+            size: node.size,
+            count: node.count,
+            children,
+        }
+    }
+}
+
+/// A node of the tree while it is being built. The children are kept in a map
+/// for a fast lookup by name.
+#[derive(Default)]
+struct NodeBuilder {
+    size: u64,
+    count: usize,
+    children: HashMap<String, NodeBuilder>,
+}
+
+impl NodeBuilder {
+    fn add(&mut self, size: u64) {
+        self.size += size;
+        self.count += 1;
+    }
+}
+
+/// Struct used for reporting the size of the namespaces and modules of Cpp and
+/// Rust symbols as a tree. The demangled names of the symbols are split into
+/// their path elements (see [`crate::demangle::split_path`]) and the sizes are
+/// aggregated for every element of the path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeReport {
+    root: TreeNode,
+}
+
+impl TreeReport {
+    /// Creates a new [`TreeReport`] from the given symbols.
+    /// This type is intended to be created by the [`crate::Atlas::report_tree`]
+    /// method which filters the symbols beforehand.
+    pub(crate) fn new<'a>(syms: impl Iterator<Item = &'a Symbol>) -> Self {
+        let mut root = NodeBuilder::default();
+        for s in syms {
+            let mut node = &mut root;
+            for name in demangle::split_path(&s.demangled) {
+                node.add(s.size);
+                node = node.children.entry(String::from(name)).or_default();
+            }
+            node.add(s.size);
+        }
+        let root = TreeNode::from_builder(String::new(), root);
+
+        TreeReport { root }
+    }
+
+    /// Returns the root node of the tree. It has no name and contains the
+    /// total size of all symbols.
+    pub fn root(&self) -> &TreeNode {
+        &self.root
+    }
+
+    /// Returns the node at the given path (e.g. `["ot", "Mle"]`).
+    pub fn node(&self, path: &[&str]) -> Option<&TreeNode> {
+        path.iter().try_fold(&self.root, |node, name| {
+            node.children.iter().find(|c| c.name == *name)
+        })
+    }
+
+    /// Writes a table to the supplied `writer` with the nodes of the tree down
+    /// to `max_depth` (1 for only the top-level namespaces). Deeper nodes are
+    /// collapsed into their parent. Each level is indented and sorted by size.
+    /// `max_count` can be used to limit the number of children printed per
+    /// node.
+    pub fn print(
+        &self,
+        max_depth: usize,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let mut stack = self
+            .root
+            .children
+            .iter()
+            .take(max_count.unwrap_or(usize::MAX))
+            .rev()
+            .map(|node| (node, 0))
+            .collect::<Vec<_>>();
+        while let Some((node, depth)) = stack.pop() {
+            let size_string = if human_readable {
+                ByteSize::b(node.size).to_string_as(true)
+            } else {
+                node.size.to_string()
+            };
+            let _ = table.add_row(row!(
+                format!("{}{}", "  ".repeat(depth), node.name),
+                size_string,
+                node.count
+            ));
+
+            if depth + 1 < max_depth {
+                stack.extend(
+                    node.children
+                        .iter()
+                        .take(max_count.unwrap_or(usize::MAX))
+                        .rev()
+                        .map(|child| (child, depth + 1)),
+                );
+            }
+        }
+
+        table.set_titles(row!["Name", "Size [Bytes]", "Symbols"]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the tree down to `max_depth`.
+    pub fn print_json(&self, max_depth: usize, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().tree(self, max_depth).print(writer)
+    }
+}
+
+/// A directory or source file of the [`FileReport`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileNode {
+    /// Name of the directory or file (a single component of the path).
+    pub name: String,
+    /// Aggregated memory usage per language of all symbols below this node.
+    pub langs: LangReport,
+    /// Number of symbols below this node.
+    pub count: usize,
+    /// Child nodes sorted by the total size (ROM and RAM) with the largest
+    /// being the first. Source files don't have any children.
+    pub children: Vec<FileNode>,
+}
+
+impl FileNode {
+    /// Get the total size in bytes of all languages in the memory region.
+    pub fn size(&self, mem_region: MemoryRegion) -> ByteSize {
+        self.langs.size(SymbolLang::Any, mem_region)
+    }
+
+    /// Converts the nodes collected while building the tree into
+    /// [`FileNode`]s sorted by size.
+    fn from_builder(name: String, node: FileBuilder) -> Self {
+        let mut children = node
+            .children
+            .into_iter()
+            .map(|(name, child)| FileNode::from_builder(name, child))
+            .collect::<Vec<_>>();
+        children.sort_by(|a, b| {
+            b.size(MemoryRegion::Both)
+                .cmp(&a.size(MemoryRegion::Both))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        FileNode {
+            name,
+            langs: node.langs,
+            count: node.count,
+            children,
+        }
+    }
+
+    /// Returns the children with a memory usage in the given region sorted by
+    /// their size in that region.
+    fn children_in(&self, mem_region: MemoryRegion) -> Vec<&FileNode> {
+        let mut children = self
+            .children
+            .iter()
+            .filter(|c| c.size(mem_region).as_u64() > 0)
+            .collect::<Vec<_>>();
+        children.sort_by(|a, b| {
+            b.size(mem_region)
+                .cmp(&a.size(mem_region))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        children
+    }
+}
+
+/// A node of the file tree while it is being built.
+#[derive(Default)]
+struct FileBuilder {
+    langs: LangReport,
+    count: usize,
+    children: BTreeMap<String, FileBuilder>,
+}
+
+impl FileBuilder {
+    fn add(&mut self, langs: LangReport, count: usize) {
+        self.langs = self.langs + langs;
+        self.count += count;
+    }
+}
+
+/// Struct used for reporting the memory usage (ROM/RAM) per language of the
+/// source files and directories as a tree, similar to the `rom_report` and
+/// `ram_report` targets of Zephyr. The source files are taken from the DWARF
+/// debugging information (see [`crate::DwarfInfo::file_at`]). The directories
+/// shared by all source files are removed from the tree (see
+/// [`FileReport::prefix`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileReport {
+    prefix: PathBuf,
+    root: FileNode,
+}
+
+impl FileReport {
+    /// Creates a new [`FileReport`] from the path to every source file with
+    /// the memory usage per language and the number of its symbols. Symbols
+    /// without a source file are passed with a path of `None` and are added
+    /// as a top-level node named [`UNKNOWN_FILE`].
+    /// This type is intended to be created by the [`crate::Atlas::report_files`]
+    /// method.
+    pub(crate) fn new(files: Vec<(Option<String>, LangReport, usize)>) -> Self {
+        let mut root = FileBuilder::default();
+        let mut unknown = FileBuilder::default();
+        for (path, langs, count) in files {
+            let path = match path {
+                Some(path) => path,
+                None => {
+                    unknown.add(langs, count);
+                    continue;
+                }
+            };
+            let mut node = &mut root;
+            for component in Path::new(&path).components() {
+                node.add(langs, count);
+                let name = component.as_os_str().to_string_lossy().into_owned();
+                node = node.children.entry(name).or_default();
+            }
+            node.add(langs, count);
+        }
+
+        // Directories containing the whole tree are collapsed into the prefix.
+        let mut prefix = PathBuf::new();
+        while root.children.len() == 1 {
+            let (name, child) = root.children.iter_mut().next().unwrap();
+            if child.children.is_empty() {
+                break;
+            }
+            prefix.push(name);
+            root = std::mem::take(child);
+        }
+
+        if unknown.count > 0 {
+            root.add(unknown.langs, unknown.count);
+            root.children.insert(String::from(UNKNOWN_FILE), unknown);
+        }
+        let root = FileNode::from_builder(String::new(), root);
+
+        FileReport { prefix, root }
+    }
+
+    /// Returns the directory shared by all source files which has been
+    /// removed from the tree.
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// Returns the root node of the tree. It has no name and contains the
+    /// total memory usage of all symbols.
+    pub fn root(&self) -> &FileNode {
+        &self.root
+    }
+
+    /// Returns the node of the directory or file at the given path relative
+    /// to the [`FileReport::prefix`] (e.g. `zephyr/subsys/net`).
+    pub fn node(&self, path: &str) -> Option<&FileNode> {
+        Path::new(path)
+            .components()
+            .try_fold(&self.root, |node, component| {
+                let name = component.as_os_str().to_string_lossy();
+                node.children.iter().find(|c| c.name == name)
+            })
+    }
+
+    /// Writes a table to the supplied `writer` with the size per language of
+    /// the directories and files in the given memory region down to
+    /// `max_depth` (1 for only the top-level directories). Deeper nodes are
+    /// collapsed into their parent. Each level is indented and sorted by size.
+    /// `max_count` can be used to limit the number of children printed per
+    /// node.
+    pub fn print(
+        &self,
+        mem_region: MemoryRegion,
+        max_depth: usize,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: ByteSize| {
+            if human_readable {
+                size.to_string_as(true)
+            } else {
+                size.as_u64().to_string()
+            }
+        };
+        let total = self.root.size(mem_region).as_u64() as f64;
+
+        let mut stack = self
+            .root
+            .children_in(mem_region)
+            .into_iter()
+            .take(max_count.unwrap_or(usize::MAX))
+            .rev()
+            .map(|node| (node, 0))
+            .collect::<Vec<_>>();
+        while let Some((node, depth)) = stack.pop() {
+            let size = node.size(mem_region);
+            let _ = table.add_row(row!(
+                format!("{}{}", "  ".repeat(depth), node.name),
+                to_string(node.langs.size(SymbolLang::C, mem_region)),
+                to_string(node.langs.size(SymbolLang::Cpp, mem_region)),
+                to_string(node.langs.size(SymbolLang::Rust, mem_region)),
+                to_string(node.langs.size(SymbolLang::Asm, mem_region)),
+                to_string(size),
+                format!("{:.1}", 100_f64 * size.as_u64() as f64 / total)
+            ));
+
+            if depth + 1 < max_depth {
+                stack.extend(
+                    node.children_in(mem_region)
+                        .into_iter()
+                        .take(max_count.unwrap_or(usize::MAX))
+                        .rev()
+                        .map(|child| (child, depth + 1)),
+                );
+            }
+        }
+
+        table.set_titles(row![
+            format!("Path ({})", mem_region),
+            "C",
+            "Cpp",
+            "Rust",
+            "Asm",
+            "Size [Bytes]",
+            "%age"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the tree down to `max_depth`.
+    pub fn print_json(&self, max_depth: usize, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().files(self, max_depth).print(writer)
+    }
+}
+
+/// A generic function (or static) of the [`GenericReport`] together with the
+/// instantiations created from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericFn {
+    /// Demangled name without the generic or template arguments (see
+    /// [`crate::demangle::generic_base`]).
+    pub name: String,
+    pub lang: SymbolLang,
+    /// Number of instantiations (Rust monomorphizations or Cpp template
+    /// instantiations).
+    pub instances: usize,
+    /// Total size in bytes of all instantiations.
+    pub size: u64,
+}
+
+/// Struct used for reporting the size of generic code. Rust monomorphizations
+/// and Cpp template instantiations only differ in their generic arguments and
+/// are aggregated under the function they were created from. Legacy Rust
+/// symbols don't contain the generic arguments at all. Their monomorphizations
+/// share the same demangled name and are aggregated as well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericReport {
+    generics: Vec<GenericFn>,
+}
+
+impl GenericReport {
+    /// Creates a new [`GenericReport`] from the given symbols. Only functions
+    /// with at least two instantiations are included. Aliases (e.g. the
+    /// complete and base object constructors of Cpp) are counted once.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_generics`] method which filters the symbols
+    /// beforehand.
+    pub(crate) fn new<'a>(syms: impl Iterator<Item = &'a Symbol>) -> Self {
+        let mut groups: HashMap<(String, SymbolLang), Vec<&Symbol>> = HashMap::new();
+        for s in syms {
+            let name = match demangle::generic_base(&s.demangled) {
+                Some(base) => base,
+                None if s.lang == SymbolLang::Rust => s.demangled.clone(),
+                None => continue,
+            };
+            let group = groups.entry((name, s.lang)).or_default();
+            if !group.iter().any(|g| g.addr == s.addr) {
+                group.push(s);
+            }
+        }
+
+        let mut generics = groups
+            .into_iter()
+            .filter(|(_, group)| group.len() >= 2)
+            .map(|((name, lang), group)| GenericFn {
+                name,
+                lang,
+                instances: group.len(),
+                size: group.iter().map(|s| s.size).sum(),
+            })
+            .collect::<Vec<_>>();
+        generics.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        GenericReport { generics }
+    }
+
+    /// Returns the generic functions with the largest total size first.
+    pub fn generics(&self) -> &[GenericFn] {
+        &self.generics
+    }
+
+    /// Writes a table to the supplied `writer` with the generic functions
+    /// starting with the largest. `max_count` can be used to limit the number
+    /// of functions printed.
+    pub fn print(
+        &self,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        for g in self.generics.iter().take(max_count.unwrap_or(usize::MAX)) {
+            let size_string = if human_readable {
+                ByteSize::b(g.size).to_string_as(true)
+            } else {
+                g.size.to_string()
+            };
+            let _ = table.add_row(row!(g.lang, g.name, g.instances, size_string));
+        }
+
+        table.set_titles(row!["Language", "Name", "Instances", "Size [Bytes]"]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the generic functions.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().generics(self).print(writer)
+    }
+}
+
+/// A version of a crate of the [`DuplicateReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrateVersion {
+    /// Disambiguator of the crate (see [`crate::demangle::crate_version`]).
+    pub disambiguator: String,
+    /// Number of symbols of this version.
+    pub count: usize,
+    /// Size in bytes of the symbols of this version.
+    pub size: u64,
+}
+
+/// A crate of the [`DuplicateReport`] that has been linked in multiple
+/// versions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCrate {
+    pub name: String,
+    /// Versions of the crate sorted by size with the largest being the first.
+    pub versions: Vec<CrateVersion>,
+}
+
+impl DuplicateCrate {
+    /// Returns the number of bytes that could be saved by only linking a
+    /// single version of the crate. This is the size of all but the largest
+    /// version.
+    pub fn wasted(&self) -> u64 {
+        self.versions.iter().skip(1).map(|v| v.size).sum()
+    }
+}
+
+/// Struct used for reporting the Rust crates that have been linked in multiple
+/// versions (e.g. two versions of `heapless` pulled in by different
+/// dependencies). The versions are told apart by the disambiguator of the
+/// crates (see [`Symbol::crate_version`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateReport {
+    crates: Vec<DuplicateCrate>,
+    unchecked: usize,
+}
+
+impl DuplicateReport {
+    /// Creates a new [`DuplicateReport`] from the given symbols. Symbols
+    /// without a known disambiguator are ignored. The ones with legacy Rust
+    /// mangling are counted as unchecked (see [`DuplicateReport::unchecked`]).
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_duplicates`] method which filters the symbols
+    /// beforehand.
+    pub(crate) fn new<'a>(syms: impl Iterator<Item = &'a Symbol>) -> Self {
+        let mut crates: HashMap<String, HashMap<String, CrateVersion>> = HashMap::new();
+        let mut unchecked = 0;
+        for s in syms {
+            let (name, disambiguator) = match s.crate_version() {
+                Some(version) => version,
+                None => {
+                    if demangle::scheme(&s.mangled) == demangle::ManglingScheme::RustLegacy {
+                        unchecked += 1;
+                    }
+                    continue;
+                }
+            };
+            let version = crates
+                .entry(name)
+                .or_default()
+                .entry(disambiguator.clone())
+                .or_insert(CrateVersion {
+                    disambiguator,
+                    count: 0,
+                    size: 0,
+                });
+            version.count += 1;
+            version.size += s.size;
+        }
+
+        let mut crates = crates
+            .into_iter()
+            .filter(|(_, versions)| versions.len() >= 2)
+            .map(|(name, versions)| {
+                let mut versions = versions.into_values().collect::<Vec<_>>();
+                versions.sort_by(|a, b| {
+                    b.size
+                        .cmp(&a.size)
+                        .then_with(|| a.disambiguator.cmp(&b.disambiguator))
+                });
+                DuplicateCrate { name, versions }
+            })
+            .collect::<Vec<_>>();
+        crates.sort_by(|a, b| {
+            b.wasted()
+                .cmp(&a.wasted())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        DuplicateReport { crates, unchecked }
+    }
+
+    /// Returns the duplicated crates starting with the most wasted bytes.
+    pub fn crates(&self) -> &[DuplicateCrate] {
+        &self.crates
+    }
+
+    /// Returns the number of bytes wasted by all duplicated crates.
+    pub fn wasted(&self) -> u64 {
+        self.crates.iter().map(|c| c.wasted()).sum()
+    }
+
+    /// Returns the number of symbols with legacy Rust mangling whose crate
+    /// version is unknown. The hash of a legacy symbol covers the whole
+    /// symbol, so the disambiguator of its crate can't be derived from it.
+    /// Duplicates among these symbols are only detected if the DWARF
+    /// debugging information is used or the symbols are v0 mangled.
+    pub fn unchecked(&self) -> usize {
+        self.unchecked
+    }
+
+    /// Writes a table to the supplied `writer` with every version of the
+    /// duplicated crates. The wasted bytes are listed next to the largest
+    /// version.
+    pub fn print(&self, human_readable: bool, writer: &mut impl Write) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: u64| {
+            if human_readable {
+                ByteSize::b(size).to_string_as(true)
+            } else {
+                size.to_string()
+            }
+        };
+
+        for c in &self.crates {
+            for (i, v) in c.versions.iter().enumerate() {
+                let (name, wasted) = if i == 0 {
+                    (c.name.as_str(), to_string(c.wasted()))
+                } else {
+                    ("", String::new())
+                };
+                let _ = table.add_row(row!(
+                    name,
+                    v.disambiguator,
+                    v.count,
+                    to_string(v.size),
+                    wasted
+                ));
+            }
+        }
+
+        table.set_titles(row![
+            "Crate",
+            "Disambiguator",
+            "Symbols",
+            "Size [Bytes]",
+            "Wasted [Bytes]"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the duplicated crates.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().duplicates(self).print(writer)
+    }
+}
+
+/// A gap between two adjacent symbols of a section that is presumably caused
+/// by the alignment of the following symbol or that has been filled by the
+/// linker according to the linker map file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaddingGap {
+    /// Start address of the gap.
+    pub addr: u64,
+    /// Size of the gap in bytes.
+    pub size: u64,
+    /// Address alignment of the following symbol, i.e. the largest power of
+    /// two its address is a multiple of. This is an upper bound of the
+    /// alignment the symbol actually requires, which isn't recorded in the ELF
+    /// file.
+    pub addr_align: u64,
+    pub section: String,
+    /// Language of the following symbol which the gap is attributed to.
+    /// [`SymbolLang::Any`] if there is no following symbol or its language is
+    /// unknown.
+    pub lang: SymbolLang,
+    /// Demangled name of the symbol in front of the gap. Empty if there is no
+    /// such symbol.
+    pub before: String,
+    /// Demangled name of the symbol after the gap. Empty if there is no such
+    /// symbol.
+    pub after: String,
+}
+
+/// Padding of a section of the [`PaddingReport`] per language.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SectionPadding {
+    pub name: String,
+    pub c: u64,
+    pub cpp: u64,
+    pub rust: u64,
+    pub asm: u64,
+    /// Padding that isn't followed by a symbol of a known language.
+    pub unattributed: u64,
+}
+
+impl SectionPadding {
+    /// Get the number of bytes of padding in front of the symbols of the
+    /// specified language. [`SymbolLang::Any`] returns the sum of all
+    /// languages and the unattributed padding, i.e. the size of all gaps of
+    /// the section.
+    pub fn lang_size(&self, lang: SymbolLang) -> u64 {
+        match lang {
+            SymbolLang::C => self.c,
+            SymbolLang::Cpp => self.cpp,
+            SymbolLang::Rust => self.rust,
+            SymbolLang::Asm => self.asm,
+            SymbolLang::Any => self.c + self.cpp + self.rust + self.asm + self.unattributed,
+        }
+    }
+}
+
+/// Struct used for reporting the bytes lost to alignment between adjacent
+/// symbols (e.g. in front of 32-byte aligned stack objects).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PaddingReport {
+    gaps: Vec<PaddingGap>,
+    sections: Vec<SectionPadding>,
+}
+
+impl PaddingReport {
+    /// Creates a new [`PaddingReport`] from the given symbols. The symbols of
+    /// every section are sorted by their address. A gap between two adjacent
+    /// symbols is considered padding if it is smaller than the address
+    /// alignment of the following symbol (see [`PaddingGap::addr_align`]).
+    /// Larger gaps (e.g. literal pools) and the bytes in front of the first and
+    /// after the last symbol of a section aren't included. Symbols without a
+    /// size or section are ignored. This type is intended to be created by the
+    /// [`crate::Atlas::report_padding`] method which filters the symbols
+    /// beforehand.
+    pub(crate) fn new<'a>(syms: impl Iterator<Item = &'a Symbol>) -> Self {
+        let mut gaps = Vec::new();
+        for (section, syms) in group_by_section(syms) {
+            let mut prev = syms[0];
+            let mut end = prev.addr + prev.size;
+            for s in syms.into_iter().skip(1) {
+                if s.addr > end {
+                    let addr_align = 1 << s.addr.trailing_zeros();
+                    if s.addr - end < addr_align {
+                        gaps.push(PaddingGap {
+                            addr: end,
+                            size: s.addr - end,
+                            addr_align,
+                            section: String::from(section),
+                            lang: s.lang,
+                            before: prev.demangled.clone(),
+                            after: s.demangled.clone(),
+                        });
+                    }
+                }
+                if s.addr + s.size > end {
+                    end = s.addr + s.size;
+                    prev = s;
+                }
+            }
+        }
+
+        PaddingReport::from_gaps(gaps)
+    }
+
+    /// Creates a new [`PaddingReport`] from the fill bytes recorded in the
+    /// linker map file (see [`crate::map::FILL`]) instead of guessing the
+    /// padding from the addresses of the symbols. Every fill is reported as a
+    /// gap between the adjacent symbols of its section regardless of its size.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_padding`] method which filters the symbols and
+    /// the fill beforehand.
+    pub(crate) fn with_fill<'a>(
+        syms: impl Iterator<Item = &'a Symbol>,
+        fill: impl Iterator<Item = &'a Symbol>,
+    ) -> Self {
+        let sections = group_by_section(syms);
+
+        let mut gaps = Vec::new();
+        for f in fill.filter(|f| f.size > 0) {
+            let section = match &f.section {
+                Some(section) => section,
+                None => continue,
+            };
+            let syms = sections
+                .get(section.as_str())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let end = f.addr + f.size;
+            let before = syms[..syms.partition_point(|s| s.addr < f.addr)].last();
+            let after = syms.get(syms.partition_point(|s| s.addr < end));
+            let name = |s: Option<&&Symbol>| s.map(|s| s.demangled.clone()).unwrap_or_default();
+
+            gaps.push(PaddingGap {
+                addr: f.addr,
+                size: f.size,
+                addr_align: 1 << end.trailing_zeros(),
+                section: section.clone(),
+                lang: after.map(|s| s.lang).unwrap_or(SymbolLang::Any),
+                before: name(before),
+                after: name(after),
+            });
+        }
+
+        PaddingReport::from_gaps(gaps)
+    }
+
+    /// Sums up the gaps of every section.
+    fn from_gaps(mut gaps: Vec<PaddingGap>) -> Self {
+        gaps.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.addr.cmp(&b.addr)));
+
+        let mut sections: BTreeMap<&str, SectionPadding> = BTreeMap::new();
+        for g in &gaps {
+            let section = sections.entry(&g.section).or_default();
+            match g.lang {
+                SymbolLang::C => section.c += g.size,
+                SymbolLang::Cpp => section.cpp += g.size,
+                SymbolLang::Rust => section.rust += g.size,
+                SymbolLang::Asm => section.asm += g.size,
+                SymbolLang::Any => section.unattributed += g.size,
+            }
+        }
+        let mut sections = sections
+            .into_iter()
+            .map(|(name, padding)| SectionPadding {
+                name: String::from(name),
+                ..padding
+            })
+            .collect::<Vec<_>>();
+        sections.sort_by(|a, b| {
+            b.lang_size(SymbolLang::Any)
+                .cmp(&a.lang_size(SymbolLang::Any))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        PaddingReport { gaps, sections }
+    }
+
+    /// Returns all gaps starting with the largest.
+    pub fn gaps(&self) -> &[PaddingGap] {
+        &self.gaps
+    }
+
+    /// Returns the padding of every section starting with the most padding.
+    pub fn sections(&self) -> &[SectionPadding] {
+        &self.sections
+    }
+
+    /// Returns the padding of the section with the given name.
+    pub fn section(&self, name: &str) -> Option<&SectionPadding> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    /// Returns the number of bytes of padding in front of the symbols of the
+    /// specified language in all sections. [`SymbolLang::Any`] returns the
+    /// size of all gaps.
+    pub fn size(&self, lang: SymbolLang) -> u64 {
+        self.sections.iter().map(|s| s.lang_size(lang)).sum()
+    }
+
+    /// Writes a table to the supplied `writer` with the padding of every
+    /// language in every section. The last row contains the total of all
+    /// sections.
+    pub fn print_sections(
+        &self,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: u64| {
+            if human_readable {
+                ByteSize::b(size).to_string_as(true)
+            } else {
+                size.to_string()
+            }
+        };
+
+        for s in &self.sections {
+            let _ = table.add_row(row!(
+                s.name,
+                to_string(s.c),
+                to_string(s.cpp),
+                to_string(s.rust),
+                to_string(s.asm),
+                to_string(s.unattributed),
+                to_string(s.lang_size(SymbolLang::Any))
+            ));
+        }
+        let _ = table.add_row(row!(
+            "Total",
+            to_string(self.size(SymbolLang::C)),
+            to_string(self.size(SymbolLang::Cpp)),
+            to_string(self.size(SymbolLang::Rust)),
+            to_string(self.size(SymbolLang::Asm)),
+            to_string(self.sections.iter().map(|s| s.unattributed).sum()),
+            to_string(self.size(SymbolLang::Any))
+        ));
+
+        table.set_titles(row![
+            "Section",
+            "C",
+            "Cpp",
+            "Rust",
+            "Asm",
+            "Unattributed",
+            "Padding [Bytes]"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a table to the supplied `writer` with the gaps starting with the
+    /// largest. The table contains the size, address alignment, address, and
+    /// section of every gap as well as the symbols on both sides. `max_count`
+    /// can be used to limit the number of gaps printed.
+    pub fn print_gaps(
+        &self,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        for g in self.gaps.iter().take(max_count.unwrap_or(usize::MAX)) {
+            let size_string = if human_readable {
+                ByteSize::b(g.size).to_string_as(true)
+            } else {
+                g.size.to_string()
+            };
+            let _ = table.add_row(row!(
+                size_string,
+                g.addr_align,
+                format!("{:#010x}", g.addr),
+                g.section,
+                g.before,
+                g.after
+            ));
+        }
+
+        table.set_titles(row![
+            "Size [Bytes]",
+            "Addr Align",
+            "Address",
+            "Section",
+            "Before",
+            "After"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the padding of every section and the largest gaps.
+    /// `max_count` can be used to limit the number of gaps.
+    pub fn print_json(
+        &self,
+        max_count: Option<usize>,
+        writer: &mut impl Write,
+    ) -> Result<(), Error> {
+        JsonReport::new().padding(self, max_count).print(writer)
+    }
+}
+
+/// Groups the symbols with a size by their section. The symbols of every
+/// section are sorted by their address and the larger one comes first if two
+/// symbols share an address (e.g. aliases). Symbols without a section are
+/// skipped.
+fn group_by_section<'a>(
+    syms: impl Iterator<Item = &'a Symbol>,
+) -> BTreeMap<&'a str, Vec<&'a Symbol>> {
+    let mut sections: BTreeMap<&str, Vec<&Symbol>> = BTreeMap::new();
+    for s in syms.filter(|s| s.size > 0) {
+        if let Some(section) = &s.section {
+            sections.entry(section).or_default().push(s);
+        }
+    }
+    for syms in sections.values_mut() {
+        syms.sort_by(|a, b| a.addr.cmp(&b.addr).then_with(|| b.size.cmp(&a.size)));
+    }
+    sections
+}
+
+/// Struct used for reporting the symbols contained in more than one of the
+/// Rust libraries (see [`RuntimeDuplicate`]). Every Rust static library
+/// bundles its own copy of `core`, `alloc`, and `compiler_builtins`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeReport<'a> {
+    dups: Vec<&'a RuntimeDuplicate>,
+}
+
+impl<'a> RuntimeReport<'a> {
+    /// Creates a new [`RuntimeReport`] sorted by the wasted bytes, the number
+    /// of linked copies, and the size of the copies.
+    /// This type is intended to be created by the
+    /// [`crate::Atlas::report_runtime`] method.
+    pub(crate) fn new(dups: &'a [RuntimeDuplicate]) -> Self {
+        let mut dups = dups.iter().collect::<Vec<_>>();
+        let max_size = |d: &RuntimeDuplicate| d.libs.iter().map(|(_, size)| *size).max();
+        dups.sort_by(|a, b| {
+            b.wasted()
+                .cmp(&a.wasted())
+                .then_with(|| b.linked.len().cmp(&a.linked.len()))
+                .then_with(|| max_size(b).cmp(&max_size(a)))
+                .then_with(|| a.demangled.cmp(&b.demangled))
+        });
+
+        RuntimeReport { dups }
+    }
+
+    /// Returns the duplicated symbols starting with the most wasted bytes.
+    pub fn dups(&self) -> &[&'a RuntimeDuplicate] {
+        &self.dups
     }
 
-    /// Writes a table to the supplied `writer` with a summary of the memory
-    /// usage for every language in the given memory region. The size can either
-    /// be printed in exact bytes or in human-readable KiB, MiB, etc. Supply the
-    /// method with a handle to `stdout` if you want to print the table to the
-    /// terminal.
-    ///
-    /// # Example
-    /// ```ignore
-    /// report.print(MemoryRegion::Ram, true, &mut std::io::stdout())?;
-    /// ```
+    /// Returns the number of bytes wasted by all additional copies linked into
+    /// the ELF file.
+    pub fn wasted(&self) -> u64 {
+        self.dups.iter().map(|d| d.wasted()).sum()
+    }
+
+    /// Writes a table to the supplied `writer` with the duplicated symbols,
+    /// the libraries containing them, and the libraries whose copy has been
+    /// linked. `max_count` can be used to limit the number of symbols printed.
     pub fn print(
         &self,
-        mem_type: MemoryRegion,
+        max_count: Option<usize>,
         human_readable: bool,
         writer: &mut impl Write,
     ) -> Result<usize, Error> {
         let mut table = Table::new();
 
-        for x in self.iter_region(mem_type) {
-            let lang_string = x.0.to_string();
-            let size_string = if human_readable {
-                x.1.to_string_as(true)
+        let file_names = |paths: Vec<&PathBuf>| {
+            paths
+                .iter()
+                .map(|p| p.file_name().unwrap_or_default().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        for d in self.dups.iter().take(max_count.unwrap_or(usize::MAX)) {
+            let wasted_string = if human_readable {
+                ByteSize::b(d.wasted()).to_string_as(true)
             } else {
-                x.1.as_u64().to_string()
+                d.wasted().to_string()
             };
-            let _ = table.add_row(row!(lang_string, size_string, format!("{:.1}", x.2)));
+            let mut kept = file_names(d.kept.iter().collect());
+            if d.ambiguous {
+                kept.push_str(" (ambiguous)");
+            }
+            let _ = table.add_row(row!(
+                d.demangled,
+                file_names(d.libs.iter().map(|(path, _)| path).collect()),
+                kept,
+                d.linked.len(),
+                wasted_string
+            ));
         }
 
-        table.set_titles(row![mem_type.to_string(), "Size [Bytes]", "%age"]);
+        table.set_titles(row![
+            "Name",
+            "Libraries",
+            "Kept",
+            "Linked",
+            "Wasted [Bytes]"
+        ]);
         table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
 
-        // `?` uses `From<std::io::error> for Error` to convert the Error variant. This unpacks the
-        // Ok variant with then has to be repackaged.
         Ok(table.print(writer)?)
     }
 
-    /// Creates an iterator which returns a tuple for every language containing
-    /// its size in bytes and the percentage relative to the sum of all
-    /// languages. The items returned by the iterator are already sorted
-    /// according to the size with the largest being the first. Use the
-    /// `.rev()` method on the iterator if you want it to start with the largest
-    /// one.
-    pub fn iter_region(
-        &self,
-        mem_region: MemoryRegion,
-    ) -> std::vec::IntoIter<(SymbolLang, ByteSize, f64)> {
-        // NOTE:
-        // In order to be able to sort something, you HAVE to have all the data.
-        // Therefore, putting everything in a Vec and then sorting it in place is
-        // is probably not the stupidest thing to do. However, I'm not sure if
-        // it is a good idea to then turn this vector into a consuming iterator.
-        let mut data = vec![
-            (
-                SymbolLang::C,
-                self.size(SymbolLang::C, mem_region),
-                self.size_pct(SymbolLang::C, mem_region),
-            ),
-            (
-                SymbolLang::Cpp,
-                self.size(SymbolLang::Cpp, mem_region),
-                self.size_pct(SymbolLang::Cpp, mem_region),
-            ),
-            (
-                SymbolLang::Rust,
-                self.size(SymbolLang::Rust, mem_region),
-                self.size_pct(SymbolLang::Rust, mem_region),
-            ),
-        ];
-
-        // Sort by size in reverse order (largest to smallest)
-        data.sort_by(|a, b| b.1.cmp(&a.1));
-        data.into_iter()
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the duplicated symbols.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().runtime(self).print(writer)
     }
 }
 
@@ -226,13 +1967,13 @@ where
             strings.push(s.lang.to_string());
             strings.push(s.demangled.clone());
             let size_string = if human_readable {
-                ByteSize::b(s.size as u64).to_string_as(true)
+                ByteSize::b(s.size).to_string_as(true)
             } else {
                 s.size.to_string()
             };
             strings.push(size_string);
             strings.push(s.sym_type.to_string());
-            strings.push(s.sym_type.mem_region().to_string());
+            strings.push(s.mem_region.to_string());
 
             // Get the widths of the strings in the current row.
             // Cell::get_width() exists but will be set to private on the next
@@ -290,6 +2031,12 @@ where
         // Ok variant with then has to be repackaged.
         Ok(table.print(writer)?)
     }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing all the symbols of the report.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().syms(self).print(writer)
+    }
 }
 
 impl<'a, I> IntoIterator for &SymbolReport<'a, I>
@@ -323,3 +2070,976 @@ where
         self.iter.next()
     }
 }
+
+/// A list of the ways a symbol can change between two builds.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SymbolChange {
+    /// The symbol only exists in the new build.
+    Added,
+    /// The symbol only exists in the old build.
+    Removed,
+    /// The symbol exists in both builds but its size changed.
+    Resized,
+}
+
+impl Display for SymbolChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// The size of a symbol in an old and a new build.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SymbolDiff {
+    /// Normalized name of the symbol (see [`demangle::normalize`]).
+    pub name: String,
+    /// Demangled name of the symbol in the newer build it exists in.
+    pub demangled: String,
+    pub lang: SymbolLang,
+    pub mem_region: MemoryRegion,
+    /// Size in bytes in the old build. `0` if the symbol was added.
+    pub old_size: u64,
+    /// Size in bytes in the new build. `0` if the symbol was removed.
+    pub new_size: u64,
+}
+
+impl SymbolDiff {
+    /// Returns how the symbol changed between the builds.
+    pub fn change(&self) -> SymbolChange {
+        if self.old_size == 0 {
+            SymbolChange::Added
+        } else if self.new_size == 0 {
+            SymbolChange::Removed
+        } else {
+            SymbolChange::Resized
+        }
+    }
+
+    /// Returns the growth of the symbol in bytes. Negative values mean that
+    /// the symbol shrank (or was removed).
+    pub fn delta(&self) -> i64 {
+        self.new_size as i64 - self.old_size as i64
+    }
+}
+
+/// Struct used for reporting the differences in memory usage between an old
+/// and a new build of an application.
+///
+/// Symbols are matched by their normalized name and memory region. Symbols
+/// sharing the same normalized name (e.g. instances of a generic Rust function
+/// which only differ in their hash) are combined and compared as a whole.
+#[derive(Debug, Clone)]
+pub struct DiffReport {
+    old: LangReport,
+    new: LangReport,
+    /// Changed symbols sorted by their absolute growth (largest first). Growth
+    /// is listed before an equally large reduction.
+    syms: Vec<SymbolDiff>,
+}
+
+impl DiffReport {
+    /// Creates a new [`DiffReport`] from the language reports and symbols of
+    /// the old and the new build.
+    /// This type is intended to be created by the [`crate::Atlas::report_diff`]
+    /// method.
+    pub(crate) fn new(
+        old: LangReport,
+        new: LangReport,
+        old_syms: &[Symbol],
+        new_syms: &[Symbol],
+    ) -> Self {
+        let mut diffs: HashMap<(String, MemoryRegion), SymbolDiff> = HashMap::new();
+
+        for (s, is_new) in old_syms
+            .iter()
+            .map(|s| (s, false))
+            .chain(new_syms.iter().map(|s| (s, true)))
+        {
+            let name = demangle::normalize(&s.mangled);
+            let diff = diffs
+                .entry((name.clone(), s.mem_region))
+                .or_insert_with(|| SymbolDiff {
+                    name,
+                    demangled: s.demangled.clone(),
+                    lang: s.lang,
+                    mem_region: s.mem_region,
+                    old_size: 0,
+                    new_size: 0,
+                });
+            if is_new {
+                diff.demangled = s.demangled.clone();
+                diff.lang = s.lang;
+                diff.new_size += s.size;
+            } else {
+                diff.old_size += s.size;
+            }
+        }
+
+        let mut syms = diffs
+            .into_values()
+            .filter(|d| d.delta() != 0)
+            .collect::<Vec<_>>();
+        syms.sort_by(|a, b| {
+            b.delta()
+                .abs()
+                .cmp(&a.delta().abs())
+                .then_with(|| b.delta().cmp(&a.delta()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        DiffReport { old, new, syms }
+    }
+
+    /// Returns the language report of the old build.
+    pub fn old_report(&self) -> &LangReport {
+        &self.old
+    }
+
+    /// Returns the language report of the new build.
+    pub fn new_report(&self) -> &LangReport {
+        &self.new
+    }
+
+    /// Get the growth in bytes of the specified language and memory region
+    /// from the old to the new build. Negative values mean that the memory
+    /// usage decreased. [`SymbolLang::Any`] returns the net total of all
+    /// languages.
+    pub fn size_delta(&self, lang: SymbolLang, mem_region: MemoryRegion) -> i64 {
+        self.new.size(lang, mem_region).as_u64() as i64
+            - self.old.size(lang, mem_region).as_u64() as i64
+    }
+
+    /// Returns all symbols that were added, removed, or resized sorted by
+    /// their absolute growth with the largest change being the first.
+    pub fn syms(&self) -> &[SymbolDiff] {
+        &self.syms
+    }
+
+    /// Creates an iterator over the changed symbols of the selected languages
+    /// and memory region in the same order as [`syms`]. The arguments work
+    /// like the ones of [`crate::Atlas::report_syms`].
+    ///
+    /// [`syms`]: DiffReport::syms
+    pub fn iter_syms(
+        &self,
+        lang: Vec<SymbolLang>,
+        mem_region: MemoryRegion,
+        max_count: Option<usize>,
+    ) -> impl Iterator<Item = &SymbolDiff> + Clone {
+        self.syms
+            .iter()
+            .filter(move |s| (lang.contains(&SymbolLang::Any)) || (lang.contains(&s.lang)))
+            .filter(move |s| (mem_region == MemoryRegion::Both) || (s.mem_region == mem_region))
+            .take(max_count.unwrap_or(usize::MAX))
+    }
+
+    /// Creates an iterator which returns a tuple for every language containing
+    /// its size in the old and the new build as well as the growth in bytes.
+    /// The items are sorted by the absolute growth with the largest change
+    /// being the first.
+    pub fn iter_region(
+        &self,
+        mem_region: MemoryRegion,
+    ) -> std::vec::IntoIter<(SymbolLang, ByteSize, ByteSize, i64)> {
+        let mut data = [
+            SymbolLang::C,
+            SymbolLang::Cpp,
+            SymbolLang::Rust,
+            SymbolLang::Asm,
+        ]
+        .iter()
+        .map(|lang| {
+            (
+                *lang,
+                self.old.size(*lang, mem_region),
+                self.new.size(*lang, mem_region),
+                self.size_delta(*lang, mem_region),
+            )
+        })
+        .collect::<Vec<_>>();
+
+        data.sort_by_key(|d| std::cmp::Reverse(d.3.abs()));
+        data.into_iter()
+    }
+
+    /// Writes a table to the supplied `writer` with the memory usage of every
+    /// language in the old and the new build for the given memory region. The
+    /// last row contains the net total of all languages.
+    pub fn print_lang(
+        &self,
+        mem_region: MemoryRegion,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: ByteSize| {
+            if human_readable {
+                size.to_string_as(true)
+            } else {
+                size.as_u64().to_string()
+            }
+        };
+
+        for x in self.iter_region(mem_region) {
+            let _ = table.add_row(row!(
+                x.0.to_string(),
+                to_string(x.1),
+                to_string(x.2),
+                delta_string(x.3, human_readable)
+            ));
+        }
+        let _ = table.add_row(row!(
+            "Total",
+            to_string(self.old.size(SymbolLang::Any, mem_region)),
+            to_string(self.new.size(SymbolLang::Any, mem_region)),
+            delta_string(self.size_delta(SymbolLang::Any, mem_region), human_readable)
+        ));
+
+        table.set_titles(row![
+            mem_region.to_string(),
+            "Old [Bytes]",
+            "New [Bytes]",
+            "Delta [Bytes]"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a table to the supplied `writer` with the changed symbols of the
+    /// selected languages and memory region (see [`iter_syms`]). The table
+    /// contains the kind of change, language, old and new size, growth, and
+    /// name of every symbol.
+    ///
+    /// [`iter_syms`]: DiffReport::iter_syms
+    pub fn print_syms(
+        &self,
+        lang: Vec<SymbolLang>,
+        mem_region: MemoryRegion,
+        max_count: Option<usize>,
+        human_readable: bool,
+        writer: &mut impl Write,
+    ) -> Result<usize, Error> {
+        let mut table = Table::new();
+
+        let to_string = |size: u64| {
+            if human_readable {
+                ByteSize::b(size).to_string_as(true)
+            } else {
+                size.to_string()
+            }
+        };
+
+        for s in self.iter_syms(lang, mem_region, max_count) {
+            let _ = table.add_row(row!(
+                s.change().to_string(),
+                s.lang.to_string(),
+                to_string(s.old_size),
+                to_string(s.new_size),
+                delta_string(s.delta(), human_readable),
+                s.demangled
+            ));
+        }
+
+        table.set_titles(row![
+            "Change",
+            "Language",
+            "Old [Bytes]",
+            "New [Bytes]",
+            "Delta [Bytes]",
+            "Name"
+        ]);
+        table.set_format(*format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+
+        Ok(table.print(writer)?)
+    }
+
+    /// Writes a JSON document (see [`JsonReport`]) to the supplied `writer`
+    /// containing the size of every language in both builds as well as all
+    /// changed symbols.
+    pub fn print_json(&self, writer: &mut impl Write) -> Result<(), Error> {
+        JsonReport::new().diff(self).print(writer)
+    }
+}
+
+/// Formats a growth in bytes with an explicit sign.
+fn delta_string(delta: i64, human_readable: bool) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    if human_readable {
+        format!(
+            "{}{}",
+            sign,
+            ByteSize::b(delta.unsigned_abs()).to_string_as(true)
+        )
+    } else {
+        format!("{}{}", sign, delta.unsigned_abs())
+    }
+}
+
+/// Version of the JSON documents written by [`JsonReport`]. It is incremented
+/// whenever existing fields are changed or removed. Adding new fields doesn't
+/// change the version.
+pub const JSON_VERSION: u32 = 1;
+
+/// A versioned JSON document combining one or more reports. Reports that
+/// aren't added are omitted from the document.
+///
+/// # Example
+/// ```ignore
+/// JsonReport::new()
+///     .lang(&at.report_lang().unwrap())
+///     .fails(at.fails.as_ref().unwrap())
+///     .print(&mut std::io::stdout())?;
+/// ```
+/// results in
+/// ```text
+/// {
+///   "version": 1,
+///   "languages": [
+///     { "language": "C", "rom": 316, "ram": 125, "copied": 61 },
+///     ...
+///   ],
+///   "unattributed": { "rom": 0, "ram": 0, "copied": 0 },
+///   "failed": []
+/// }
+/// ```
+#[derive(Debug, Default, Serialize)]
+pub struct JsonReport<'a> {
+    version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    languages: Option<Vec<JsonLang>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unattributed: Option<JsonUnattributed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    crates: Option<Vec<JsonCrate<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    libraries: Option<Vec<JsonLib<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    objects: Option<Vec<JsonObject<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    regions: Option<Vec<JsonRegion<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sections: Option<Vec<JsonSection<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbols: Option<Vec<JsonSymbol<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tree: Option<Vec<JsonTreeNode<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<JsonFiles<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    generics: Option<Vec<JsonGeneric<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicates: Option<Vec<JsonDuplicate<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    padding: Option<JsonPadding<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    runtime: Option<Vec<JsonRuntimeDuplicate<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<JsonDiff<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    violations: Option<Vec<JsonViolation<'a>>>,
+    /// Lines of the nm output that couldn't be parsed into symbols.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed: Option<&'a [String]>,
+}
+
+impl<'a> JsonReport<'a> {
+    /// Creates an empty document with the current [`JSON_VERSION`].
+    pub fn new() -> Self {
+        JsonReport {
+            version: JSON_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the ROM, RAM, and copied size of every language and of the
+    /// unattributed bytes to the document.
+    pub fn lang(mut self, report: &LangReport) -> Self {
+        self.languages = Some(JsonLang::from_report(report));
+        self.unattributed = Some(JsonUnattributed {
+            rom: report.unattributed.rom.as_u64(),
+            ram: report.unattributed.ram.as_u64(),
+            copied: report.unattributed.copied.as_u64(),
+        });
+        self
+    }
+
+    /// Adds the ROM, RAM, and copied size of every Rust crate to the
+    /// document.
+    pub fn crates(mut self, report: &'a CrateReport) -> Self {
+        self.crates = Some(
+            report
+                .crates
+                .iter()
+                .map(|(name, mem)| JsonCrate {
+                    name,
+                    rom: mem.rom.as_u64(),
+                    ram: mem.ram.as_u64(),
+                    copied: mem.copied.as_u64(),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the ROM, RAM, and copied size as well as the symbol counts of
+    /// every library to the document.
+    pub fn libs(mut self, report: &'a LibReport) -> Self {
+        self.libraries = Some(
+            report
+                .libs
+                .iter()
+                .map(|lib| JsonLib {
+                    path: &lib.path,
+                    language: lib.lang.to_string(),
+                    rom: lib.mem.rom.as_u64(),
+                    ram: lib.mem.ram.as_u64(),
+                    copied: lib.mem.copied.as_u64(),
+                    linked: lib.linked,
+                    dropped: lib.dropped,
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the ROM, RAM, and copied size of every object file to the
+    /// document.
+    pub fn objects(mut self, report: &'a ObjectReport) -> Self {
+        self.objects = Some(
+            report
+                .objects
+                .iter()
+                .map(|o| JsonObject {
+                    object: &o.object,
+                    archive: o.archive.as_deref(),
+                    rom: o.mem.rom.as_u64(),
+                    ram: o.mem.ram.as_u64(),
+                    copied: o.mem.copied.as_u64(),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the usage of every memory region of the linker script to the
+    /// document.
+    pub fn regions(mut self, report: &'a RegionReport) -> Self {
+        self.regions = Some(
+            report
+                .regions
+                .iter()
+                .map(|r| JsonRegion {
+                    name: &r.region.name,
+                    origin: r.region.origin,
+                    length: r.region.length,
+                    used: r.used,
+                    unattributed: r.unattributed,
+                    free: r.free(),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the size and the bytes of every language of all sections to the
+    /// document.
+    pub fn sections(mut self, report: &'a SectionReport) -> Self {
+        self.sections = Some(
+            report
+                .sections
+                .iter()
+                .map(|s| JsonSection {
+                    name: &s.name,
+                    address: s.addr,
+                    size: s.size,
+                    memory_region: s.mem_region.to_string(),
+                    linker_region: s.linker_region.as_deref(),
+                    c: s.c,
+                    cpp: s.cpp,
+                    rust: s.rust,
+                    asm: s.asm,
+                    unattributed: s.unattributed,
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the symbols of the report to the document.
+    pub fn syms<I>(mut self, report: &SymbolReport<'a, I>) -> Self
+    where
+        I: Iterator<Item = &'a Symbol> + Clone,
+    {
+        self.symbols = Some(report.into_iter().map(JsonSymbol::from).collect());
+        self
+    }
+
+    /// Adds the nodes of the tree report down to `max_depth` to the document.
+    pub fn tree(mut self, report: &'a TreeReport, max_depth: usize) -> Self {
+        self.tree = Some(JsonTreeNode::from_nodes(&report.root.children, max_depth));
+        self
+    }
+
+    /// Adds the directories and source files of the file report down to
+    /// `max_depth` to the document.
+    pub fn files(mut self, report: &'a FileReport, max_depth: usize) -> Self {
+        self.files = Some(JsonFiles {
+            prefix: report.prefix.to_string_lossy(),
+            children: JsonFileNode::from_nodes(&report.root.children, max_depth),
+        });
+        self
+    }
+
+    /// Adds the generic functions of the report to the document.
+    pub fn generics(mut self, report: &'a GenericReport) -> Self {
+        self.generics = Some(
+            report
+                .generics
+                .iter()
+                .map(|g| JsonGeneric {
+                    language: g.lang.to_string(),
+                    name: &g.name,
+                    instances: g.instances,
+                    size: g.size,
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the padding of every section and the largest gaps (limited to
+    /// `max_count`) of the report to the document.
+    pub fn padding(mut self, report: &'a PaddingReport, max_count: Option<usize>) -> Self {
+        self.padding = Some(JsonPadding {
+            sections: report
+                .sections
+                .iter()
+                .map(|s| JsonSectionPadding {
+                    name: &s.name,
+                    c: s.c,
+                    cpp: s.cpp,
+                    rust: s.rust,
+                    asm: s.asm,
+                    unattributed: s.unattributed,
+                })
+                .collect(),
+            gaps: report
+                .gaps
+                .iter()
+                .take(max_count.unwrap_or(usize::MAX))
+                .map(|g| JsonPaddingGap {
+                    address: g.addr,
+                    size: g.size,
+                    address_alignment: g.addr_align,
+                    section: &g.section,
+                    language: g.lang.to_string(),
+                    before: &g.before,
+                    after: &g.after,
+                })
+                .collect(),
+        });
+        self
+    }
+
+    /// Adds the duplicated crates of the report to the document.
+    pub fn duplicates(mut self, report: &'a DuplicateReport) -> Self {
+        self.duplicates = Some(
+            report
+                .crates
+                .iter()
+                .map(|c| JsonDuplicate {
+                    name: &c.name,
+                    wasted: c.wasted(),
+                    versions: c
+                        .versions
+                        .iter()
+                        .map(|v| JsonCrateVersion {
+                            disambiguator: &v.disambiguator,
+                            count: v.count,
+                            size: v.size,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the symbols of the runtime report to the document.
+    pub fn runtime(mut self, report: &RuntimeReport<'a>) -> Self {
+        self.runtime = Some(
+            report
+                .dups
+                .iter()
+                .map(|d| JsonRuntimeDuplicate {
+                    name: &d.demangled,
+                    mangled: &d.mangled,
+                    libraries: d
+                        .libs
+                        .iter()
+                        .map(|(path, size)| JsonRuntimeCopy {
+                            path,
+                            size: *size,
+                            kept: d.kept.contains(path),
+                        })
+                        .collect(),
+                    linked: &d.linked,
+                    ambiguous: d.ambiguous,
+                    wasted: d.wasted(),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the language sizes of both builds and the changed symbols of the
+    /// diff report to the document.
+    pub fn diff(mut self, report: &'a DiffReport) -> Self {
+        self.diff = Some(JsonDiff {
+            old: JsonLang::from_report(&report.old),
+            new: JsonLang::from_report(&report.new),
+            symbols: report.syms.iter().map(JsonSymbolDiff::from).collect(),
+        });
+        self
+    }
+
+    /// Adds the exceeded limits of the budget check to the document.
+    pub fn check(mut self, report: &'a CheckReport) -> Self {
+        self.violations = Some(
+            report
+                .violations()
+                .iter()
+                .map(|v| JsonViolation {
+                    limit: v.limit.to_string(),
+                    symbol: v.symbol.as_deref(),
+                    size: v.size,
+                    max: v.max,
+                    over: v.over(),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Adds the lines that couldn't be parsed into symbols (see
+    /// [`crate::Atlas::fails`]) to the document.
+    pub fn fails(mut self, fails: &'a [String]) -> Self {
+        self.failed = Some(fails);
+        self
+    }
+
+    /// Writes the document as pretty-printed JSON to the supplied `writer`.
+    pub fn print(&self, writer: &mut impl Write) -> Result<(), Error> {
+        serde_json::to_writer_pretty(&mut *writer, self)
+            .map_err(|json_error| Error::new(ErrorKind::Io).with(json_error))?;
+        Ok(writeln!(writer)?)
+    }
+}
+
+/// Size of a language in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonLang {
+    language: String,
+    rom: u64,
+    ram: u64,
+    copied: u64,
+}
+
+impl JsonLang {
+    fn from_report(report: &LangReport) -> Vec<JsonLang> {
+        [
+            SymbolLang::C,
+            SymbolLang::Cpp,
+            SymbolLang::Rust,
+            SymbolLang::Asm,
+        ]
+        .iter()
+        .map(|lang| JsonLang {
+            language: lang.to_string(),
+            rom: report.size(*lang, MemoryRegion::Rom).as_u64(),
+            ram: report.size(*lang, MemoryRegion::Ram).as_u64(),
+            copied: report.size_copied(*lang).as_u64(),
+        })
+        .collect()
+    }
+}
+
+/// Size of the bytes that aren't covered by any symbol in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonUnattributed {
+    rom: u64,
+    ram: u64,
+    copied: u64,
+}
+
+/// Size of a Rust crate in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonCrate<'a> {
+    name: &'a str,
+    rom: u64,
+    ram: u64,
+    copied: u64,
+}
+
+/// Size and symbol counts of a static library in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonLib<'a> {
+    path: &'a std::path::Path,
+    language: String,
+    rom: u64,
+    ram: u64,
+    copied: u64,
+    linked: Option<usize>,
+    dropped: Option<usize>,
+}
+
+/// Size of an object file in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonObject<'a> {
+    object: &'a str,
+    archive: Option<&'a std::path::Path>,
+    rom: u64,
+    ram: u64,
+    copied: u64,
+}
+
+/// Usage of a memory region of the linker script in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonRegion<'a> {
+    name: &'a str,
+    origin: u64,
+    length: u64,
+    used: u64,
+    unattributed: u64,
+    free: u64,
+}
+
+/// An allocated section in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonSection<'a> {
+    name: &'a str,
+    address: u64,
+    size: u64,
+    memory_region: String,
+    linker_region: Option<&'a str>,
+    c: u64,
+    cpp: u64,
+    rust: u64,
+    asm: u64,
+    unattributed: u64,
+}
+
+/// A symbol in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonSymbol<'a> {
+    language: String,
+    name: &'a str,
+    mangled: &'a str,
+    address: u64,
+    size: u64,
+    symbol_type: String,
+    memory_region: String,
+    section: Option<&'a str>,
+    load_address: Option<u64>,
+    archive: Option<&'a std::path::Path>,
+    object: Option<&'a str>,
+    linker_region: Option<&'a str>,
+}
+
+impl<'a> From<&'a Symbol> for JsonSymbol<'a> {
+    fn from(s: &'a Symbol) -> Self {
+        JsonSymbol {
+            language: s.lang.to_string(),
+            name: &s.demangled,
+            mangled: &s.mangled,
+            address: s.addr,
+            size: s.size,
+            symbol_type: s.sym_type.to_string(),
+            memory_region: s.mem_region.to_string(),
+            section: s.section.as_deref(),
+            load_address: s.lma,
+            archive: s.archive.as_deref(),
+            object: s.object.as_deref(),
+            linker_region: s.linker_region.as_deref(),
+        }
+    }
+}
+
+/// A node of the tree report in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonTreeNode<'a> {
+    name: &'a str,
+    size: u64,
+    count: usize,
+    children: Vec<JsonTreeNode<'a>>,
+}
+
+impl<'a> JsonTreeNode<'a> {
+    fn from_nodes(nodes: &'a [TreeNode], max_depth: usize) -> Vec<Self> {
+        if max_depth == 0 {
+            return Vec::new();
+        }
+        nodes
+            .iter()
+            .map(|node| JsonTreeNode {
+                name: &node.name,
+                size: node.size,
+                count: node.count,
+                children: JsonTreeNode::from_nodes(&node.children, max_depth - 1),
+            })
+            .collect()
+    }
+}
+
+/// The file report in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonFiles<'a> {
+    prefix: Cow<'a, str>,
+    children: Vec<JsonFileNode<'a>>,
+}
+
+/// A directory or source file in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonFileNode<'a> {
+    name: &'a str,
+    languages: Vec<JsonLang>,
+    count: usize,
+    children: Vec<JsonFileNode<'a>>,
+}
+
+impl<'a> JsonFileNode<'a> {
+    fn from_nodes(nodes: &'a [FileNode], max_depth: usize) -> Vec<Self> {
+        if max_depth == 0 {
+            return Vec::new();
+        }
+        nodes
+            .iter()
+            .map(|node| JsonFileNode {
+                name: &node.name,
+                languages: JsonLang::from_report(&node.langs),
+                count: node.count,
+                children: JsonFileNode::from_nodes(&node.children, max_depth - 1),
+            })
+            .collect()
+    }
+}
+
+/// Padding report in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonPadding<'a> {
+    sections: Vec<JsonSectionPadding<'a>>,
+    gaps: Vec<JsonPaddingGap<'a>>,
+}
+
+/// Padding of a section per language in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonSectionPadding<'a> {
+    name: &'a str,
+    c: u64,
+    cpp: u64,
+    rust: u64,
+    asm: u64,
+    unattributed: u64,
+}
+
+/// A gap between two symbols in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonPaddingGap<'a> {
+    address: u64,
+    size: u64,
+    address_alignment: u64,
+    section: &'a str,
+    language: String,
+    before: &'a str,
+    after: &'a str,
+}
+
+/// A generic function in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonGeneric<'a> {
+    language: String,
+    name: &'a str,
+    instances: usize,
+    size: u64,
+}
+
+/// A duplicated crate in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonDuplicate<'a> {
+    name: &'a str,
+    wasted: u64,
+    versions: Vec<JsonCrateVersion<'a>>,
+}
+
+/// A version of a duplicated crate in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonCrateVersion<'a> {
+    disambiguator: &'a str,
+    count: usize,
+    size: u64,
+}
+
+/// A symbol contained in multiple Rust libraries in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonRuntimeDuplicate<'a> {
+    name: &'a str,
+    mangled: &'a str,
+    libraries: Vec<JsonRuntimeCopy<'a>>,
+    linked: &'a [u64],
+    ambiguous: bool,
+    wasted: u64,
+}
+
+/// A copy of a symbol contained in a Rust library in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonRuntimeCopy<'a> {
+    path: &'a std::path::Path,
+    size: u64,
+    kept: bool,
+}
+
+/// Differences between two builds in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonDiff<'a> {
+    old: Vec<JsonLang>,
+    new: Vec<JsonLang>,
+    symbols: Vec<JsonSymbolDiff<'a>>,
+}
+
+/// A changed symbol in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonSymbolDiff<'a> {
+    change: String,
+    language: String,
+    name: &'a str,
+    memory_region: String,
+    old_size: u64,
+    new_size: u64,
+    delta: i64,
+}
+
+impl<'a> From<&'a SymbolDiff> for JsonSymbolDiff<'a> {
+    fn from(s: &'a SymbolDiff) -> Self {
+        JsonSymbolDiff {
+            change: s.change().to_string(),
+            language: s.lang.to_string(),
+            name: &s.demangled,
+            memory_region: s.mem_region.to_string(),
+            old_size: s.old_size,
+            new_size: s.new_size,
+            delta: s.delta(),
+        }
+    }
+}
+
+/// An exceeded limit of the budget in the JSON document.
+#[derive(Debug, Serialize)]
+struct JsonViolation<'a> {
+    limit: String,
+    symbol: Option<&'a str>,
+    size: u64,
+    max: u64,
+    over: u64,
+}
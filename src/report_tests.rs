@@ -7,15 +7,25 @@ mod totalmem_tests {
         let m = CombinedMem::new(123, 456);
         assert_eq!(m.rom.as_u64(), 123);
         assert_eq!(m.ram.as_u64(), 456);
+        assert_eq!(m.copied.as_u64(), 0);
+    }
+
+    #[test]
+    fn with_copied() {
+        let m = CombinedMem::with_copied(123, 456, 78);
+        assert_eq!(m.rom.as_u64(), 123);
+        assert_eq!(m.ram.as_u64(), 456);
+        assert_eq!(m.copied.as_u64(), 78);
     }
 
     #[test]
     fn add() {
-        let first = CombinedMem::new(110, 33);
+        let first = CombinedMem::with_copied(110, 33, 20);
         let second = CombinedMem::new(450, 99);
         let sum = first + second;
         assert_eq!(sum.rom.as_u64(), 560);
         assert_eq!(sum.ram.as_u64(), 132);
+        assert_eq!(sum.copied.as_u64(), 20);
     }
 }
 
@@ -27,9 +37,10 @@ mod langreport_tests {
 
     lazy_static! {
         static ref TEST_REPORT: LangReport = LangReport::new(
-            CombinedMem::new(40, 10),
+            CombinedMem::with_copied(40, 10, 4),
             CombinedMem::new(25, 15),
-            CombinedMem::new(35, 75),
+            CombinedMem::with_copied(35, 75, 12),
+            CombinedMem::default(),
         );
     }
 
@@ -39,19 +50,28 @@ mod langreport_tests {
             CombinedMem {
                 rom: ByteSize::b(1),
                 ram: ByteSize::b(2),
+                copied: ByteSize::b(0),
             },
             CombinedMem {
                 rom: ByteSize::b(3),
                 ram: ByteSize::b(4),
+                copied: ByteSize::b(0),
             },
             CombinedMem {
                 rom: ByteSize::b(5),
                 ram: ByteSize::b(6),
+                copied: ByteSize::b(0),
+            },
+            CombinedMem {
+                rom: ByteSize::b(7),
+                ram: ByteSize::b(8),
+                copied: ByteSize::b(0),
             },
         );
         assert_eq!(r.c.rom.as_u64(), 1);
         assert_eq!(r.cpp.rom.as_u64(), 3);
         assert_eq!(r.rust.ram.as_u64(), 6);
+        assert_eq!(r.asm.ram.as_u64(), 8);
     }
 
     #[test]
@@ -73,6 +93,15 @@ mod langreport_tests {
         assert_eq!(r.size(SymbolLang::Rust, MemoryRegion::Ram).as_u64(), 75);
     }
 
+    #[test]
+    fn size_copied() {
+        let r = *TEST_REPORT;
+        assert_eq!(r.size_copied(SymbolLang::Any).as_u64(), 16);
+        assert_eq!(r.size_copied(SymbolLang::C).as_u64(), 4);
+        assert_eq!(r.size_copied(SymbolLang::Cpp).as_u64(), 0);
+        assert_eq!(r.size_copied(SymbolLang::Rust).as_u64(), 12);
+    }
+
     #[test]
     fn size_pct() {
         let r = *TEST_REPORT;
@@ -156,7 +185,8 @@ mod langreport_tests {
 
         r.print(MemoryRegion::Both, false, &mut result).unwrap();
 
-        let re = Regex::new(r"\s*(\w+)\s*\|\s*(\S.*\S)\s*\|\s*([\d.]+)\s*").unwrap();
+        let re =
+            Regex::new(r"\s*(\w+)\s*\|\s*([^|]*\S)\s*\|\s*([\d.]+)\s*\|\s*([^|]*\S)\s*").unwrap();
         let mut data_iter = r.iter_region(MemoryRegion::Both);
 
         for line in std::str::from_utf8(&result).unwrap().lines() {
@@ -168,6 +198,7 @@ mod langreport_tests {
             assert_eq!(caps[1].parse::<SymbolLang>().unwrap(), lang);
             assert_eq!(caps[2].parse::<ByteSize>().unwrap(), size);
             assert!((caps[3].parse::<f64>().unwrap() - pct).abs() < 1e-1);
+            assert_eq!(caps[4].parse::<ByteSize>().unwrap(), r.size_copied(lang));
         }
         assert_eq!(data_iter.next(), None);
     }
@@ -179,7 +210,8 @@ mod langreport_tests {
 
         r.print(MemoryRegion::Both, true, &mut result).unwrap();
 
-        let re = Regex::new(r"\s*(\w+)\s*\|\s*(\S.*\S)\s*\|\s*([\d.]+)\s*").unwrap();
+        let re =
+            Regex::new(r"\s*(\w+)\s*\|\s*([^|]*\S)\s*\|\s*([\d.]+)\s*\|\s*([^|]*\S)\s*").unwrap();
         let mut data_iter = r.iter_region(MemoryRegion::Both);
 
         for line in std::str::from_utf8(&result).unwrap().lines() {
@@ -191,6 +223,7 @@ mod langreport_tests {
             assert_eq!(caps[1].parse::<SymbolLang>().unwrap(), lang);
             assert_eq!(caps[2].parse::<ByteSize>().unwrap(), size);
             assert!((caps[3].parse::<f64>().unwrap() - pct).abs() < 1e-1);
+            assert_eq!(caps[4].parse::<ByteSize>().unwrap(), r.size_copied(lang));
         }
         assert_eq!(data_iter.next(), None);
     }
@@ -202,7 +235,8 @@ mod langreport_tests {
 
         r.print(MemoryRegion::Rom, true, &mut result).unwrap();
 
-        let re = Regex::new(r"\s*(\w+)\s*\|\s*(\S.*\S)\s*\|\s*([\d.]+)\s*").unwrap();
+        let re =
+            Regex::new(r"\s*(\w+)\s*\|\s*([^|]*\S)\s*\|\s*([\d.]+)\s*\|\s*([^|]*\S)\s*").unwrap();
         let mut data_iter = r.iter_region(MemoryRegion::Rom);
 
         for line in std::str::from_utf8(&result).unwrap().lines() {
@@ -214,6 +248,7 @@ mod langreport_tests {
             assert_eq!(caps[1].parse::<SymbolLang>().unwrap(), lang);
             assert_eq!(caps[2].parse::<ByteSize>().unwrap(), size);
             assert!((caps[3].parse::<f64>().unwrap() - pct).abs() < 1e-1);
+            assert_eq!(caps[4].parse::<ByteSize>().unwrap(), r.size_copied(lang));
         }
         assert_eq!(data_iter.next(), None);
     }
@@ -225,7 +260,8 @@ mod langreport_tests {
 
         r.print(MemoryRegion::Ram, true, &mut result).unwrap();
 
-        let re = Regex::new(r"\s*(\w+)\s*\|\s*(\S.*\S)\s*\|\s*([\d.]+)\s*").unwrap();
+        let re =
+            Regex::new(r"\s*(\w+)\s*\|\s*([^|]*\S)\s*\|\s*([\d.]+)\s*\|\s*([^|]*\S)\s*").unwrap();
         let mut data_iter = r.iter_region(MemoryRegion::Ram);
 
         for line in std::str::from_utf8(&result).unwrap().lines() {
@@ -237,9 +273,929 @@ mod langreport_tests {
             assert_eq!(caps[1].parse::<SymbolLang>().unwrap(), lang);
             assert_eq!(caps[2].parse::<ByteSize>().unwrap(), size);
             assert!((caps[3].parse::<f64>().unwrap() - pct).abs() < 1e-1);
+            assert_eq!(caps[4].parse::<ByteSize>().unwrap(), r.size_copied(lang));
         }
         assert_eq!(data_iter.next(), None);
     }
+
+    #[test]
+    fn unattributed() {
+        let r = TEST_REPORT.with_unattributed(CombinedMem::with_copied(100, 0, 8));
+        assert_eq!(r.size_unattributed(MemoryRegion::Rom).as_u64(), 100);
+        assert_eq!(r.size_unattributed(MemoryRegion::Ram).as_u64(), 0);
+        // The unattributed bytes aren't part of any language but count
+        // towards the percentages.
+        assert_eq!(r.size(SymbolLang::Any, MemoryRegion::Rom).as_u64(), 100);
+        assert!((r.size_pct(SymbolLang::C, MemoryRegion::Rom) - 20_f64).abs() < 1e-8);
+        assert!((r.size_pct(SymbolLang::Any, MemoryRegion::Rom) - 50_f64).abs() < 1e-8);
+        assert!((r.size_unattributed_pct(MemoryRegion::Rom) - 50_f64).abs() < 1e-8);
+        assert!((r.size_pct(SymbolLang::C, MemoryRegion::Ram) - 10_f64).abs() < 1e-8);
+
+        let mut result = Vec::new();
+        r.print(MemoryRegion::Rom, false, &mut result).unwrap();
+        let s = String::from_utf8(result).unwrap();
+        let last = s.lines().last().unwrap();
+        assert!(last.starts_with(" Unattributed | 100 "));
+        assert!(last.contains("| 50.0 "));
+        assert!(last.ends_with("| 8 "));
+
+        // The row is omitted if all bytes are covered by symbols.
+        let mut result = Vec::new();
+        r.print(MemoryRegion::Ram, false, &mut result).unwrap();
+        assert!(!String::from_utf8(result).unwrap().contains("Unattributed"));
+    }
+}
+
+#[cfg(test)]
+mod cratereport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> CrateReport {
+        CrateReport::new(vec![
+            (String::from("core"), CombinedMem::new(60, 0)),
+            (String::from("heapless"), CombinedMem::new(10, 20)),
+            (
+                String::from("my_crate"),
+                CombinedMem::with_copied(30, 40, 8),
+            ),
+            (String::from("alloc"), CombinedMem::new(0, 0)),
+        ])
+    }
+
+    #[test]
+    fn size() {
+        let r = create_test_report();
+        assert_eq!(r.size("core", MemoryRegion::Rom).as_u64(), 60);
+        assert_eq!(r.size("my_crate", MemoryRegion::Ram).as_u64(), 40);
+        assert_eq!(r.size("heapless", MemoryRegion::Both).as_u64(), 30);
+        assert_eq!(r.size("std", MemoryRegion::Rom).as_u64(), 0);
+    }
+
+    #[test]
+    fn size_pct() {
+        let r = create_test_report();
+        assert_eq!(r.size_pct("core", MemoryRegion::Rom), 60.0);
+        assert_eq!(r.size_pct("heapless", MemoryRegion::Ram), 100.0 / 3.0);
+        assert_eq!(r.size_pct("my_crate", MemoryRegion::Both), 43.75);
+    }
+
+    #[test]
+    fn iter_region() {
+        let r = create_test_report();
+        let names = r
+            .iter_region(MemoryRegion::Rom)
+            .map(|(name, _, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["core", "my_crate", "heapless"]);
+
+        let (name, size, pct) = r.iter_region(MemoryRegion::Ram).next().unwrap();
+        assert_eq!(name, "my_crate");
+        assert_eq!(size.as_u64(), 40);
+        assert_eq!(pct, r.size_pct("my_crate", MemoryRegion::Ram));
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(MemoryRegion::Rom, Some(2), false, &mut writer)
+            .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Crate (Rom) "));
+        assert!(lines[2].starts_with(" core "));
+        assert!(lines[2].contains("| 60 "));
+        assert!(lines[3].starts_with(" my_crate "));
+    }
+}
+
+#[cfg(test)]
+mod libreport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> LibReport {
+        LibReport::new(vec![
+            LibUsage {
+                path: PathBuf::from("/libs/libc_lib.a"),
+                lang: SymbolLang::C,
+                mem: CombinedMem::new(100, 20),
+                linked: Some(3),
+                dropped: Some(7),
+            },
+            LibUsage {
+                path: PathBuf::from("/libs/librust_lib.a"),
+                lang: SymbolLang::Rust,
+                mem: CombinedMem::new(400, 0),
+                linked: None,
+                dropped: None,
+            },
+        ])
+    }
+
+    #[test]
+    fn libs() {
+        let r = create_test_report();
+        assert_eq!(r.libs().len(), 2);
+        assert_eq!(r.libs()[0].path, PathBuf::from("/libs/librust_lib.a"));
+        assert_eq!(r.libs()[0].size(MemoryRegion::Rom).as_u64(), 400);
+        assert_eq!(r.libs()[1].size(MemoryRegion::Both).as_u64(), 120);
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Library "));
+        assert!(lines[2].starts_with(" librust_lib.a | Rust     | 400 "));
+        assert!(lines[3].starts_with(" libc_lib.a    | C        | 100 "));
+        assert!(lines[3].trim_end().ends_with("| 3      | 7"));
+    }
+}
+
+#[cfg(test)]
+mod objectreport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> ObjectReport {
+        ObjectReport::new(vec![
+            ObjectSize {
+                archive: Some(PathBuf::from("/libs/libc_lib.a")),
+                object: String::from("c_lib.o"),
+                mem: CombinedMem::new(100, 20),
+            },
+            ObjectSize {
+                archive: None,
+                object: String::from("app.o"),
+                mem: CombinedMem::new(300, 0),
+            },
+            ObjectSize {
+                archive: Some(PathBuf::from("/libs/libc_lib.a")),
+                object: String::from("bss.o"),
+                mem: CombinedMem::new(0, 40),
+            },
+        ])
+    }
+
+    #[test]
+    fn iter_region() {
+        let r = create_test_report();
+        let rom = r.iter_region(MemoryRegion::Rom).collect::<Vec<_>>();
+        assert_eq!(rom.len(), 2);
+        assert_eq!(rom[0].0.object, "app.o");
+        assert_eq!(rom[0].1.as_u64(), 300);
+        assert_eq!(rom[0].2, 75.0);
+        assert_eq!(rom[1].0.object, "c_lib.o");
+
+        let ram = r.iter_region(MemoryRegion::Ram).collect::<Vec<_>>();
+        assert_eq!(ram[0].0.object, "bss.o");
+        assert_eq!(ram[1].0.object, "c_lib.o");
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(MemoryRegion::Rom, Some(1), false, &mut writer)
+            .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with(" Object (Rom) | Library "));
+        assert!(lines[2].starts_with(" app.o "));
+
+        let mut writer = Vec::new();
+        r.print(MemoryRegion::Ram, None, false, &mut writer)
+            .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        assert!(s
+            .lines()
+            .nth(3)
+            .unwrap()
+            .starts_with(" c_lib.o      | libc_lib.a | 20 "));
+    }
+}
+
+#[cfg(test)]
+mod regionreport_tests {
+    use super::super::*;
+
+    fn usage(name: &str, origin: u64, length: u64, used: u64, unattributed: u64) -> RegionUsage {
+        RegionUsage {
+            region: LinkerRegion {
+                name: String::from(name),
+                attributes: String::new(),
+                origin,
+                length,
+                memory: None,
+            },
+            used,
+            unattributed,
+        }
+    }
+
+    fn create_test_report() -> RegionReport {
+        RegionReport::new(vec![
+            usage("FLASH", 0x08000000, 1024, 256, 0),
+            usage("CCM", 0x10000000, 512, 0, 0),
+            usage("SRAM1", 0x20000000, 256, 300, 0),
+            usage("BKPSRAM", 0x40024000, 256, 100, 28),
+        ])
+    }
+
+    #[test]
+    fn usage_free() {
+        let r = create_test_report();
+        let flash = r.region("FLASH").unwrap();
+        assert_eq!(flash.free(), 768);
+        assert_eq!(flash.used_pct(), 25.0);
+        // Overflowing region.
+        let sram1 = r.region("SRAM1").unwrap();
+        assert_eq!(sram1.free(), 0);
+        assert!(sram1.used_pct() > 100.0);
+        assert!(r.region("SRAM2").is_none());
+        // Unattributed bytes are occupied as well.
+        let bkpsram = r.region("BKPSRAM").unwrap();
+        assert_eq!(bkpsram.free(), 128);
+        assert_eq!(bkpsram.used_pct(), 50.0);
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with(" Region  | Origin "));
+        // Regions are printed in the order of the linker script.
+        assert!(lines[2].starts_with(" FLASH   | 0x08000000 | 1024 "));
+        assert!(lines[2].contains("| 256 "));
+        assert!(lines[2].contains("| 768 "));
+        assert!(lines[2].contains("| 25.0 "));
+        assert!(lines[3].starts_with(" CCM "));
+        assert!(lines[4].contains("| 117.2 "));
+        assert!(lines[5].contains("| 100 "));
+        assert!(lines[5].contains("| 28 "));
+        assert!(lines[5].contains("| 128 "));
+    }
+}
+
+#[cfg(test)]
+mod sectionreport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> SectionReport {
+        SectionReport::new(vec![
+            SectionSize {
+                name: String::from(".text"),
+                addr: 0x08000000,
+                size: 1024,
+                mem_region: MemoryRegion::Rom,
+                linker_region: Some(String::from("FLASH")),
+                c: 512,
+                cpp: 0,
+                rust: 256,
+                asm: 16,
+                unattributed: 240,
+            },
+            SectionSize {
+                name: String::from(".bss"),
+                addr: 0x20000000,
+                size: 128,
+                mem_region: MemoryRegion::Ram,
+                linker_region: None,
+                c: 64,
+                cpp: 32,
+                rust: 0,
+                asm: 0,
+                unattributed: 32,
+            },
+        ])
+    }
+
+    #[test]
+    fn lang_size() {
+        let r = create_test_report();
+        let text = r.section(".text").unwrap();
+        assert_eq!(text.lang_size(SymbolLang::C), 512);
+        assert_eq!(text.lang_size(SymbolLang::Rust), 256);
+        assert_eq!(text.lang_size(SymbolLang::Any), 512 + 256 + 16);
+        assert!(r.section(".data").is_none());
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Section | Address "));
+        // Sections are printed in the order of the section header table.
+        assert!(lines[2].starts_with(" .text   | 0x08000000 | Rom    | FLASH  | 1024 "));
+        assert!(lines[2].contains("| 512 "));
+        assert!(lines[2].ends_with("| 240 "));
+        assert!(lines[3].starts_with(" .bss    | 0x20000000 | Ram    | -      | 128 "));
+    }
+}
+
+#[cfg(test)]
+mod treereport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> TreeReport {
+        let syms = [
+            "00001000 00000020 T ot::Mle::Start()",
+            "00001020 00000010 T ot::Mle::Stop()",
+            "00001030 00000008 T ot::Coap::Send()",
+            "00001038 00000004 T main",
+        ]
+        .iter()
+        .map(|line| Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::Cpp).unwrap())
+        .collect::<Vec<_>>();
+        TreeReport::new(syms.iter())
+    }
+
+    #[test]
+    fn root() {
+        let r = create_test_report();
+        assert_eq!(r.root().size, 0x3c);
+        assert_eq!(r.root().count, 4);
+        let names = r
+            .root()
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["ot", "main"]);
+    }
+
+    #[test]
+    fn node() {
+        let r = create_test_report();
+        let mle = r.node(&["ot", "Mle"]).unwrap();
+        assert_eq!(mle.size, 0x30);
+        assert_eq!(mle.count, 2);
+        assert_eq!(mle.children[0].name, "Start()");
+        assert_eq!(r.node(&["ot", "Coap", "Send()"]).unwrap().size, 8);
+        assert!(r.node(&["ot", "Radio"]).is_none());
+        assert_eq!(r.node(&[]), Some(r.root()));
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(2, None, false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with(" Name "));
+        assert!(lines[2].starts_with(" ot "));
+        assert!(lines[2].contains("| 56 "));
+        assert!(lines[3].starts_with("   Mle "));
+        assert!(lines[4].starts_with("   Coap "));
+        assert!(lines[5].starts_with(" main "));
+    }
+
+    #[test]
+    fn print_max_count() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(3, Some(1), false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[4].starts_with("     Start() "));
+    }
+}
+
+#[cfg(test)]
+mod filereport_tests {
+    use super::super::*;
+
+    fn langs(c: u64, rust: u64, ram: u64) -> LangReport {
+        LangReport::new(
+            CombinedMem::new(c, ram),
+            CombinedMem::default(),
+            CombinedMem::new(rust, 0),
+            CombinedMem::default(),
+        )
+    }
+
+    fn create_test_report() -> FileReport {
+        FileReport::new(vec![
+            (
+                Some(String::from("/ws/zephyr/kernel/sched.c")),
+                langs(100, 0, 8),
+                3,
+            ),
+            (
+                Some(String::from("/ws/zephyr/kernel/timer.c")),
+                langs(50, 0, 0),
+                2,
+            ),
+            (
+                Some(String::from("/ws/zephyr/subsys/net/ip.c")),
+                langs(80, 0, 16),
+                4,
+            ),
+            (
+                Some(String::from("/ws/app/src/lib.rs")),
+                langs(0, 200, 0),
+                5,
+            ),
+            (None, langs(10, 0, 4), 1),
+        ])
+    }
+
+    #[test]
+    fn prefix() {
+        let r = create_test_report();
+        assert_eq!(r.prefix(), Path::new("/ws"));
+        let names = r
+            .root()
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["zephyr", "app", UNKNOWN_FILE]);
+    }
+
+    #[test]
+    fn prefix_single_file() {
+        let r = FileReport::new(vec![(Some(String::from("src/main.c")), langs(4, 0, 0), 1)]);
+        assert_eq!(r.prefix(), Path::new("src"));
+        assert_eq!(r.root().children[0].name, "main.c");
+    }
+
+    #[test]
+    fn node() {
+        let r = create_test_report();
+        assert_eq!(r.root().count, 15);
+        assert_eq!(r.root().size(MemoryRegion::Rom).as_u64(), 440);
+
+        let zephyr = r.node("zephyr").unwrap();
+        assert_eq!(zephyr.size(MemoryRegion::Rom).as_u64(), 230);
+        assert_eq!(zephyr.size(MemoryRegion::Ram).as_u64(), 24);
+        assert_eq!(zephyr.count, 9);
+
+        let kernel = r.node("zephyr/kernel").unwrap();
+        assert_eq!(
+            kernel.langs.size(SymbolLang::C, MemoryRegion::Rom).as_u64(),
+            150
+        );
+        assert_eq!(kernel.children[0].name, "sched.c");
+
+        let app = r.node("app/src").unwrap();
+        assert_eq!(
+            app.langs.size(SymbolLang::Rust, MemoryRegion::Rom).as_u64(),
+            200
+        );
+        assert_eq!(app.langs.size(SymbolLang::C, MemoryRegion::Rom).as_u64(), 0);
+
+        assert!(r.node("zephyr/drivers").is_none());
+        assert_eq!(r.node(""), Some(r.root()));
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(MemoryRegion::Rom, 2, None, false, &mut writer)
+            .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with(" Path (Rom) "));
+        assert!(lines[2].starts_with(" zephyr "));
+        assert!(lines[2].contains("| 230 "));
+        assert!(lines[3].starts_with("   kernel "));
+        assert!(lines[4].starts_with("   subsys "));
+        assert!(lines[5].starts_with(" app "));
+        assert!(lines[5].contains("| 200 "));
+        assert!(lines[7].starts_with(" [Unknown] "));
+    }
+
+    #[test]
+    fn print_region() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(MemoryRegion::Ram, 1, Some(1), false, &mut writer)
+            .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        // app doesn't use any RAM and only the largest entry is printed.
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].starts_with(" zephyr "));
+        assert!(lines[2].contains("| 24 "));
+    }
+}
+
+#[cfg(test)]
+mod genericreport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> GenericReport {
+        let syms = [
+            (
+                "00001000 00000010 W ot::Array<int, 4>::Push(int)",
+                SymbolLang::Cpp,
+            ),
+            (
+                "00001010 00000018 W ot::Array<long, 8>::Push(long)",
+                SymbolLang::Cpp,
+            ),
+            (
+                "00001028 00000008 W ot::Array<int, 4>::Array()",
+                SymbolLang::Cpp,
+            ),
+            (
+                "00001028 00000008 W ot::Array<int, 4>::Array()",
+                SymbolLang::Cpp,
+            ),
+            (
+                "00001030 00000004 W ot::Array<long, 8>::Array()",
+                SymbolLang::Cpp,
+            ),
+            (
+                "00001034 00000020 W ot::Max<int>(int, int)",
+                SymbolLang::Cpp,
+            ),
+            ("00001054 00000030 t app::largest", SymbolLang::Rust),
+            ("00001084 00000010 t app::largest", SymbolLang::Rust),
+            ("00001094 00000040 T app::main", SymbolLang::Rust),
+            ("000010d4 00000040 T ot::Instance::Get()", SymbolLang::Cpp),
+        ]
+        .iter()
+        .map(|(line, lang)| Symbol::from_rawsymbols_lang(*line, *line, *lang).unwrap())
+        .collect::<Vec<_>>();
+        GenericReport::new(syms.iter())
+    }
+
+    #[test]
+    fn generics() {
+        let r = create_test_report();
+        assert_eq!(
+            r.generics(),
+            &[
+                GenericFn {
+                    name: String::from("app::largest"),
+                    lang: SymbolLang::Rust,
+                    instances: 2,
+                    size: 0x40,
+                },
+                GenericFn {
+                    name: String::from("ot::Array::Push"),
+                    lang: SymbolLang::Cpp,
+                    instances: 2,
+                    size: 0x28,
+                },
+                GenericFn {
+                    name: String::from("ot::Array::Array"),
+                    lang: SymbolLang::Cpp,
+                    instances: 2,
+                    size: 0x0c,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(Some(2), false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Language | Name "));
+        assert!(lines[2].starts_with(" Rust     | app::largest "));
+        assert!(lines[2].contains("| 2 "));
+        assert!(lines[2].contains("| 64 "));
+        assert!(lines[3].starts_with(" Cpp      | ot::Array::Push "));
+    }
+}
+
+#[cfg(test)]
+mod duplicatereport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> DuplicateReport {
+        let syms = [
+            "00001000 00000010 T _RNvCse29rZBteZmL_7ringbuf8checksum",
+            "00001010 00000020 T _RNvMCse29rZBteZmL_7ringbufNtB2_7RingBuf4push",
+            "00001030 00000018 T _RNvCsg9FFvDQaDFm_7ringbuf8checksum",
+            "00001048 00000040 T _RNvCs1234_4core3fmt",
+            "00001088 00000040 T rust_checksum",
+        ]
+        .iter()
+        .map(|line| Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::Rust).unwrap())
+        .collect::<Vec<_>>();
+        DuplicateReport::new(syms.iter())
+    }
+
+    #[test]
+    fn crates() {
+        let r = create_test_report();
+        assert_eq!(
+            r.crates(),
+            &[DuplicateCrate {
+                name: String::from("ringbuf"),
+                versions: vec![
+                    CrateVersion {
+                        disambiguator: String::from("a3789542a9d61af9"),
+                        count: 2,
+                        size: 0x30,
+                    },
+                    CrateVersion {
+                        disambiguator: String::from("bc2dcf194c61c5f2"),
+                        count: 1,
+                        size: 0x18,
+                    },
+                ],
+            }]
+        );
+        assert_eq!(r.crates()[0].wasted(), 0x18);
+        assert_eq!(r.wasted(), 0x18);
+        assert_eq!(r.unchecked(), 0);
+    }
+
+    #[test]
+    fn unchecked() {
+        let syms = [
+            "00001000 00000010 T _ZN7ringbuf8checksum17h78e340d3d2b7b109E",
+            "00001010 00000010 T _ZN7ringbuf8checksum17h1111111111111111E",
+            "00001020 00000040 T rust_checksum",
+        ]
+        .iter()
+        .map(|line| Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::Rust).unwrap())
+        .collect::<Vec<_>>();
+        let r = DuplicateReport::new(syms.iter());
+        assert!(r.crates().is_empty());
+        assert_eq!(r.unchecked(), 2);
+    }
+
+    #[test]
+    fn print() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print(false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Crate "));
+        assert!(lines[2].starts_with(" ringbuf | a3789542a9d61af9 | 2 "));
+        assert!(lines[2].trim_end().ends_with("| 24"));
+        assert!(lines[3].starts_with("         | bc2dcf194c61c5f2 | 1 "));
+    }
+}
+
+#[cfg(test)]
+mod paddingreport_tests {
+    use super::super::*;
+
+    fn create_test_report() -> PaddingReport {
+        let syms = [
+            ("20000000 00000004 b a", SymbolLang::C, Some(".bss")),
+            // 28 bytes in front of a 32-byte aligned stack.
+            ("20000020 00000040 b stack", SymbolLang::Rust, Some(".bss")),
+            ("20000060 00000008 b c", SymbolLang::C, Some(".bss")),
+            // Alias of `c`.
+            ("20000060 00000004 b c_alias", SymbolLang::C, Some(".bss")),
+            ("20000070 00000004 b d", SymbolLang::C, Some(".bss")),
+            // The gap is larger than the alignment.
+            ("20000090 00000004 b e", SymbolLang::C, Some(".bss")),
+            ("20001000 00000002 d f", SymbolLang::C, Some(".data")),
+            ("20001004 00000004 d g", SymbolLang::Cpp, Some(".data")),
+            ("20001010 00000004 d h", SymbolLang::Cpp, None),
+        ]
+        .iter()
+        .map(|(line, lang, section)| {
+            let mut s = Symbol::from_rawsymbols_lang(*line, *line, *lang).unwrap();
+            s.section = section.map(String::from);
+            s
+        })
+        .collect::<Vec<_>>();
+        PaddingReport::new(syms.iter())
+    }
+
+    #[test]
+    fn gaps() {
+        let r = create_test_report();
+        let gaps = r
+            .gaps()
+            .iter()
+            .map(|g| {
+                (
+                    g.addr,
+                    g.size,
+                    g.addr_align,
+                    g.section.as_str(),
+                    g.lang,
+                    g.before.as_str(),
+                    g.after.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            gaps,
+            vec![
+                (0x20000004, 28, 32, ".bss", SymbolLang::Rust, "a", "stack"),
+                (0x20000068, 8, 16, ".bss", SymbolLang::C, "c", "d"),
+                (0x20001002, 2, 4, ".data", SymbolLang::Cpp, "f", "g"),
+            ]
+        );
+    }
+
+    #[test]
+    fn sections() {
+        let r = create_test_report();
+        let names = r
+            .sections()
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![".bss", ".data"]);
+        let bss = r.section(".bss").unwrap();
+        assert_eq!(bss.lang_size(SymbolLang::C), 8);
+        assert_eq!(bss.lang_size(SymbolLang::Rust), 28);
+        assert_eq!(bss.lang_size(SymbolLang::Any), 36);
+        assert!(r.section(".text").is_none());
+        assert_eq!(r.size(SymbolLang::Cpp), 2);
+        assert_eq!(r.size(SymbolLang::Any), 38);
+    }
+
+    #[test]
+    fn with_fill() {
+        let syms = [
+            ("08000000 00000022 T a", SymbolLang::C),
+            ("08000030 00000010 T b", SymbolLang::Rust),
+        ]
+        .iter()
+        .map(|(line, lang)| {
+            let mut s = Symbol::from_rawsymbols_lang(*line, *line, *lang).unwrap();
+            s.section = Some(String::from(".text"));
+            s
+        })
+        .collect::<Vec<_>>();
+        let fill = ["08000022 0000000e T *fill*", "08000040 00000008 T *fill*"]
+            .iter()
+            .map(|line| {
+                let mut s = Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::Any).unwrap();
+                s.section = Some(String::from(".text"));
+                s
+            })
+            .collect::<Vec<_>>();
+        let r = PaddingReport::with_fill(syms.iter(), fill.iter());
+
+        // The fill in front of `b` is reported although it is larger than the
+        // address alignment of `b`.
+        assert_eq!(
+            r.gaps()[0],
+            PaddingGap {
+                addr: 0x08000022,
+                size: 0xe,
+                addr_align: 16,
+                section: String::from(".text"),
+                lang: SymbolLang::Rust,
+                before: String::from("a"),
+                after: String::from("b"),
+            }
+        );
+        // The fill at the end of the section isn't followed by any symbol.
+        assert_eq!(r.gaps()[1].lang, SymbolLang::Any);
+        assert_eq!(r.gaps()[1].before, "b");
+        assert_eq!(r.gaps()[1].after, "");
+
+        let text = r.section(".text").unwrap();
+        assert_eq!(text.rust, 0xe);
+        assert_eq!(text.unattributed, 0x8);
+        assert_eq!(
+            r.size(SymbolLang::Any),
+            r.gaps().iter().map(|g| g.size).sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let r = PaddingReport::new([].iter());
+        assert!(r.gaps().is_empty());
+        assert_eq!(r.size(SymbolLang::Any), 0);
+    }
+
+    #[test]
+    fn print_sections() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print_sections(false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with(" Section | C "));
+        assert!(lines[2].starts_with(" .bss    | 8 "));
+        assert!(lines[2].ends_with("| 36 "));
+        assert!(lines[4].starts_with(" Total   | 8 "));
+        assert!(lines[4].ends_with("| 38 "));
+    }
+
+    #[test]
+    fn print_gaps() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print_gaps(Some(2), false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Size [Bytes] | Addr Align "));
+        assert!(lines[2].starts_with(" 28           | 32         | 0x20000004 | .bss "));
+        assert!(lines[2].ends_with("| a      | stack "));
+        assert!(lines[3].starts_with(" 8 "));
+    }
+}
+
+#[cfg(test)]
+mod runtimereport_tests {
+    use super::super::*;
+    use std::path::PathBuf;
+
+    fn dup(name: &str, sizes: &[u64], linked: &[u64]) -> RuntimeDuplicate {
+        let libs = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, size)| (PathBuf::from(format!("/libs/librust_{}.a", i)), *size))
+            .collect::<Vec<_>>();
+        let kept = libs
+            .iter()
+            .filter(|(_, size)| linked.contains(size))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        RuntimeDuplicate {
+            mangled: String::from(name),
+            demangled: String::from(name),
+            ambiguous: kept.len() > linked.len(),
+            kept,
+            libs,
+            linked: linked.to_vec(),
+        }
+    }
+
+    fn create_test_dups() -> Vec<RuntimeDuplicate> {
+        vec![
+            dup("__divdc3", &[1024, 1024], &[]),
+            dup("core::panicking::panic", &[16, 24], &[24]),
+            dup("core::fmt::write", &[512, 512], &[512, 512]),
+        ]
+    }
+
+    #[test]
+    fn dups() {
+        let dups = create_test_dups();
+        let r = RuntimeReport::new(&dups);
+        let names = r
+            .dups()
+            .iter()
+            .map(|d| d.demangled.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["core::fmt::write", "core::panicking::panic", "__divdc3"]
+        );
+        assert_eq!(r.wasted(), 512);
+    }
+
+    #[test]
+    fn print() {
+        let dups = create_test_dups();
+        let r = RuntimeReport::new(&dups);
+        let mut writer = Vec::new();
+        r.print(Some(2), false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(" Name "));
+        assert!(lines[2].starts_with(" core::fmt::write "));
+        assert!(lines[2].contains("| librust_0.a, librust_1.a | librust_0.a, librust_1.a | 2 "));
+        assert!(lines[2].trim_end().ends_with("| 512"));
+        assert!(lines[3].contains("| librust_1.a "));
+    }
+
+    #[test]
+    fn print_ambiguous() {
+        let dups = vec![dup("core::panicking::panic", &[8, 8], &[8])];
+        let r = RuntimeReport::new(&dups);
+        let mut writer = Vec::new();
+        r.print(None, false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        assert!(s.contains("| librust_0.a, librust_1.a (ambiguous) | 1 "));
+    }
 }
 
 mod symbolreport_tests {
@@ -322,10 +1278,7 @@ mod symbolreport_tests {
             assert_eq!(caps[2], sym.demangled);
             assert_eq!(caps[3], sym.size.to_string());
             assert_eq!(caps[4].parse::<SymbolType>().unwrap(), sym.sym_type);
-            assert_eq!(
-                caps[5].parse::<MemoryRegion>().unwrap(),
-                sym.sym_type.mem_region()
-            );
+            assert_eq!(caps[5].parse::<MemoryRegion>().unwrap(), sym.mem_region);
         }
         assert_eq!(data_iter.next(), None);
     }
@@ -350,13 +1303,593 @@ mod symbolreport_tests {
             let sym = data_iter.next().unwrap();
             assert_eq!(caps[1].parse::<SymbolLang>().unwrap(), sym.lang);
             assert_eq!(caps[2], sym.demangled);
-            assert_eq!(caps[3], ByteSize::b(sym.size as u64).to_string_as(true));
+            assert_eq!(caps[3], ByteSize::b(sym.size).to_string_as(true));
             assert_eq!(caps[4].parse::<SymbolType>().unwrap(), sym.sym_type);
-            assert_eq!(
-                caps[5].parse::<MemoryRegion>().unwrap(),
-                sym.sym_type.mem_region()
-            );
+            assert_eq!(caps[5].parse::<MemoryRegion>().unwrap(), sym.mem_region);
         }
         assert_eq!(data_iter.next(), None);
     }
 }
+
+#[cfg(test)]
+mod diffreport_tests {
+    use super::super::*;
+
+    fn sym(line: &str, lang: SymbolLang) -> Symbol {
+        Symbol::from_rawsymbols_lang(line, line, lang).unwrap()
+    }
+
+    fn create_test_report() -> DiffReport {
+        let old = vec![
+            sym("00001000 00000010 T unchanged", SymbolLang::C),
+            sym("00001010 00000020 T resized", SymbolLang::C),
+            sym("00001030 00000008 T removed", SymbolLang::C),
+            sym(
+                "00001040 00000030 T _ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E",
+                SymbolLang::Rust,
+            ),
+            sym("20000000 00000004 b completed.8911", SymbolLang::C),
+        ];
+        let new = vec![
+            sym("00001000 00000010 T unchanged", SymbolLang::C),
+            sym("00001010 00000028 T resized", SymbolLang::C),
+            sym("00001038 00000100 T added", SymbolLang::Cpp),
+            // Only the hash of the Rust symbol changed.
+            sym(
+                "00001138 00000030 T _ZN4core3fmt9Formatter3pad17hde7d70127d765717E",
+                SymbolLang::Rust,
+            ),
+            sym("20000000 00000004 b completed.0", SymbolLang::C),
+        ];
+        DiffReport::new(
+            LangReport::new(
+                CombinedMem::new(0x10 + 0x20 + 0x08, 4),
+                CombinedMem::default(),
+                CombinedMem::new(0x30, 0),
+                CombinedMem::default(),
+            ),
+            LangReport::new(
+                CombinedMem::new(0x10 + 0x28, 4),
+                CombinedMem::new(0x100, 0),
+                CombinedMem::new(0x30, 0),
+                CombinedMem::default(),
+            ),
+            &old,
+            &new,
+        )
+    }
+
+    #[test]
+    fn syms() {
+        let r = create_test_report();
+        let syms = r.syms();
+        assert_eq!(syms.len(), 3);
+
+        assert_eq!(syms[0].name, "added");
+        assert_eq!(syms[0].lang, SymbolLang::Cpp);
+        assert_eq!(syms[0].change(), SymbolChange::Added);
+        assert_eq!(syms[0].delta(), 0x100);
+
+        assert_eq!(syms[1].name, "resized");
+        assert_eq!(syms[1].change(), SymbolChange::Resized);
+        assert_eq!(syms[1].old_size, 0x20);
+        assert_eq!(syms[1].new_size, 0x28);
+        assert_eq!(syms[1].delta(), 8);
+
+        assert_eq!(syms[2].name, "removed");
+        assert_eq!(syms[2].change(), SymbolChange::Removed);
+        assert_eq!(syms[2].delta(), -8);
+    }
+
+    #[test]
+    fn combine_same_name() {
+        let old = vec![
+            sym(
+                "00001000 00000010 T _ZN3foo3bar17h2e7465a2fecc1fa5E",
+                SymbolLang::Rust,
+            ),
+            sym(
+                "00001010 00000020 T _ZN3foo3bar17hde7d70127d765717E",
+                SymbolLang::Rust,
+            ),
+        ];
+        let new = vec![sym(
+            "00001000 00000010 T _ZN3foo3bar17h0123456789abcdefE",
+            SymbolLang::Rust,
+        )];
+        let r = DiffReport::new(LangReport::default(), LangReport::default(), &old, &new);
+        assert_eq!(r.syms().len(), 1);
+        assert_eq!(r.syms()[0].name, "foo::bar");
+        assert_eq!(r.syms()[0].old_size, 0x30);
+        assert_eq!(r.syms()[0].new_size, 0x10);
+    }
+
+    #[test]
+    fn iter_syms() {
+        let r = create_test_report();
+        let names = |iter: &mut dyn Iterator<Item = &SymbolDiff>| {
+            iter.map(|s| s.name.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&mut r.iter_syms(vec![SymbolLang::C], MemoryRegion::Rom, None)),
+            vec!["resized", "removed"]
+        );
+        assert_eq!(
+            names(&mut r.iter_syms(vec![SymbolLang::Any], MemoryRegion::Both, Some(1))),
+            vec!["added"]
+        );
+        assert!(r
+            .iter_syms(vec![SymbolLang::Any], MemoryRegion::Ram, None)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn size_delta() {
+        let r = create_test_report();
+        assert_eq!(r.size_delta(SymbolLang::Any, MemoryRegion::Rom), 0x100);
+        assert_eq!(r.size_delta(SymbolLang::C, MemoryRegion::Rom), 0);
+        assert_eq!(r.size_delta(SymbolLang::Cpp, MemoryRegion::Both), 0x100);
+        assert_eq!(r.size_delta(SymbolLang::Rust, MemoryRegion::Rom), 0);
+        assert_eq!(r.size_delta(SymbolLang::Any, MemoryRegion::Ram), 0);
+    }
+
+    #[test]
+    fn iter_region() {
+        let r = create_test_report();
+        let mut iter = r.iter_region(MemoryRegion::Rom);
+        let (lang, old, new, delta) = iter.next().unwrap();
+        assert_eq!(lang, SymbolLang::Cpp);
+        assert_eq!(old.as_u64(), 0);
+        assert_eq!(new.as_u64(), 0x100);
+        assert_eq!(delta, 0x100);
+        assert_eq!(iter.count(), 3);
+    }
+
+    #[test]
+    fn print_lang() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print_lang(MemoryRegion::Rom, false, &mut writer).unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[2].starts_with(" Cpp "));
+        assert!(lines[2].contains("| +256"));
+        assert!(lines[6].starts_with(" Total "));
+        assert!(lines[6].contains("| +256"));
+    }
+
+    #[test]
+    fn print_syms() {
+        let r = create_test_report();
+        let mut writer = Vec::new();
+        r.print_syms(
+            vec![SymbolLang::Any],
+            MemoryRegion::Rom,
+            None,
+            false,
+            &mut writer,
+        )
+        .unwrap();
+        let s = String::from_utf8(writer).unwrap();
+        let lines = s.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[2].starts_with(" Added "));
+        assert!(lines[4].starts_with(" Removed "));
+        assert!(lines[4].contains("| -8 "));
+    }
+}
+
+#[cfg(test)]
+mod jsonreport_tests {
+    use super::super::*;
+    use serde_json::{json, Value};
+
+    fn to_value(print: impl FnOnce(&mut Vec<u8>) -> Result<(), Error>) -> Value {
+        let mut writer = Vec::new();
+        print(&mut writer).unwrap();
+        serde_json::from_slice(&writer).unwrap()
+    }
+
+    #[test]
+    fn empty() {
+        let v = to_value(|w| JsonReport::new().print(w));
+        assert_eq!(v, json!({ "version": JSON_VERSION }));
+    }
+
+    #[test]
+    fn lang() {
+        let r = LangReport::new(
+            CombinedMem::with_copied(40, 10, 4),
+            CombinedMem::new(25, 15),
+            CombinedMem::default(),
+            CombinedMem::new(2, 0),
+        );
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(v["version"], 1);
+        assert_eq!(
+            v["languages"],
+            json!([
+                { "language": "C", "rom": 40, "ram": 10, "copied": 4 },
+                { "language": "Cpp", "rom": 25, "ram": 15, "copied": 0 },
+                { "language": "Rust", "rom": 0, "ram": 0, "copied": 0 },
+                { "language": "Asm", "rom": 2, "ram": 0, "copied": 0 },
+            ])
+        );
+        assert_eq!(
+            v["unattributed"],
+            json!({ "rom": 0, "ram": 0, "copied": 0 })
+        );
+        assert!(v.get("symbols").is_none());
+    }
+
+    #[test]
+    fn syms() {
+        let mut s = Symbol::from_rawsymbols_lang(
+            "0002eda6 000000a6 T _ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E",
+            "0002eda6 000000a6 T core::fmt::Formatter::pad",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        s.section = Some(String::from(".text"));
+        let data = [s];
+
+        let v = to_value(|w| SymbolReport::new(data.iter()).print_json(w));
+        assert_eq!(v["version"], 1);
+        assert_eq!(
+            v["symbols"],
+            json!([{
+                "language": "Rust",
+                "name": "core::fmt::Formatter::pad",
+                "mangled": "_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E",
+                "address": 0x0002eda6,
+                "size": 0xa6,
+                "symbol_type": "TextSection",
+                "memory_region": "Rom",
+                "section": ".text",
+                "load_address": null,
+                "archive": null,
+                "object": null,
+                "linker_region": null,
+            }])
+        );
+    }
+
+    #[test]
+    fn fails() {
+        let fails = [String::from("invalid line")];
+        let v = to_value(|w| {
+            JsonReport::new()
+                .lang(&LangReport::default())
+                .fails(&fails)
+                .print(w)
+        });
+        assert_eq!(v["failed"], json!(["invalid line"]));
+        assert_eq!(v["languages"].as_array().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn diff() {
+        let old = vec![Symbol::from_rawsymbols_lang(
+            "00001000 00000010 T removed",
+            "00001000 00000010 T removed",
+            SymbolLang::C,
+        )
+        .unwrap()];
+        let r = DiffReport::new(
+            LangReport::new(
+                CombinedMem::new(0x10, 0),
+                CombinedMem::default(),
+                CombinedMem::default(),
+                CombinedMem::default(),
+            ),
+            LangReport::default(),
+            &old,
+            &[],
+        );
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(v["diff"]["old"][0]["rom"], 0x10);
+        assert_eq!(v["diff"]["new"][0]["rom"], 0);
+        assert_eq!(
+            v["diff"]["symbols"],
+            json!([{
+                "change": "Removed",
+                "language": "C",
+                "name": "removed",
+                "memory_region": "Rom",
+                "old_size": 0x10,
+                "new_size": 0,
+                "delta": -0x10,
+            }])
+        );
+    }
+
+    #[test]
+    fn check() {
+        let budget = crate::Budget::parse("[total]\nram = 10").unwrap();
+        let r = budget.check(
+            &LangReport::new(
+                CombinedMem::new(0, 12),
+                CombinedMem::default(),
+                CombinedMem::default(),
+                CombinedMem::default(),
+            ),
+            &[],
+        );
+        let v = to_value(|w| JsonReport::new().check(&r).print(w));
+        assert_eq!(
+            v["violations"],
+            json!([{
+                "limit": "Total Ram",
+                "symbol": null,
+                "size": 12,
+                "max": 10,
+                "over": 2,
+            }])
+        );
+    }
+
+    #[test]
+    fn crates() {
+        let r = CrateReport::new(vec![(
+            String::from("core"),
+            CombinedMem::with_copied(6, 4, 2),
+        )]);
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["crates"],
+            json!([{ "name": "core", "rom": 6, "ram": 4, "copied": 2 }])
+        );
+    }
+
+    #[test]
+    fn libs() {
+        let r = LibReport::new(vec![LibUsage {
+            path: PathBuf::from("libs/libfoo.a"),
+            lang: SymbolLang::Cpp,
+            mem: CombinedMem::with_copied(6, 4, 2),
+            linked: Some(1),
+            dropped: None,
+        }]);
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["libraries"],
+            json!([{
+                "path": "libs/libfoo.a",
+                "language": "Cpp",
+                "rom": 6,
+                "ram": 4,
+                "copied": 2,
+                "linked": 1,
+                "dropped": null,
+            }])
+        );
+    }
+
+    #[test]
+    fn objects() {
+        let r = ObjectReport::new(vec![ObjectSize {
+            archive: Some(PathBuf::from("libs/libfoo.a")),
+            object: String::from("foo.o"),
+            mem: CombinedMem::with_copied(6, 4, 2),
+        }]);
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["objects"],
+            json!([{
+                "object": "foo.o",
+                "archive": "libs/libfoo.a",
+                "rom": 6,
+                "ram": 4,
+                "copied": 2,
+            }])
+        );
+    }
+
+    #[test]
+    fn regions() {
+        let r = RegionReport::new(vec![RegionUsage {
+            region: LinkerRegion {
+                name: String::from("RAM"),
+                attributes: String::from("rwx"),
+                origin: 0x20000000,
+                length: 4096,
+                memory: None,
+            },
+            used: 1024,
+            unattributed: 16,
+        }]);
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["regions"],
+            json!([{
+                "name": "RAM",
+                "origin": 0x20000000,
+                "length": 4096,
+                "used": 1024,
+                "unattributed": 16,
+                "free": 3056,
+            }])
+        );
+    }
+
+    #[test]
+    fn sections() {
+        let r = SectionReport::new(vec![SectionSize {
+            name: String::from(".data"),
+            addr: 0x20000000,
+            size: 64,
+            mem_region: MemoryRegion::Ram,
+            linker_region: Some(String::from("RAM")),
+            c: 16,
+            cpp: 0,
+            rust: 40,
+            asm: 0,
+            unattributed: 8,
+        }]);
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["sections"],
+            json!([{
+                "name": ".data",
+                "address": 0x20000000,
+                "size": 64,
+                "memory_region": "Ram",
+                "linker_region": "RAM",
+                "c": 16,
+                "cpp": 0,
+                "rust": 40,
+                "asm": 0,
+                "unattributed": 8,
+            }])
+        );
+    }
+
+    #[test]
+    fn tree() {
+        let line = "00001000 00000010 T core::fmt::write";
+        let syms = [Symbol::from_rawsymbols_lang(line, line, SymbolLang::Rust).unwrap()];
+        let r = TreeReport::new(syms.iter());
+        let v = to_value(|w| r.print_json(2, w));
+        assert_eq!(
+            v["tree"],
+            json!([{
+                "name": "core",
+                "size": 16,
+                "count": 1,
+                "children": [{ "name": "fmt", "size": 16, "count": 1, "children": [] }],
+            }])
+        );
+    }
+
+    #[test]
+    fn files() {
+        let r = FileReport::new(vec![(
+            Some(String::from("/ws/app/src/main.c")),
+            LangReport::new(
+                CombinedMem::new(16, 4),
+                CombinedMem::default(),
+                CombinedMem::default(),
+                CombinedMem::default(),
+            ),
+            2,
+        )]);
+        let v = to_value(|w| r.print_json(1, w));
+        assert_eq!(v["files"]["prefix"], "/ws/app/src");
+        assert_eq!(v["files"]["children"][0]["name"], "main.c");
+        assert_eq!(v["files"]["children"][0]["count"], 2);
+        assert_eq!(
+            v["files"]["children"][0]["languages"][0],
+            json!({ "language": "C", "rom": 16, "ram": 4, "copied": 0 })
+        );
+    }
+
+    #[test]
+    fn generics() {
+        let syms = [
+            "00001000 00000010 t app::largest",
+            "00001010 00000008 t app::largest",
+        ]
+        .iter()
+        .map(|line| Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::Rust).unwrap())
+        .collect::<Vec<_>>();
+        let r = GenericReport::new(syms.iter());
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["generics"],
+            json!([{ "language": "Rust", "name": "app::largest", "instances": 2, "size": 24 }])
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        let syms = [
+            "00001000 00000010 T _RNvCse29rZBteZmL_7ringbuf8checksum",
+            "00001010 00000018 T _RNvCsg9FFvDQaDFm_7ringbuf8checksum",
+        ]
+        .iter()
+        .map(|line| Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::Rust).unwrap())
+        .collect::<Vec<_>>();
+        let r = DuplicateReport::new(syms.iter());
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["duplicates"],
+            json!([{
+                "name": "ringbuf",
+                "wasted": 16,
+                "versions": [
+                    { "disambiguator": "bc2dcf194c61c5f2", "count": 1, "size": 24 },
+                    { "disambiguator": "a3789542a9d61af9", "count": 1, "size": 16 },
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn padding() {
+        let syms = ["00001000 00000006 T foo", "00001008 00000004 T bar"]
+            .iter()
+            .map(|line| {
+                let mut s = Symbol::from_rawsymbols_lang(*line, *line, SymbolLang::C).unwrap();
+                s.section = Some(String::from(".text"));
+                s
+            })
+            .collect::<Vec<_>>();
+        let r = PaddingReport::new(syms.iter());
+        let v = to_value(|w| r.print_json(None, w));
+        assert_eq!(
+            v["padding"],
+            json!({
+                "sections": [{
+                    "name": ".text",
+                    "c": 2,
+                    "cpp": 0,
+                    "rust": 0,
+                    "asm": 0,
+                    "unattributed": 0,
+                }],
+                "gaps": [{
+                    "address": 0x1006,
+                    "size": 2,
+                    "address_alignment": 8,
+                    "section": ".text",
+                    "language": "C",
+                    "before": "foo",
+                    "after": "bar",
+                }],
+            })
+        );
+        let v = to_value(|w| r.print_json(Some(0), w));
+        assert_eq!(v["padding"]["gaps"], json!([]));
+    }
+
+    #[test]
+    fn runtime() {
+        let libs = vec![(PathBuf::from("liba.a"), 16), (PathBuf::from("libb.a"), 24)];
+        let dups = [RuntimeDuplicate {
+            mangled: String::from("_ZN4core9panicking5panic17h0123456789abcdefE"),
+            demangled: String::from("core::panicking::panic"),
+            kept: vec![PathBuf::from("libb.a")],
+            libs,
+            linked: vec![24],
+            ambiguous: false,
+        }];
+        let r = RuntimeReport::new(&dups);
+        let v = to_value(|w| r.print_json(w));
+        assert_eq!(
+            v["runtime"],
+            json!([{
+                "name": "core::panicking::panic",
+                "mangled": "_ZN4core9panicking5panic17h0123456789abcdefE",
+                "libraries": [
+                    { "path": "liba.a", "size": 16, "kept": false },
+                    { "path": "libb.a", "size": 24, "kept": true },
+                ],
+                "linked": [24],
+                "ambiguous": false,
+                "wasted": 0,
+            }])
+        );
+    }
+}
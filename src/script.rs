@@ -0,0 +1,331 @@
+//! Parse the `MEMORY` command of GNU linker scripts (e.g. `linker.cmd` of
+//! Zephyr or `memory.x` of the cortex-m crates). It declares the name, start
+//! address, and length of every memory region (e.g. `FLASH`, `CCM`, `SRAM1`) of
+//! the chip. Only the `MEMORY` command is evaluated. Everything else contained
+//! in the linker script is ignored.
+//!
+//! ```text
+//! MEMORY
+//! {
+//!     FLASH (rx)  : ORIGIN = 0x08000000, LENGTH = 1M
+//!     CCM (rw)    : ORIGIN = 0x10000000, LENGTH = 64K
+//!     SRAM1 (rwx) : ORIGIN = 0x20000000, LENGTH = 112K
+//!     SRAM2 (rwx) : ORIGIN = ORIGIN(SRAM1) + LENGTH(SRAM1), LENGTH = 16K
+//! }
+//! ```
+
+use crate::error::{Error, ErrorKind};
+use crate::sym::MemoryRegion;
+use std::fs;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::CharIndices;
+
+#[cfg(test)]
+#[path = "./script_tests.rs"]
+mod script_tests;
+
+/// A memory region declared in the `MEMORY` command of a linker script.
+#[derive(PartialEq, Debug, Clone)]
+pub struct LinkerRegion {
+    /// Name of the region (e.g. `FLASH`).
+    pub name: String,
+    /// Attributes of the region without the parentheses (e.g. `rx`). Empty if
+    /// no attributes have been declared.
+    pub attributes: String,
+    /// Start address of the region.
+    pub origin: u64,
+    /// Size of the region in bytes.
+    pub length: u64,
+    /// Memory the symbols within the region are classified as regardless of
+    /// their section or symbol type (e.g. [`MemoryRegion::Ram`] for functions
+    /// placed in RAM). Only set for the regions of a region file (see
+    /// [`crate::regions`]).
+    pub memory: Option<MemoryRegion>,
+}
+
+impl LinkerRegion {
+    /// Checks if the given address lies within the region.
+    pub fn contains(&self, addr: u64) -> bool {
+        (addr >= self.origin) && (addr - self.origin < self.length)
+    }
+}
+
+/// The memory regions of a parsed linker script.
+#[derive(Debug)]
+pub struct LinkerScript {
+    regions: Vec<LinkerRegion>,
+}
+
+impl LinkerScript {
+    /// Reads and parses the linker script at the given path. Returns an
+    /// [`ErrorKind::Io`] error if the file couldn't be read. See
+    /// [`LinkerScript::parse`] for the other errors.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let data = fs::read(path.as_ref())?;
+        let s = std::str::from_utf8(&data)
+            .map_err(|str_error| Error::new(ErrorKind::InvalidLinkerScript).with(str_error))?;
+        LinkerScript::parse(s)
+    }
+
+    /// Parses the `MEMORY` commands of a linker script. The addresses and
+    /// lengths may be expressions containing the `K` and `M` suffixes, the
+    /// arithmetic operators, and the `ORIGIN()` and `LENGTH()` builtins.
+    /// Returns an [`ErrorKind::InvalidLinkerScript`] error if the script
+    /// doesn't contain a `MEMORY` command or if it couldn't be parsed.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let tokens = tokenize(&strip_comments(s));
+
+        let mut regions = Vec::new();
+        let mut found = false;
+        let mut iter = tokens.iter().peekable();
+        while let Some(token) = iter.next() {
+            if *token != Token::Ident(String::from("MEMORY")) {
+                continue;
+            }
+            expect(&mut iter, &Token::Punct('{'))?;
+            found = true;
+            while iter.peek() != Some(&&Token::Punct('}')) {
+                let region = parse_region(&mut iter, &regions)?;
+                regions.push(region);
+            }
+            expect(&mut iter, &Token::Punct('}'))?;
+        }
+
+        if !found {
+            return Err(Error::new(ErrorKind::InvalidLinkerScript).with("no MEMORY command"));
+        }
+
+        Ok(LinkerScript { regions })
+    }
+
+    /// Returns all memory regions in the order they are declared.
+    pub fn regions(&self) -> &[LinkerRegion] {
+        &self.regions
+    }
+
+    /// Returns the memory region containing the given address. If regions
+    /// overlap, the one declared first is returned.
+    pub fn region_at(&self, addr: u64) -> Option<&LinkerRegion> {
+        self.regions.iter().find(|r| r.contains(addr))
+    }
+}
+
+/// A token of the linker script.
+#[derive(PartialEq, Debug, Clone)]
+enum Token {
+    /// Names of regions, keywords, and attributes.
+    Ident(String),
+    Number(u64),
+    Punct(char),
+}
+
+type Tokens<'a> = Peekable<std::slice::Iter<'a, Token>>;
+
+/// Removes the C-style comments of the script. Line comments are removed as
+/// well as they are used by some scripts passed through the C preprocessor.
+fn strip_comments(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('/') {
+        out.push_str(&rest[..start]);
+        let comment = &rest[start..];
+        rest = if let Some(block) = comment.strip_prefix("/*") {
+            out.push(' ');
+            block.find("*/").map(|end| &block[end + 2..]).unwrap_or("")
+        } else if comment.starts_with("//") {
+            comment.find('\n').map(|end| &comment[end..]).unwrap_or("")
+        } else {
+            out.push('/');
+            &comment[1..]
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Splits the script into tokens. Numbers are converted including their
+/// `K`/`M` suffix (e.g. `64K`) and hexadecimal prefix (e.g. `0x8000`). Words
+/// starting with a digit that aren't valid numbers are kept as identifiers
+/// and only result in an error if they are part of the `MEMORY` command.
+fn tokenize(s: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices> = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let is_word = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '$');
+        if is_word(c) {
+            let mut end = start + c.len_utf8();
+            while let Some((idx, c)) = chars.peek().copied() {
+                if !is_word(c) {
+                    break;
+                }
+                end = idx + c.len_utf8();
+                let _ = chars.next();
+            }
+            let word = &s[start..end];
+            match parse_number(word) {
+                Some(value) if c.is_ascii_digit() => tokens.push(Token::Number(value)),
+                _ => tokens.push(Token::Ident(String::from(word))),
+            }
+        } else {
+            tokens.push(Token::Punct(c));
+        }
+    }
+    tokens
+}
+
+/// Parses a number of the linker script (e.g. `0x20000000`, `256K`, `1M`).
+fn parse_number(word: &str) -> Option<u64> {
+    let (digits, multiplier) = match word.as_bytes().last() {
+        Some(b'K') | Some(b'k') => (&word[..word.len() - 1], 1024),
+        Some(b'M') | Some(b'm') => (&word[..word.len() - 1], 1024 * 1024),
+        _ => (word, 1),
+    };
+    let value = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => digits.parse::<u64>(),
+    }
+    .ok()?;
+    value.checked_mul(multiplier)
+}
+
+/// Consumes the next token and checks that it is the expected one.
+fn expect(iter: &mut Tokens, expected: &Token) -> Result<(), Error> {
+    match iter.next() {
+        Some(token) if token == expected => Ok(()),
+        token => Err(Error::new(ErrorKind::InvalidLinkerScript)
+            .with(format!("expected {:?} instead of {:?}", expected, token))),
+    }
+}
+
+/// Parses a single region declaration of the `MEMORY` command
+/// (`name [(attr)] : ORIGIN = origin, LENGTH = len`). The regions declared
+/// before are required for evaluating `ORIGIN()` and `LENGTH()`.
+fn parse_region(iter: &mut Tokens, regions: &[LinkerRegion]) -> Result<LinkerRegion, Error> {
+    let name = match iter.next() {
+        Some(Token::Ident(name)) => name.clone(),
+        token => {
+            return Err(Error::new(ErrorKind::InvalidLinkerScript)
+                .with(format!("expected region name instead of {:?}", token)))
+        }
+    };
+
+    let mut attributes = String::new();
+    if iter.peek() == Some(&&Token::Punct('(')) {
+        let _ = iter.next();
+        loop {
+            match iter.next() {
+                Some(Token::Punct(')')) => break,
+                Some(Token::Ident(s)) => attributes.push_str(s),
+                Some(Token::Punct(c)) => attributes.push(*c),
+                _ => return Err(Error::new(ErrorKind::InvalidLinkerScript)),
+            }
+        }
+    }
+    expect(iter, &Token::Punct(':'))?;
+
+    let mut origin = None;
+    let mut length = None;
+    for _ in 0..2 {
+        let value = match iter.next() {
+            Some(Token::Ident(key)) if matches!(key.as_str(), "ORIGIN" | "org" | "o") => {
+                &mut origin
+            }
+            Some(Token::Ident(key)) if matches!(key.as_str(), "LENGTH" | "len" | "l") => {
+                &mut length
+            }
+            token => {
+                return Err(Error::new(ErrorKind::InvalidLinkerScript)
+                    .with(format!("expected ORIGIN or LENGTH instead of {:?}", token)))
+            }
+        };
+        expect(iter, &Token::Punct('='))?;
+        *value = Some(parse_expr(iter, regions)?);
+        if iter.peek() == Some(&&Token::Punct(',')) {
+            let _ = iter.next();
+        }
+    }
+
+    match (origin, length) {
+        (Some(origin), Some(length)) => Ok(LinkerRegion {
+            name,
+            attributes,
+            origin,
+            length,
+            memory: None,
+        }),
+        _ => Err(Error::new(ErrorKind::InvalidLinkerScript)
+            .with(format!("region {} requires ORIGIN and LENGTH", name))),
+    }
+}
+
+/// Evaluates an expression consisting of sums and differences of terms.
+fn parse_expr(iter: &mut Tokens, regions: &[LinkerRegion]) -> Result<u64, Error> {
+    let mut value = parse_term(iter, regions)?;
+    while let Some(Token::Punct(op @ ('+' | '-'))) = iter.peek().copied() {
+        let _ = iter.next();
+        let rhs = parse_term(iter, regions)?;
+        value = match op {
+            '+' => value.wrapping_add(rhs),
+            _ => value.wrapping_sub(rhs),
+        };
+    }
+    Ok(value)
+}
+
+/// Evaluates a term consisting of products and quotients of factors.
+fn parse_term(iter: &mut Tokens, regions: &[LinkerRegion]) -> Result<u64, Error> {
+    let mut value = parse_factor(iter, regions)?;
+    while let Some(Token::Punct(op @ ('*' | '/'))) = iter.peek().copied() {
+        let _ = iter.next();
+        let rhs = parse_factor(iter, regions)?;
+        value = match op {
+            '*' => value.wrapping_mul(rhs),
+            _ => value
+                .checked_div(rhs)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidLinkerScript).with("division by 0"))?,
+        };
+    }
+    Ok(value)
+}
+
+/// Evaluates a number, a parenthesized expression, or one of the `ORIGIN()`
+/// and `LENGTH()` builtins referring to a previously declared region.
+fn parse_factor(iter: &mut Tokens, regions: &[LinkerRegion]) -> Result<u64, Error> {
+    match iter.next() {
+        Some(Token::Number(value)) => Ok(*value),
+        Some(Token::Punct('(')) => {
+            let value = parse_expr(iter, regions)?;
+            expect(iter, &Token::Punct(')'))?;
+            Ok(value)
+        }
+        Some(Token::Ident(builtin)) if matches!(builtin.as_str(), "ORIGIN" | "LENGTH") => {
+            expect(iter, &Token::Punct('('))?;
+            let region = match iter.next() {
+                Some(Token::Ident(name)) => regions.iter().find(|r| r.name == *name),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                Error::new(ErrorKind::InvalidLinkerScript)
+                    .with(format!("{}() of an unknown region", builtin))
+            })?;
+            expect(iter, &Token::Punct(')'))?;
+            Ok(if builtin == "ORIGIN" {
+                region.origin
+            } else {
+                region.length
+            })
+        }
+        token => Err(Error::new(ErrorKind::InvalidLinkerScript)
+            .with(format!("unexpected {:?} in expression", token))),
+    }
+}
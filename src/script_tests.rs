@@ -0,0 +1,140 @@
+mod linkerscript_tests {
+    use super::super::*;
+
+    #[test]
+    fn open() {
+        let script = LinkerScript::open("test_data/c_app_lma/link.ld").unwrap();
+        assert_eq!(
+            script.regions(),
+            &[
+                LinkerRegion {
+                    name: String::from("FLASH"),
+                    attributes: String::from("rx"),
+                    origin: 0x08000000,
+                    length: 256 * 1024,
+                    memory: None,
+                },
+                LinkerRegion {
+                    name: String::from("RAM"),
+                    attributes: String::from("rwx"),
+                    origin: 0x20000000,
+                    length: 64 * 1024,
+                    memory: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn open_not_found() {
+        let err = LinkerScript::open("kljsdflkjsdf").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io);
+    }
+
+    #[test]
+    fn expressions() {
+        // Excerpt of a preprocessed Zephyr linker.cmd and a memory.x.
+        let s = "
+            /* Memory layout */
+            MEMORY
+            {
+                FLASH (rx) : ORIGIN = (0x8000000 + 0x0), LENGTH = (1024*1K - 0x0)
+                CCM (rw) : org = 0x10000000, len = 64K // core coupled
+                SRAM1 (!rx) : o = 0x20000000, l = 112K
+                SRAM2 : ORIGIN = ORIGIN(SRAM1) + LENGTH(SRAM1), LENGTH = 0x4000
+                IDT_LIST (wx) : ORIGIN = 0xFFFF7FFF, LENGTH = 2M / 64
+            }
+            SECTIONS { .text : { *(.text*) } > FLASH }
+        ";
+        let script = LinkerScript::parse(s).unwrap();
+        let regions = script
+            .regions()
+            .iter()
+            .map(|r| (r.name.as_str(), r.attributes.as_str(), r.origin, r.length))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            regions,
+            vec![
+                ("FLASH", "rx", 0x8000000, 0x100000),
+                ("CCM", "rw", 0x10000000, 0x10000),
+                ("SRAM1", "!rx", 0x20000000, 0x1c000),
+                ("SRAM2", "", 0x2001c000, 0x4000),
+                ("IDT_LIST", "wx", 0xFFFF7FFF, 0x8000),
+            ]
+        );
+    }
+
+    #[test]
+    fn region_at() {
+        let script = LinkerScript::open("test_data/c_app_lma/link.ld").unwrap();
+        assert_eq!(script.region_at(0x08000000).unwrap().name, "FLASH");
+        assert_eq!(script.region_at(0x0803ffff).unwrap().name, "FLASH");
+        assert!(script.region_at(0x08040000).is_none());
+        assert_eq!(script.region_at(0x20000040).unwrap().name, "RAM");
+        assert!(script.region_at(0).is_none());
+    }
+
+    #[test]
+    fn no_memory() {
+        let err = LinkerScript::parse("SECTIONS { .text : { *(.text*) } }").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidLinkerScript);
+    }
+
+    #[test]
+    fn invalid() {
+        let parse = |s: &str| LinkerScript::parse(s).unwrap_err().kind();
+        // Unknown region.
+        assert_eq!(
+            parse("MEMORY { RAM : ORIGIN = ORIGIN(FLASH), LENGTH = 4K }"),
+            ErrorKind::InvalidLinkerScript
+        );
+        // Invalid number.
+        assert_eq!(
+            parse("MEMORY { RAM : ORIGIN = 0x2000000g, LENGTH = 4K }"),
+            ErrorKind::InvalidLinkerScript
+        );
+        // Missing length.
+        assert_eq!(
+            parse("MEMORY { RAM : ORIGIN = 0x20000000 }"),
+            ErrorKind::InvalidLinkerScript
+        );
+        // Unterminated command.
+        assert_eq!(
+            parse("MEMORY { RAM : ORIGIN = 0x20000000, LENGTH = 4K"),
+            ErrorKind::InvalidLinkerScript
+        );
+    }
+}
+
+mod strip_comments_tests {
+    use super::super::*;
+
+    #[test]
+    fn comments() {
+        assert_eq!(strip_comments("a /* b */c"), "a  c");
+        assert_eq!(strip_comments("a /* b\n c */ d // e\nf"), "a   d \nf");
+        assert_eq!(strip_comments("/DISCARD/ : { }"), "/DISCARD/ : { }");
+        assert_eq!(strip_comments("a /* b"), "a  ");
+    }
+}
+
+mod parse_number_tests {
+    use super::super::*;
+
+    #[test]
+    fn valid() {
+        assert_eq!(parse_number("42"), Some(42));
+        assert_eq!(parse_number("0x8000"), Some(0x8000));
+        assert_eq!(parse_number("0X10"), Some(0x10));
+        assert_eq!(parse_number("64K"), Some(64 * 1024));
+        assert_eq!(parse_number("1M"), Some(1024 * 1024));
+        assert_eq!(parse_number("0x10k"), Some(0x10 * 1024));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse_number("0x"), None);
+        assert_eq!(parse_number("12G"), None);
+        assert_eq!(parse_number("K"), None);
+    }
+}
@@ -1,12 +1,18 @@
 //! Handle symbols output by the [nm](https://sourceware.org/binutils/docs/binutils/nm.html)
 //! utility.
 
+use crate::demangle::{
+    crate_name, crate_version, demangle, instantiating_crate, scheme, ManglingScheme,
+};
+use crate::dwarf::{unit_crate, unit_crate_version};
+use crate::elf::SectionHeader;
 use crate::error::{Error, ErrorKind};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[cfg(test)]
@@ -15,7 +21,7 @@ mod sym_tests;
 
 /// A list of memory regions used to classify where the [`SymbolType`] is
 /// stored.
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum MemoryRegion {
     Unknown,
     /// Read-only memory (e.g., application code, ...)
@@ -57,7 +63,7 @@ impl TryFrom<&str> for MemoryRegion {
 }
 
 /// A list of languages for classifying the origin of a [`Symbol`].
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
 pub enum SymbolLang {
     /// Can be used as a parameter for methods for not having to specify any
     /// language.
@@ -65,6 +71,9 @@ pub enum SymbolLang {
     Rust,
     C,
     Cpp,
+    /// Assembly. This can only be detected with the DWARF debugging
+    /// information (see [`crate::dwarf`]).
+    Asm,
 }
 
 impl Display for SymbolLang {
@@ -83,6 +92,7 @@ impl FromStr for SymbolLang {
             "c" => Ok(SymbolLang::C),
             "cpp" => Ok(SymbolLang::Cpp),
             "rust" => Ok(SymbolLang::Rust),
+            "asm" => Ok(SymbolLang::Asm),
             _ => Err(Error::new(ErrorKind::InvalidEnumStr)),
         }
     }
@@ -248,29 +258,20 @@ impl TryFrom<&str> for SymbolType {
 }
 
 impl SymbolType {
-    /// Returns the [`MemoryRegion`] that the given symbol type is associated
-    /// to.
-    ///
-    /// # Panics
-    /// Currently panics on various symbol types that have not yet been
-    /// determined if they are stored in ROM or RAM. Panicking has been chosen
-    /// in order to make it more visible during the developement of this tool.
-    /// In the future, this should be refactored into returning a
-    /// `Result<Self, Error>`.
+    /// Returns the [`MemoryRegion`] that the given symbol type is usually
+    /// associated to. This is only a fallback for symbols whose section is not
+    /// known, as the symbol type alone is not sufficient for every symbol
+    /// (e.g. weak symbols can be located in ROM or RAM). Symbol types that
+    /// can't be classified return [`MemoryRegion::Unknown`].
     pub fn mem_region(&self) -> MemoryRegion {
         match *self {
-            Self::ReadOnlyDataSection | Self::TextSection | Self::Weak => MemoryRegion::Rom,
-            Self::BssSection | Self::DataSection => MemoryRegion::Ram,
-            // FIXME:
-            // Eventually, this should be replaced with by returning a result
-            // type. However, for the meantime, let this be a panic to determine
-            // during the development phase of this tool, if there are other
-            // symbols that could be present in an ELF file. (I assume that some
-            // symbol types should never make it to the finally linked ELF file.)
-            _ => panic!(
-                "The memory region for a symbol of type {:?} is unknown!",
-                self
-            ),
+            Self::ReadOnlyDataSection | Self::TextSection => MemoryRegion::Rom,
+            Self::BssSection
+            | Self::DataSection
+            | Self::Global
+            | Self::UninitializedOrZeroInitialized
+            | Self::Common => MemoryRegion::Ram,
+            _ => MemoryRegion::Unknown,
         }
     }
 }
@@ -279,8 +280,8 @@ impl SymbolType {
 /// utility. This can either be a demangled or a mangled one.
 #[derive(PartialEq, Debug)]
 pub struct RawSymbol {
-    addr: u32,
-    size: u32,
+    addr: u64,
+    size: u64,
     sym_type: SymbolType,
     name: String,
 }
@@ -298,7 +299,7 @@ impl Default for RawSymbol {
 
 impl RawSymbol {
     /// Creates a new [RawSymbol].
-    pub fn new(addr: u32, size: u32, sym_type: SymbolType, name: String) -> Self {
+    pub fn new(addr: u64, size: u64, sym_type: SymbolType, name: String) -> Self {
         RawSymbol {
             addr,
             size,
@@ -313,15 +314,19 @@ impl FromStr for RawSymbol {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex =
-                Regex::new(r"^\s*([0-9a-fA-F]{8})\s+([0-9a-fA-F]{8})\s+(\S)\s+(.*?)\s*$").unwrap();
+            // Addresses and sizes are printed with 8 hex digits for 32-bit ELF
+            // files and with 16 hex digits for 64-bit ones.
+            static ref RE: Regex = Regex::new(
+                r"^\s*([0-9a-fA-F]{8}|[0-9a-fA-F]{16})\s+([0-9a-fA-F]{8}|[0-9a-fA-F]{16})\s+(\S)\s+(.*?)\s*$"
+            )
+            .unwrap();
         }
 
         let caps = RE.captures(s).ok_or(Error::new(ErrorKind::InvalidSymbol))?;
 
-        let addr = u32::from_str_radix(caps.get(1).unwrap().as_str(), 16)
+        let addr = u64::from_str_radix(caps.get(1).unwrap().as_str(), 16)
             .map_err(|_e| Error::new(ErrorKind::InvalidSymbol))?;
-        let size = u32::from_str_radix(caps.get(2).unwrap().as_str(), 16)
+        let size = u64::from_str_radix(caps.get(2).unwrap().as_str(), 16)
             .map_err(|_e| Error::new(ErrorKind::InvalidSymbol))?;
         let sym_type = caps
             .get(3)
@@ -345,21 +350,55 @@ impl TryFrom<&str> for RawSymbol {
 
 /// Symbol created by combining the mangled and demangled information from the
 /// nm utility.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct Symbol {
-    pub addr: u32,
-    pub size: u32,
+    pub addr: u64,
+    pub size: u64,
     pub sym_type: SymbolType,
     pub mangled: String,
     pub demangled: String,
     pub lang: SymbolLang,
+    /// Name of the ELF section containing the symbol. `None` if the section is
+    /// not known (e.g. for symbols of static libraries).
+    pub section: Option<String>,
+    /// Memory region of the symbol. This is derived from the flags of its
+    /// section (see [`Symbol::with_section`]) and falls back to
+    /// [`SymbolType::mem_region`] as long as the section is not known.
+    pub mem_region: MemoryRegion,
+    /// Load address (LMA) of the initial value of the symbol if it differs
+    /// from its address. This is the case for initialized data which is
+    /// stored in ROM and copied to RAM at startup.
+    pub lma: Option<u64>,
+    /// Path to the static library the symbol was linked from as recorded in
+    /// the linker map file. `None` if not known or if the object file was
+    /// passed directly to the linker.
+    pub archive: Option<PathBuf>,
+    /// Name of the object file (or archive member) the symbol was linked from
+    /// as recorded in the linker map file or in the matching library (see
+    /// [`Symbol::lib`]).
+    pub object: Option<String>,
+    /// Path to the library (see [`crate::Atlas::add_lib`]) the symbol has been
+    /// matched to. `None` if it doesn't stem from any of the added libraries.
+    pub lib: Option<PathBuf>,
+    /// Name of the DWARF compilation unit containing the symbol. `None` if the
+    /// debugging information hasn't been used or doesn't cover the symbol.
+    pub unit: Option<String>,
+    /// Path to the source file declaring the symbol (see
+    /// [`crate::DwarfInfo::file_at`]). `None` if the debugging information
+    /// hasn't been used or doesn't cover the symbol.
+    pub file: Option<String>,
+    /// Name of the memory region of the linker script or region file
+    /// containing the symbol (see [`crate::Atlas::set_script`] and
+    /// [`crate::Atlas::set_region_file`]). `None` if neither has been used or
+    /// if the symbol isn't part of any region.
+    pub linker_region: Option<String>,
 }
 
 impl Symbol {
     /// Creates a new [`Symbol`].
     pub fn new(
-        addr: u32,
-        size: u32,
+        addr: u64,
+        size: u64,
         sym_type: SymbolType,
         mangled: String,
         demangled: String,
@@ -372,6 +411,15 @@ impl Symbol {
             mangled,
             demangled,
             lang,
+            section: None,
+            mem_region: sym_type.mem_region(),
+            lma: None,
+            archive: None,
+            object: None,
+            lib: None,
+            unit: None,
+            file: None,
+            linker_region: None,
         }
     }
 
@@ -431,14 +479,49 @@ impl Symbol {
             return Err(Error::new(ErrorKind::InvalidSymbol));
         }
 
-        Ok(Symbol {
-            addr: mangled.addr,
-            size: mangled.size,
-            sym_type: mangled.sym_type,
-            mangled: mangled.name,
-            demangled: demangled.name,
-            lang: SymbolLang::Any,
-        })
+        Ok(Symbol::new(
+            mangled.addr,
+            mangled.size,
+            mangled.sym_type,
+            mangled.name,
+            demangled.name,
+            SymbolLang::Any,
+        ))
+    }
+
+    /// Creates a [`Symbol`] from a single mangled [`RawSymbol`]. The demangled
+    /// name is derived from the mangled one in-process (see
+    /// [`crate::demangle::demangle`]). Like
+    /// [`from_rawsymbols`], the `lang` member is set to [`SymbolLang::Any`].
+    ///
+    /// [`from_rawsymbols`]: Symbol::from_rawsymbols
+    pub fn from_rawsymbol(mangled: RawSymbol) -> Self {
+        let demangled = demangle(&mangled.name);
+        Symbol::new(
+            mangled.addr,
+            mangled.size,
+            mangled.sym_type,
+            mangled.name,
+            demangled,
+            SymbolLang::Any,
+        )
+    }
+
+    /// Assigns the ELF section containing the symbol and derives the memory
+    /// region from its flags (see [`SectionHeader::mem_region`]). Symbols
+    /// without a section are classified as [`MemoryRegion::Unknown`].
+    pub fn with_section(mut self, section: Option<&SectionHeader>) -> Self {
+        match section {
+            Some(section) => {
+                self.section = Some(section.name.clone());
+                self.mem_region = section.mem_region();
+            }
+            None => {
+                self.section = None;
+                self.mem_region = MemoryRegion::Unknown;
+            }
+        }
+        self
     }
 
     /// Same as [`from_rawsymbols`] but allows the `lang` field of the struct to be
@@ -459,6 +542,83 @@ impl Symbol {
         Ok(s)
     }
 
+    /// Checks if the symbol is located in RAM but its initial value is copied
+    /// from ROM at startup. Such symbols occupy both memory regions.
+    pub fn copied_from_rom(&self) -> bool {
+        (self.mem_region == MemoryRegion::Ram) && self.lma.is_some()
+    }
+
+    /// Checks if the symbol belongs to Rust code. This is the case if its
+    /// language is Rust or if its name is mangled according to one of the Rust
+    /// schemes (see [`scheme`]). The latter identifies the Rust symbols of an
+    /// application analyzed without the Rust libraries or DWARF.
+    pub fn is_rust(&self) -> bool {
+        (self.lang == SymbolLang::Rust)
+            || matches!(
+                scheme(&self.mangled),
+                ManglingScheme::RustLegacy | ManglingScheme::RustV0
+            )
+    }
+
+    /// Returns the name of the Rust crate the symbol belongs to (see
+    /// [`crate_name`]). Symbols without a path (e.g. `#[no_mangle]` functions)
+    /// are attributed to the static library they were linked from if it is
+    /// known (`librust_lib.a` results in `rust_lib`). Returns `None` for
+    /// symbols that don't belong to Rust code (see [`Symbol::is_rust`]) or if
+    /// the crate can't be determined.
+    pub fn rust_crate(&self) -> Option<String> {
+        if !self.is_rust() {
+            return None;
+        }
+        if let Some(name) = crate_name(&self.demangled) {
+            return Some(String::from(name));
+        }
+
+        let stem = self.archive.as_ref()?.file_stem()?.to_str()?;
+        Some(String::from(stem.strip_prefix("lib").unwrap_or(stem)))
+    }
+
+    /// Returns the name of the Rust crate whose use of generic code caused the
+    /// symbol to be emitted. The crate is taken from the DWARF compilation unit
+    /// if known (see [`crate::dwarf::unit_crate`]) and from the generic
+    /// arguments of the demangled name otherwise (see [`instantiating_crate`]).
+    /// Falls back to the defining crate (see [`Symbol::rust_crate`]) for code
+    /// that isn't generic.
+    pub fn instantiating_crate(&self) -> Option<String> {
+        if !self.is_rust() {
+            return None;
+        }
+        if let Some(name) = self.unit.as_deref().and_then(unit_crate) {
+            return Some(String::from(name));
+        }
+        if let Some(name) = instantiating_crate(&self.demangled) {
+            return Some(String::from(name));
+        }
+
+        self.rust_crate()
+    }
+
+    /// Returns the name and the disambiguator of the Rust crate the symbol
+    /// belongs to. The disambiguator is taken from the name of v0 symbols (see
+    /// [`crate_version`]) or from the DWARF compilation unit containing the
+    /// symbol (see [`crate::dwarf::unit_crate_version`]) if it was generated
+    /// by the defining crate. Returns `None` if the disambiguator is unknown.
+    pub fn crate_version(&self) -> Option<(String, String)> {
+        if let Some(version) = crate_version(&self.mangled) {
+            return Some(version);
+        }
+        if !self.is_rust() {
+            return None;
+        }
+
+        let (name, version) = self.unit.as_deref().and_then(unit_crate_version)?;
+        if self.rust_crate().as_deref() == Some(name) {
+            Some((String::from(name), String::from(version)))
+        } else {
+            None
+        }
+    }
+
     /// Checks if two [`Symbol`]s are related. In the scope of this crate,
     /// two symbols are "related" if the following attributes are the same:
     /// - mangled name
@@ -123,15 +123,18 @@ mod symboltype_tests {
         assert_eq!(t.mem_region(), MemoryRegion::Rom);
         t = SymbolType::ReadOnlyDataSection;
         assert_eq!(t.mem_region(), MemoryRegion::Rom);
-        t = SymbolType::Weak;
-        assert_eq!(t.mem_region(), MemoryRegion::Rom);
+        t = SymbolType::Global;
+        assert_eq!(t.mem_region(), MemoryRegion::Ram);
+        t = SymbolType::UninitializedOrZeroInitialized;
+        assert_eq!(t.mem_region(), MemoryRegion::Ram);
     }
 
     #[test]
-    #[should_panic]
-    fn illegal_memory_region() {
-        let t = SymbolType::Global;
-        t.mem_region();
+    fn unknown_memory_region() {
+        // Weak symbols and absolutes can't be classified without their section.
+        assert_eq!(SymbolType::Weak.mem_region(), MemoryRegion::Unknown);
+        assert_eq!(SymbolType::TaggedWeak.mem_region(), MemoryRegion::Unknown);
+        assert_eq!(SymbolType::Absolute.mem_region(), MemoryRegion::Unknown);
     }
 }
 
@@ -148,6 +151,8 @@ mod symbollang_tests {
         assert_eq!(lang, SymbolLang::Cpp);
         let lang = SymbolLang::from_str("rust").unwrap();
         assert_eq!(lang, SymbolLang::Rust);
+        let lang = SymbolLang::from_str("asm").unwrap();
+        assert_eq!(lang, SymbolLang::Asm);
     }
 
     #[test]
@@ -303,6 +308,32 @@ mod rawsymbol_tests {
         assert_eq!(s.name, String::from("s"));
     }
 
+    #[test]
+    fn fromstr_64bit() {
+        let s = RawSymbol::from_str(
+            "ffff800000401134 0000000100000075 T _ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E",
+        );
+        assert!(s.is_ok());
+        let s = s.unwrap();
+        assert_eq!(s.addr, 0xffff_8000_0040_1134);
+        assert_eq!(s.size, 0x0000_0001_0000_0075);
+        assert_eq!(s.sym_type, SymbolType::TextSection);
+        assert_eq!(
+            s.name,
+            String::from("_ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E")
+        );
+    }
+
+    #[test]
+    fn fromstr_invalid_addr_length() {
+        let s = RawSymbol::from_str("0000000000401134 000000075 T main");
+        let err = s.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidSymbol);
+        let s = RawSymbol::from_str("000000000401134 0000000000000075 T main");
+        let err = s.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidSymbol);
+    }
+
     #[test]
     fn fromstr_invalid_addr() {
         let s = RawSymbol::from_str("000K08700 00000064 T net_if_up");
@@ -537,4 +568,113 @@ mod symbol_tests {
         .unwrap();
         assert!(!sym.related(&lib));
     }
+
+    #[test]
+    fn rust_crate() {
+        let mut sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T _ZN8heapless3vec12Vec$LT$T$GT$4push17h2e7465a2fecc1fa5E",
+            "00008700 00000064 T heapless::vec::Vec<T>::push",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        assert_eq!(sym.rust_crate(), Some(String::from("heapless")));
+
+        // The default language of mangled symbols without the Rust libraries
+        sym.lang = SymbolLang::Cpp;
+        assert_eq!(sym.rust_crate(), Some(String::from("heapless")));
+    }
+
+    #[test]
+    fn rust_crate_cpp() {
+        let sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T _ZN4core3fmt5writeEv",
+            "00008700 00000064 T core::fmt::write()",
+            SymbolLang::Cpp,
+        )
+        .unwrap();
+        assert!(!sym.is_rust());
+        assert_eq!(sym.rust_crate(), None);
+    }
+
+    #[test]
+    fn rust_crate_no_mangle() {
+        let mut sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T rust_mult",
+            "00008700 00000064 T rust_mult",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        assert_eq!(sym.rust_crate(), None);
+
+        sym.archive = Some(PathBuf::from("target/release/librust_lib.a"));
+        assert_eq!(sym.rust_crate(), Some(String::from("rust_lib")));
+    }
+
+    #[test]
+    fn instantiating_crate() {
+        let mut sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T _ZN4core3ptr13drop_in_place17h2e7465a2fecc1fa5E",
+            "00008700 00000064 T core::ptr::drop_in_place<app::Reading>",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        assert_eq!(sym.rust_crate(), Some(String::from("core")));
+        assert_eq!(sym.instantiating_crate(), Some(String::from("app")));
+
+        sym.unit = Some(String::from("src/lib.rs/@/rust_lib.1a2b3c-cgu.0"));
+        assert_eq!(sym.instantiating_crate(), Some(String::from("rust_lib")));
+
+        sym.mangled = String::from("_ZN4core3ptr13drop_in_placeEv");
+        sym.lang = SymbolLang::Cpp;
+        assert_eq!(sym.instantiating_crate(), None);
+    }
+
+    #[test]
+    fn crate_version() {
+        let sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T _RNvCsg9FFvDQaDFm_7ringbuf8checksum",
+            "00008700 00000064 T ringbuf::checksum",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        assert_eq!(
+            sym.crate_version(),
+            Some((String::from("ringbuf"), String::from("bc2dcf194c61c5f2")))
+        );
+
+        let mut sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T _ZN7ringbuf8checksum17h78e340d3d2b7b109E",
+            "00008700 00000064 T ringbuf::checksum",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        assert_eq!(sym.crate_version(), None);
+
+        sym.unit = Some(String::from("ringbuf.rs/@/ringbuf.a3789542a9d61af9-cgu.0"));
+        assert_eq!(
+            sym.crate_version(),
+            Some((String::from("ringbuf"), String::from("a3789542a9d61af9")))
+        );
+
+        // Code instantiated by another crate doesn't reveal the version.
+        sym.unit = Some(String::from(
+            "rust_lib.rs/@/rust_lib.20c98bf90d7eae5a-cgu.0",
+        ));
+        assert_eq!(sym.crate_version(), None);
+    }
+
+    #[test]
+    fn instantiating_crate_not_generic() {
+        let mut sym = Symbol::from_rawsymbols_lang(
+            "00008700 00000064 T _ZN4core3fmt9Formatter3pad17h2e7465a2fecc1fa5E",
+            "00008700 00000064 T core::fmt::Formatter::pad",
+            SymbolLang::Rust,
+        )
+        .unwrap();
+        assert_eq!(sym.instantiating_crate(), Some(String::from("core")));
+
+        // Compilation units of other compilers don't contain a crate.
+        sym.unit = Some(String::from("app.c"));
+        assert_eq!(sym.instantiating_crate(), Some(String::from("core")));
+    }
 }
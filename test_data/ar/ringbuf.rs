@@ -0,0 +1,34 @@
+#![no_std]
+
+pub struct RingBuf {
+    buf: [u8; 16],
+    head: usize,
+    len: usize,
+}
+
+impl RingBuf {
+    pub const fn new() -> Self {
+        RingBuf {
+            buf: [0; 16],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    #[inline(never)]
+    pub fn push(&mut self, b: u8) {
+        let idx = (self.head + self.len) % self.buf.len();
+        self.buf[idx] = b;
+        if self.len < self.buf.len() {
+            self.len += 1;
+        } else {
+            self.head = (self.head + 1) % self.buf.len();
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn ringbuf_checksum(data: *const u8, len: usize) -> u32 {
+    let data = unsafe { core::slice::from_raw_parts(data, len) };
+    data.iter().fold(0u32, |acc, b| acc.rotate_left(5) ^ u32::from(*b))
+}
@@ -0,0 +1,37 @@
+// Old build of the application used for testing the diff of two builds.
+// Compile with `gcc -O0 -nostdlib -static -no-pie -Wl,--build-id=none -T link.ld app_old.c -o app_old`
+// Print symbols with `nm --print-size --size-sort app_old`
+
+const char c_app_rodata_arr[] = "This array is shortened in the new build.";
+char c_app_data_arr[] = "This array stays the same.";
+static char c_app_bss_arr[64];
+
+extern char _sidata[], _sdata[], _edata[], _sbss[], _ebss[];
+
+int add(int a, int b)
+{
+    return a + b;
+}
+
+int removed_sub(int a, int b)
+{
+    return a - b;
+}
+
+void _start(void)
+{
+    char *src = _sidata;
+    char *dst = _sdata;
+
+    while (dst < _edata) {
+        *dst++ = *src++;
+    }
+    for (dst = _sbss; dst < _ebss; dst++) {
+        *dst = 0;
+    }
+
+    c_app_bss_arr[0] = c_app_data_arr[add(1, 2)] + c_app_rodata_arr[removed_sub(3, 2)];
+
+    for (;;) {
+    }
+}
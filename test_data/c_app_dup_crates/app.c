@@ -0,0 +1,19 @@
+// Build two versions of the ringbuf crate and link both into the application.
+// The legacy mangled application contains debug information:
+// rustc --crate-type=rlib --edition=2021 -C opt-level=1 -C panic=abort -g -C metadata=v1 ringbuf.rs -o libringbuf1.rlib
+// rustc --crate-type=rlib --edition=2021 -C opt-level=1 -C panic=abort -g -C metadata=v2 ringbuf.rs -o libringbuf2.rlib
+// rustc --crate-type=staticlib --edition=2021 -C opt-level=1 -C panic=abort -g --extern ringbuf_v1=libringbuf1.rlib --extern ringbuf_v2=libringbuf2.rlib rust_lib.rs -o librust_lib.a
+// gcc -O0 -g -no-pie -Wl,--gc-sections app.c librust_lib.a -o app
+//
+// The v0 mangled application is built without debug information by adding
+// `-C symbol-mangling-version=v0` and removing `-g` from the commands above.
+// The result is linked to `app_v0`.
+//
+// Print symbols with `nm --print-size --size-sort -C app`
+
+extern unsigned int rust_checksum(unsigned char a, unsigned char b);
+
+int main(int argc, char **argv)
+{
+    return rust_checksum(argc, 5);
+}
@@ -0,0 +1,28 @@
+#![no_std]
+
+pub struct RingBuf {
+    data: [u8; 16],
+    head: usize,
+}
+
+impl RingBuf {
+    pub const fn new() -> Self {
+        RingBuf { data: [0; 16], head: 0 }
+    }
+
+    #[inline(never)]
+    pub fn push(&mut self, value: u8) {
+        self.data[self.head % 16] = value;
+        self.head = self.head.wrapping_add(1);
+    }
+
+    #[inline(never)]
+    pub fn sum(&self) -> u32 {
+        self.data.iter().map(|b| *b as u32).sum()
+    }
+}
+
+#[inline(never)]
+pub fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0, |acc, b| acc ^ b)
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+#[no_mangle]
+pub extern "C" fn rust_checksum(a: u8, b: u8) -> u32 {
+    let mut old = ringbuf_v1::RingBuf::new();
+    let mut new = ringbuf_v2::RingBuf::new();
+    old.push(a);
+    new.push(b);
+    old.sum() + new.sum() + ringbuf_v1::checksum(&[a, b]) as u32
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,28 @@
+// Build the libraries and link the application with debug information:
+// g++ -O0 -g -ffunction-sections -fno-exceptions -c cpp_lib.cpp -o cpp_lib.o
+// gcc -g -c asm_lib.S -o asm_lib.o
+// rustc --crate-type=staticlib --edition=2021 -C opt-level=1 -C panic=abort -g rust_lib.rs -o librust_lib.a
+// gcc -O0 -g -no-pie -Wl,--gc-sections app.c cpp_lib.o asm_lib.o librust_lib.a -o app
+//
+// Print symbols with `nm --print-size --size-sort app`
+// Print the compilation units with `readelf --debug-dump=info app`
+
+extern int cpp_add(int a, int b);
+extern int asm_double(int a);
+extern int rust_mult(int a, int b);
+
+static char c_app_static_arr[] = "This is a static array that should be placed in the initialized data section.";
+
+int c_sub(int a, int b)
+{
+    return a - b;
+}
+
+int main(void)
+{
+    int a = cpp_add(1, 2);
+    int b = asm_double(a);
+    int c = rust_mult(a, b);
+
+    return c_sub(c, c_app_static_arr[0]);
+}
@@ -0,0 +1,20 @@
+namespace cpp_lib {
+
+class Accumulator {
+public:
+    Accumulator(int start) : sum(start) {}
+    void add(int x) { sum += x; }
+    int get() const { return sum; }
+
+private:
+    int sum;
+};
+
+} // namespace cpp_lib
+
+extern "C" int cpp_add(int a, int b)
+{
+    cpp_lib::Accumulator acc(a);
+    acc.add(b);
+    return acc.get();
+}
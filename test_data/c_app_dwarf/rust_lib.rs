@@ -0,0 +1,23 @@
+#![no_std]
+
+#[no_mangle]
+pub static mut RUST_LIB_RESULTS: [i32; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+#[inline(never)]
+fn mult(a: i32, b: i32) -> i32 {
+    a.wrapping_mul(b)
+}
+
+#[no_mangle]
+pub extern "C" fn rust_mult(a: i32, b: i32) -> i32 {
+    let res = mult(a, b);
+    unsafe {
+        RUST_LIB_RESULTS[(a & 7) as usize] = res;
+    }
+    res
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
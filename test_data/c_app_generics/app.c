@@ -0,0 +1,14 @@
+// Build the libraries and link the application with debug information:
+// g++ -g -O1 -fno-inline -ffunction-sections -fno-exceptions -c cpp_lib.cpp -o cpp_lib.o
+// rustc --crate-type=staticlib --edition=2021 -C opt-level=1 -C panic=abort -g rust_lib.rs -o librust_lib.a
+// gcc -O0 -g -no-pie -Wl,--gc-sections app.c cpp_lib.o librust_lib.a -o app
+//
+// Print symbols with `nm --print-size --size-sort -C app`
+
+extern long cpp_sum(int a, long b);
+extern long rust_largest(int a, unsigned char b);
+
+int main(int argc, char **argv)
+{
+    return (int)(cpp_sum(argc, 3) + rust_largest(argc, 5));
+}
@@ -0,0 +1,43 @@
+namespace cpp_lib {
+
+template <typename T, int N>
+class Buffer {
+public:
+    void push(T value)
+    {
+        if (len < N) {
+            data[len++] = value;
+        }
+    }
+    T sum() const
+    {
+        T s = 0;
+        for (int i = 0; i < len; i++) {
+            s += data[i];
+        }
+        return s;
+    }
+
+private:
+    T data[N];
+    int len = 0;
+};
+
+template <typename T>
+T clamp(T value, T lo, T hi)
+{
+    return value < lo ? lo : (value > hi ? hi : value);
+}
+
+} // namespace cpp_lib
+
+extern "C" long cpp_sum(int a, long b)
+{
+    cpp_lib::Buffer<int, 4> ints;
+    cpp_lib::Buffer<long, 8> longs;
+    cpp_lib::Buffer<char, 2> chars;
+    ints.push(cpp_lib::clamp(a, 0, 100));
+    longs.push(cpp_lib::clamp(b, 0L, 1000L));
+    chars.push(cpp_lib::clamp<char>(a, 'a', 'z'));
+    return ints.sum() + longs.sum() + chars.sum();
+}
@@ -0,0 +1,25 @@
+#![no_std]
+
+#[inline(never)]
+fn largest<T: PartialOrd + Copy>(items: &[T]) -> T {
+    let mut largest = items[0];
+    for &item in items {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+#[no_mangle]
+pub extern "C" fn rust_largest(a: i32, b: u8) -> i64 {
+    let ints = [a, a / 2, a * 3];
+    let bytes = [b, b / 3, b.wrapping_mul(2)];
+    let longs = [a as i64, -(a as i64), 7];
+    largest(&ints) as i64 + largest(&bytes) as i64 + largest(&longs)
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,34 @@
+// Compile with `gcc -O0 -nostdlib -static -no-pie -Wl,--build-id=none -T link.ld app.c -o app`
+// Print symbols with `nm --print-size --size-sort app`
+//
+// The linker script places the initialized data in RAM but loads it from
+// FLASH (i.e. VMA != LMA) like it is done for microcontrollers.
+
+const char c_app_rodata_arr[] = "This is a constant array that should be placed in the read-only data section.";
+char c_app_data_arr[] = "This array is initialized and gets copied from FLASH to RAM.";
+static char c_app_bss_arr[64];
+
+extern char _sidata[], _sdata[], _edata[], _sbss[], _ebss[];
+
+int add(int a, int b)
+{
+    return a + b;
+}
+
+void _start(void)
+{
+    char *src = _sidata;
+    char *dst = _sdata;
+
+    while (dst < _edata) {
+        *dst++ = *src++;
+    }
+    for (dst = _sbss; dst < _ebss; dst++) {
+        *dst = 0;
+    }
+
+    c_app_bss_arr[0] = c_app_data_arr[add(1, 2)] + c_app_rodata_arr[0];
+
+    for (;;) {
+    }
+}
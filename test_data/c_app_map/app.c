@@ -0,0 +1,20 @@
+// Compile with `gcc -O0 -ffunction-sections -fdata-sections -c app.c -o app.o`
+// Link with GNU ld using `gcc -nostdlib -static -no-pie -Wl,--build-id=none -T ../c_app_lma/link.ld -Wl,-Map=app.map app.o -Llibs -lmath -o app`
+// Link with LLD using `gcc -nostdlib -static -no-pie -Wl,--build-id=none -B <dir containing ld.lld> -fuse-ld=lld -T ../c_app_lma/link.ld -Wl,-Map=app_lld.map app.o -Llibs -lmath -o app_lld`
+// Print symbols with `nm --print-size --size-sort app`
+
+extern int lib_mult(int a, int b);
+extern const char *lib_name(void);
+
+char c_app_data_arr[] = "This array is initialized and gets copied from FLASH to RAM.";
+static char c_app_bss_arr[64];
+
+void _start(void)
+{
+    const char *name = lib_name();
+
+    c_app_bss_arr[0] = c_app_data_arr[lib_mult(1, 2)] + name[0];
+
+    for (;;) {
+    }
+}
@@ -0,0 +1,16 @@
+// Compile with `gcc -O0 -fcommon -ffunction-sections -fdata-sections -c lib.c -o libs/lib.o`
+// and archive with `ar rcs libs/libmath.a libs/lib.o`
+
+int lib_common_counter;
+
+int lib_mult(int a, int b)
+{
+    lib_common_counter++;
+    return a * b;
+}
+
+// The alignment forces the linker to insert fill bytes in front of it.
+__attribute__((aligned(16))) const char *lib_name(void)
+{
+    return "libmath";
+}
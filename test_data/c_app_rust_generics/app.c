@@ -0,0 +1,12 @@
+// Build the library and link the application with debug information:
+// rustc --crate-type=staticlib --edition=2021 -C opt-level=0 -C panic=abort -g rust_lib.rs -o librust_lib.a
+// gcc -O0 -g -no-pie -Wl,--gc-sections app.c librust_lib.a -o app
+//
+// Print symbols with `nm --print-size --size-sort -C app`
+
+extern unsigned short rust_sorted_max(unsigned short a, unsigned short b);
+
+int main(int argc, char **argv)
+{
+    return rust_sorted_max(argc, 5);
+}
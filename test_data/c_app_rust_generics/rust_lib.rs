@@ -0,0 +1,19 @@
+#![no_std]
+
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+pub struct Reading(u16);
+
+#[no_mangle]
+pub extern "C" fn rust_sorted_max(a: u16, b: u16) -> u16 {
+    let mut readings = [Reading(a), Reading(b), Reading(3), Reading(9)];
+    readings.sort_unstable_by(|x, y| x.partial_cmp(y).unwrap());
+    readings[3].0
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[no_mangle]
+pub extern "C" fn rust_eh_personality() {}
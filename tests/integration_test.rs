@@ -183,13 +183,13 @@ fn detect_permission_denied() {
 
 #[test]
 fn new_atlas() {
-    let at = Atlas::new(&*NM_PATH, file!());
+    let at = Atlas::new(file!());
     assert!(at.is_ok());
 }
 
 #[test]
 fn elf_not_found() {
-    let err = Atlas::new(&*NM_PATH, "kljsdflkjsdf").unwrap_err();
+    let err = Atlas::new("kljsdflkjsdf").unwrap_err();
     assert_eq!(err.kind(), ErrorKind::Io);
     let cause = err.into_cause().unwrap();
     let original_error = cause.downcast::<std::io::Error>().unwrap();
@@ -200,7 +200,7 @@ fn elf_not_found() {
 // arm-none-eabi-nm --print-size --size-sort --demangle rust_minimal_node.elf
 #[test]
 fn largest_syms() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -223,7 +223,7 @@ fn largest_syms() {
 // Rust symbols.
 #[test]
 fn filter_rust() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -254,7 +254,7 @@ fn filter_rust() {
 // Extract the three largest symbols in the ROM region by hand.
 #[test]
 fn filter_memregion() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -264,7 +264,7 @@ fn filter_memregion() {
         .unwrap()
         .iter()
         .rev()
-        .filter(|s| s.sym_type.mem_region() == MemoryRegion::Rom)
+        .filter(|s| s.mem_region == MemoryRegion::Rom)
         .take(3);
     let s = iter.next().unwrap();
     assert_eq!(s.addr, 0x000013ec);
@@ -284,7 +284,7 @@ fn filter_memregion() {
 // [0x00000304;0x00000400[ and the type "t" or "T".
 #[test]
 fn filter_complex() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -324,7 +324,7 @@ fn filter_complex() {
 // added to check if modification down the line change their outputs.
 #[test]
 fn report_lang_size() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -382,7 +382,7 @@ fn report_lang_size() {
 // See `report_lang_size`.
 #[test]
 fn report_lang_size_pct() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -406,7 +406,7 @@ fn report_lang_size_pct() {
 
 #[test]
 fn report_syms() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -428,7 +428,7 @@ fn report_syms() {
 
 #[test]
 fn report_syms_no_maxcount() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -440,7 +440,7 @@ fn report_syms_no_maxcount() {
 
 #[test]
 fn report_syms_single_lang() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());
@@ -453,7 +453,7 @@ fn report_syms_single_lang() {
 
 #[test]
 fn report_syms_double_lang() {
-    let mut at = Atlas::new(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
+    let mut at = Atlas::with_nm(&*NM_PATH, "test_data/rust_minimal_node.elf").unwrap();
     at.add_lib(SymbolLang::Rust, "test_data/libsecprint.a")
         .unwrap();
     assert!(at.analyze().is_ok());